@@ -188,6 +188,80 @@ impl AsyncTuiTestHarness {
             .await
     }
 
+    /// Waits until the screen contents stop changing for `quiet_for`.
+    ///
+    /// Useful after input that triggers a burst of redraws, when there is no
+    /// specific text to wait for. Polling uses Tokio timers and the PTY reads
+    /// run on the blocking pool, so long quiescence waits yield to the
+    /// runtime. A process that exits counts as stable once its last output
+    /// has been processed.
+    ///
+    /// # Arguments
+    ///
+    /// * `quiet_for` - How long the screen must stay unchanged
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Timeout` error if the screen keeps changing past the
+    /// harness's configured timeout.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # #[cfg(feature = "async-tokio")]
+    /// # async fn test() -> ratatui_testlib::Result<()> {
+    /// use std::time::Duration;
+    ///
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::AsyncTuiTestHarness;
+    ///
+    /// let mut harness = AsyncTuiTestHarness::new(80, 24).await?;
+    /// harness.spawn(CommandBuilder::new("my-app")).await?;
+    ///
+    /// harness.wait_for_stable(Duration::from_millis(300)).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_stable(&mut self, quiet_for: Duration) -> Result<()> {
+        let inner = self.inner.clone();
+        let timeout = spawn_blocking(move || inner.lock().unwrap().wait_timeout()).await?;
+
+        let start = tokio::time::Instant::now();
+        let poll_interval = Duration::from_millis(50)
+            .min(quiet_for)
+            .max(Duration::from_millis(1));
+        let mut interval = tokio::time::interval(poll_interval);
+        let mut last_hash: Option<u64> = None;
+        let mut unchanged_since = start;
+
+        loop {
+            interval.tick().await;
+
+            let harness = self.inner.clone();
+            let hash = spawn_blocking(move || {
+                let mut h = harness.lock().unwrap();
+                match h.update_state() {
+                    Ok(_) | Err(TermTestError::ProcessExited) => {}
+                    Err(e) => return Err(e),
+                }
+                Ok(h.state().content_hash())
+            })
+            .await??;
+
+            let now = tokio::time::Instant::now();
+            if last_hash != Some(hash) {
+                last_hash = Some(hash);
+                unchanged_since = now;
+            } else if now.duration_since(unchanged_since) >= quiet_for {
+                return Ok(());
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(TermTestError::Timeout { timeout_ms: timeout.as_millis() as u64 });
+            }
+        }
+    }
+
     /// Waits for a condition with custom configuration.
     ///
     /// Returns a builder for configuring the wait operation.
@@ -368,3 +442,25 @@ impl AsyncWaitAnyBuilder {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_wait_for_stable() -> Result<()> {
+        let mut harness = AsyncTuiTestHarness::new(80, 24).await?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("for i in 1 2 3 4 5; do echo line$i; sleep 0.05; done; sleep 3");
+        harness.spawn(cmd).await?;
+
+        let start = std::time::Instant::now();
+        harness.wait_for_stable(Duration::from_millis(400)).await?;
+
+        // The burst is complete, and stability didn't wait for the process to exit
+        assert!(harness.screen_contents().await.contains("line5"));
+        assert!(start.elapsed() < Duration::from_secs(3));
+        Ok(())
+    }
+}
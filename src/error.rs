@@ -59,6 +59,9 @@ pub type Result<T> = std::result::Result<T, TermTestError>;
 /// - [`TermTestError::ProcessAlreadyRunning`]: Attempt to spawn when a process is already running
 /// - [`TermTestError::NoProcessRunning`]: Attempt to interact with a non-existent process
 /// - [`TermTestError::InvalidDimensions`]: Invalid terminal size parameters
+/// - [`TermTestError::OutputLimitExceeded`]: PTY output exceeded the configured limit
+/// - [`TermTestError::OutputClosed`]: The process closed its output but kept running
+/// - [`TermTestError::GlobalDeadlineExceeded`]: A harness ran past its overall deadline
 /// - `Bevy`: Bevy ECS-related errors (requires `bevy` feature)
 #[derive(Debug, Error)]
 pub enum TermTestError {
@@ -182,6 +185,46 @@ pub enum TermTestError {
     #[error("Child process has exited")]
     ProcessExited,
 
+    /// Output limit exceeded.
+    ///
+    /// This error is returned when a harness configured with a maximum output size
+    /// reads more bytes from the PTY than allowed. It protects test runs from
+    /// runaway processes stuck in a print loop.
+    #[error("Output limit exceeded: read {read_bytes} bytes, limit is {limit_bytes} bytes")]
+    OutputLimitExceeded {
+        /// Configured limit in bytes.
+        limit_bytes: usize,
+        /// Total bytes read when the limit was hit.
+        read_bytes: usize,
+    },
+
+    /// Process closed its output but is still running.
+    ///
+    /// This error is returned by harnesses configured with
+    /// `TuiTestHarness::with_eof_timeout` when reading the PTY has hit
+    /// end-of-file for longer than the configured time while the process
+    /// stays alive. Nothing the process does can change the screen any more,
+    /// so waiting for the full timeout would be pointless.
+    #[error("Process closed its output but kept running for {timeout_ms}ms")]
+    OutputClosed {
+        /// Configured EOF timeout in milliseconds.
+        timeout_ms: u64,
+    },
+
+    /// Harness ran past its global deadline.
+    ///
+    /// This error is returned by harnesses configured with
+    /// `TuiTestHarness::with_global_deadline` once the deadline has passed,
+    /// from whichever operation noticed it first. The child process is killed,
+    /// and the message includes the harness diagnostics at that point.
+    #[error("Global deadline of {deadline_ms}ms exceeded\n\n{dump}")]
+    GlobalDeadlineExceeded {
+        /// Configured deadline in milliseconds.
+        deadline_ms: u64,
+        /// Harness diagnostics (see `TuiTestHarness::dump`).
+        dump: String,
+    },
+
     /// Bevy ECS-specific errors.
     ///
     /// This error occurs for Bevy-related failures when using the `bevy` feature,
@@ -321,6 +364,28 @@ mod tests {
         assert!(msg.contains("Child process"));
     }
 
+    #[test]
+    fn test_output_limit_exceeded_error() {
+        let err = TermTestError::OutputLimitExceeded { limit_bytes: 1024, read_bytes: 4096 };
+        let msg = err.to_string();
+
+        assert!(msg.contains("Output limit exceeded"));
+        assert!(msg.contains("4096"));
+        assert!(msg.contains("1024"));
+    }
+
+    #[test]
+    fn test_global_deadline_exceeded_error() {
+        let err = TermTestError::GlobalDeadlineExceeded {
+            deadline_ms: 2000,
+            dump: "Screen state:\nLoading".to_string(),
+        };
+        let msg = err.to_string();
+
+        assert!(msg.contains("Global deadline of 2000ms exceeded"));
+        assert!(msg.ends_with("Screen state:\nLoading"));
+    }
+
     #[cfg(feature = "sixel")]
     #[test]
     fn test_sixel_validation_error() {
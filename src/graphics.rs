@@ -164,6 +164,16 @@ impl GraphicsProtocol {
     }
 }
 
+impl From<crate::screen::ImageProtocol> for GraphicsProtocol {
+    fn from(protocol: crate::screen::ImageProtocol) -> Self {
+        match protocol {
+            crate::screen::ImageProtocol::Sixel => GraphicsProtocol::Sixel,
+            crate::screen::ImageProtocol::Kitty => GraphicsProtocol::Kitty,
+            crate::screen::ImageProtocol::ITerm2 => GraphicsProtocol::ITerm2,
+        }
+    }
+}
+
 impl std::fmt::Display for GraphicsProtocol {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.name())
@@ -42,7 +42,8 @@
 use std::{
     fs::File,
     io::Write,
-    path::Path,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
     time::{Duration, Instant},
 };
 
@@ -55,8 +56,8 @@ use crate::{
         MouseEvent, ScrollDirection,
     },
     pty::TestTerminal,
-    screen::ScreenState,
-    terminal_profiles::{Feature, TerminalCapabilities, TerminalProfile},
+    screen::{ScreenState, TerminalQuery},
+    terminal_profiles::{Feature, TerminalCapabilities, TerminalIdentity, TerminalProfile},
     timing::{fps_to_frame_budget, LatencyProfile, TimingHooks, TimingRecorder},
 };
 
@@ -192,6 +193,59 @@ pub enum RecordedEvent {
     },
 }
 
+/// A single step in a timed input script.
+///
+/// Used with [`TuiTestHarness::run_script`] to describe an interaction as
+/// declarative data. Each action is paired with a delay that elapses (while
+/// output keeps being pumped) before the action runs.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use ratatui_testlib::{KeyCode, ScriptAction, TuiTestHarness};
+///
+/// # fn test() -> ratatui_testlib::Result<()> {
+/// let mut harness = TuiTestHarness::new(80, 24)?;
+/// // ... spawn application ...
+///
+/// harness.run_script(&[
+///     (Duration::ZERO, ScriptAction::WaitForText("Ready".into())),
+///     (Duration::from_millis(100), ScriptAction::SendText("query".into())),
+///     (Duration::from_millis(50), ScriptAction::SendKey(KeyCode::Enter)),
+///     (Duration::from_millis(200), ScriptAction::Expect("Results".into())),
+/// ])?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptAction {
+    /// Send raw text to the PTY (see [`TuiTestHarness::send_text`]).
+    SendText(String),
+    /// Send a single key (see [`TuiTestHarness::send_key`]).
+    SendKey(KeyCode),
+    /// Wait until the text appears, using the configured timeout.
+    WaitForText(String),
+    /// Assert that the text is currently on screen, without waiting.
+    Expect(String),
+}
+
+/// How wait operations pause between screen checks.
+///
+/// See [`TuiTestHarness::with_poll_strategy`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PollStrategy {
+    /// Sleep for the full poll interval between checks (the default).
+    #[default]
+    Sleep,
+    /// Wake as soon as the PTY has output, waiting at most the poll interval.
+    ///
+    /// This reduces latency when the poll interval is long and avoids waking
+    /// up when nothing has changed.
+    Readable,
+}
+
 /// A timestamped recorded event.
 #[derive(Debug, Clone)]
 struct TimestampedEvent {
@@ -250,6 +304,37 @@ pub struct TuiTestHarness {
     // Timing and latency profiling
     timing_recorder: TimingRecorder,
     latency_profile: LatencyProfile,
+    // Output limiting
+    max_output_bytes: Option<usize>,
+    output_bytes_read: usize,
+    // Environment variables set on and removed from spawned processes
+    env: Vec<(String, String)>,
+    unset_env: Vec<String>,
+    poll_strategy: PollStrategy,
+    // Command line of the most recent spawn, for diagnostics
+    last_command: Option<String>,
+    // Stderr capture outside the PTY
+    separate_stderr: bool,
+    stderr_path: Option<PathBuf>,
+    // Isolated HOME directory for spawned processes
+    temp_home: bool,
+    temp_home_path: Option<PathBuf>,
+    // Feed sent text into the screen state as if echoed
+    local_echo: bool,
+    // Answer terminal queries such as cursor-position requests
+    auto_responses: bool,
+    // Replies to device attribute and DECRQSS queries
+    terminal_identity: Option<TerminalIdentity>,
+    // Diagnostic hints collected while processing output
+    hints: Vec<String>,
+    // How long output may stay at EOF while the process runs
+    eof_timeout: Option<Duration>,
+    eof_since: Option<Instant>,
+    // Number of polls made by the most recent wait
+    last_wait_polls: usize,
+    // Backstop deadline across all operations, and when it runs out
+    global_deadline: Option<Duration>,
+    deadline_at: Option<Instant>,
 }
 
 impl TuiTestHarness {
@@ -281,6 +366,25 @@ impl TuiTestHarness {
             terminal_profile: TerminalProfile::default(),
             timing_recorder: TimingRecorder::new(),
             latency_profile: LatencyProfile::new(),
+            max_output_bytes: None,
+            output_bytes_read: 0,
+            env: Vec::new(),
+            unset_env: Vec::new(),
+            poll_strategy: PollStrategy::default(),
+            last_command: None,
+            separate_stderr: false,
+            stderr_path: None,
+            temp_home: false,
+            temp_home_path: None,
+            local_echo: false,
+            auto_responses: false,
+            terminal_identity: None,
+            hints: Vec::new(),
+            eof_timeout: None,
+            eof_since: None,
+            last_wait_polls: 0,
+            global_deadline: None,
+            deadline_at: None,
         })
     }
 
@@ -303,6 +407,60 @@ impl TuiTestHarness {
         TuiTestHarnessBuilder::default()
     }
 
+    /// Creates a test harness from a [`HarnessConfig`].
+    ///
+    /// This is equivalent to the builder but takes all settings at once, which
+    /// makes it easy to share one configuration between tests.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - The harness configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if terminal creation fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use ratatui_testlib::{HarnessConfig, TuiTestHarness};
+    ///
+    /// let config = HarnessConfig {
+    ///     size: (100, 30),
+    ///     timeout: Duration::from_secs(10),
+    ///     env: vec![("NO_COLOR".into(), "1".into())],
+    ///     ..HarnessConfig::default()
+    /// };
+    ///
+    /// let harness = TuiTestHarness::from_config(config.clone())?;
+    /// let other = TuiTestHarness::from_config(config)?;
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn from_config(config: HarnessConfig) -> Result<Self> {
+        TuiTestHarnessBuilder {
+            width: config.size.0,
+            height: config.size.1,
+            timeout: config.timeout,
+            poll_interval: config.poll_interval,
+            buffer_size: config.buffer_size,
+            terminal_profile: config.terminal_profile,
+            max_output_bytes: config.max_output_bytes,
+            env: config.env,
+            unset_env: config.unset_env,
+            poll_strategy: config.poll_strategy,
+            separate_stderr: config.separate_stderr,
+            temp_home: config.temp_home,
+            local_echo: config.local_echo,
+            auto_responses: config.auto_responses,
+            terminal_identity: config.terminal_identity,
+            eof_timeout: config.eof_timeout,
+            global_deadline: config.global_deadline,
+        }
+        .build()
+    }
+
     /// Sets the timeout for wait operations.
     ///
     /// # Arguments
@@ -313,6 +471,12 @@ impl TuiTestHarness {
         self
     }
 
+    /// Returns the configured timeout for wait operations.
+    #[cfg(feature = "async-tokio")]
+    pub(crate) fn wait_timeout(&self) -> Duration {
+        self.timeout
+    }
+
     /// Sets the polling interval for wait operations.
     ///
     /// # Arguments
@@ -323,6 +487,375 @@ impl TuiTestHarness {
         self
     }
 
+    /// Sets how wait operations pause between screen checks.
+    ///
+    /// With [`PollStrategy::Readable`], waits wake up as soon as the PTY has output
+    /// instead of always sleeping for the full poll interval.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The poll strategy to use
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{PollStrategy, TuiTestHarness};
+    ///
+    /// let harness = TuiTestHarness::new(80, 24)?.with_poll_strategy(PollStrategy::Readable);
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn with_poll_strategy(mut self, strategy: PollStrategy) -> Self {
+        self.poll_strategy = strategy;
+        self
+    }
+
+    /// Limits the total number of bytes read from the PTY.
+    ///
+    /// Once the cumulative output exceeds the limit, [`update_state`](Self::update_state)
+    /// stops draining and returns [`TermTestError::OutputLimitExceeded`]. Use this to
+    /// keep a runaway process (e.g., one stuck in a print loop) from hanging a test.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Maximum number of output bytes to accept
+    pub fn with_max_output_bytes(mut self, limit: usize) -> Self {
+        self.max_output_bytes = Some(limit);
+        self
+    }
+
+    /// Captures the stderr of spawned processes separately from the PTY.
+    ///
+    /// Normally stdout and stderr both go to the PTY and are indistinguishable on
+    /// screen. With this option, [`spawn`](Self::spawn) runs the command through
+    /// `sh` with stderr redirected to a temporary file, whose contents are
+    /// available from [`stderr_contents`](Self::stderr_contents).
+    ///
+    /// This changes the child's file descriptor layout: stderr is no longer a
+    /// tty, so programs that check `isatty(2)` (e.g., to decide whether to color
+    /// their diagnostics) may behave differently. Requires `sh` on the `PATH`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?.with_separate_stderr();
+    /// harness.spawn(CommandBuilder::new("my-app"))?;
+    /// harness.wait_for_text("Ready")?;
+    ///
+    /// assert!(!harness.stderr_contents().contains("panicked"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_separate_stderr(mut self) -> Self {
+        self.separate_stderr = true;
+        self
+    }
+
+    /// Runs spawned processes with an isolated temporary home directory.
+    ///
+    /// Many applications read configuration from `$HOME`; with this option,
+    /// [`spawn`](Self::spawn) points `HOME` (and `USERPROFILE` on Windows) at an
+    /// empty temporary directory, so tests never pick up the developer's real
+    /// configuration. The directory is created on the first spawn, shared by
+    /// later spawns, and removed when the harness is dropped. Variables set
+    /// explicitly with [`TuiTestHarnessBuilder::with_env`] take precedence.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?.with_temp_home();
+    /// harness.spawn(CommandBuilder::new("my-app"))?;
+    ///
+    /// let home = harness.temp_home().unwrap();
+    /// assert!(!home.join(".config/my-app/config.toml").exists());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_temp_home(mut self) -> Self {
+        self.temp_home = true;
+        self
+    }
+
+    /// Enables or disables local echo of sent text.
+    ///
+    /// Applications in raw mode usually disable terminal echo, so text sent
+    /// with [`send_text`](Self::send_text) never appears on screen and tests
+    /// can't confirm what was typed. With local echo enabled, sent text is also
+    /// fed into the harness's [`ScreenState`] at the cursor, as if the terminal
+    /// had echoed it, while still being written to the PTY unchanged.
+    ///
+    /// This is a test-only simulation: the application never sees the echo,
+    /// and its own output may overwrite or interleave with the echoed text.
+    /// Newlines are echoed as `\r\n`. Keys sent with [`send_key`](Self::send_key)
+    /// are not echoed.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?.with_local_echo(true);
+    /// harness.spawn(CommandBuilder::new("my-raw-mode-app"))?;
+    ///
+    /// harness.send_text("secret")?;
+    /// assert!(harness.screen_contents().contains("secret"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_local_echo(mut self, enabled: bool) -> Self {
+        self.local_echo = enabled;
+        self
+    }
+
+    /// Enables or disables automatic answers to terminal queries.
+    ///
+    /// Some applications request the cursor position (DSR, `ESC [ 6 n`) and
+    /// block until the terminal replies. A real terminal answers immediately;
+    /// with this option the harness does too, writing the cursor-position report
+    /// (`ESC [ row ; col R`, 1-based) for the position at the time of the
+    /// request while processing output in [`update_state`](Self::update_state).
+    ///
+    /// When disabled (the default), unanswered requests are reported through
+    /// [`hints`](Self::hints) instead, since an application waiting for a reply
+    /// otherwise just looks hung.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?.with_auto_responses(true);
+    /// harness.spawn(CommandBuilder::new("my-app"))?;
+    /// harness.wait_for_text("Ready")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_auto_responses(mut self, enabled: bool) -> Self {
+        self.auto_responses = enabled;
+        self
+    }
+
+    /// Sets what the terminal reports itself to be.
+    ///
+    /// Applications that branch on the terminal type send identification
+    /// queries: Primary and Secondary Device Attributes (`ESC [ c`,
+    /// `ESC [ > c`) and DECRQSS (`ESC P $ q Pt ESC \`). With an identity set,
+    /// the harness answers them from it while processing output in
+    /// [`update_state`](Self::update_state), so the same application can be
+    /// tested against several terminals.
+    ///
+    /// Without an identity, these queries are answered as
+    /// [`TerminalIdentity::default()`] when
+    /// [`with_auto_responses`](Self::with_auto_responses) is enabled, and
+    /// reported through [`hints`](Self::hints) otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::{TerminalIdentity, TuiTestHarness};
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness =
+    ///     TuiTestHarness::new(80, 24)?.with_terminal_identity(TerminalIdentity::vt340());
+    /// harness.spawn(CommandBuilder::new("my-app"))?;
+    /// harness.wait_for_text("Sixel: on")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_terminal_identity(mut self, identity: TerminalIdentity) -> Self {
+        self.terminal_identity = Some(identity);
+        self
+    }
+
+    /// Fails waits early when the process closes its output but keeps running.
+    ///
+    /// Some daemons close stdout after starting up. Reads then hit end-of-file
+    /// while the process lives on, and a wait for text that can never appear
+    /// would otherwise run to the full timeout. With this option, once output
+    /// has been at EOF for `timeout`, [`update_state`](Self::update_state) (and
+    /// every wait built on it) returns [`TermTestError::OutputClosed`].
+    ///
+    /// A PTY only reports EOF when no handles to the terminal remain, so the
+    /// harness releases its own handle after spawning. As a consequence only
+    /// one process can be spawned per harness with this option enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - How long output may stay at EOF before failing
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::{TermTestError, TuiTestHarness};
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness =
+    ///     TuiTestHarness::new(80, 24)?.with_eof_timeout(Duration::from_millis(500));
+    /// harness.spawn(CommandBuilder::new("my-daemon"))?;
+    ///
+    /// match harness.wait_for_text("Ready") {
+    ///     Err(TermTestError::OutputClosed { .. }) => eprintln!("daemon closed stdout"),
+    ///     other => other?,
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_eof_timeout(mut self, timeout: Duration) -> Self {
+        self.eof_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets an overall deadline for every operation on the harness.
+    ///
+    /// Each wait has its own timeout, but a test made of many waits can still
+    /// run for a long time if the application keeps almost responding. This is
+    /// a backstop: once `deadline` has passed since this call, the next
+    /// operation that sends input or reads output kills the child process and
+    /// returns [`TermTestError::GlobalDeadlineExceeded`] with the output of
+    /// [`dump`](Self::dump). Later operations keep failing the same way.
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline` - Total time allowed, measured from this call
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness =
+    ///     TuiTestHarness::new(80, 24)?.with_global_deadline(Duration::from_secs(30));
+    /// harness.spawn(CommandBuilder::new("my-app"))?;
+    ///
+    /// for step in ["Step 1", "Step 2", "Step 3"] {
+    ///     harness.wait_for_text(step)?;
+    ///     harness.send_text("\n")?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_global_deadline(mut self, deadline: Duration) -> Self {
+        self.global_deadline = Some(deadline);
+        self.deadline_at = Some(Instant::now() + deadline);
+        self
+    }
+
+    /// Returns a diagnostic report of the harness state.
+    ///
+    /// The report lists the last spawned command, whether it is still running,
+    /// the cursor position, the screen contents and any [`hints`](Self::hints).
+    /// It is included in [`TermTestError::GlobalDeadlineExceeded`], and is handy
+    /// to print when a test fails in some other way.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.spawn(CommandBuilder::new("my-app"))?;
+    ///
+    /// if harness.wait_for_text("Ready").is_err() {
+    ///     eprintln!("{}", harness.dump());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dump(&mut self) -> String {
+        let cursor = self.state.cursor_position();
+        let mut out = format!(
+            "Command: {}\nRunning: {}\nCursor position: row={}, col={}\n",
+            self.last_command.as_deref().unwrap_or("(none)"),
+            self.terminal.is_running(),
+            cursor.0,
+            cursor.1
+        );
+        for hint in &self.hints {
+            out.push_str(&format!("Hint: {}\n", hint));
+        }
+        out.push_str(&format!("Screen state:\n{}", self.state.debug_contents()));
+        out
+    }
+
+    /// Fails with a full dump once the global deadline has passed.
+    fn check_deadline(&mut self) -> Result<()> {
+        match (self.global_deadline, self.deadline_at) {
+            (Some(deadline), Some(at)) if Instant::now() >= at => {
+                let _ = self.terminal.kill();
+                Err(TermTestError::GlobalDeadlineExceeded {
+                    deadline_ms: deadline.as_millis() as u64,
+                    dump: self.dump(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns diagnostic hints collected while processing output.
+    ///
+    /// Hints point out likely causes of hangs and timeouts, such as an
+    /// application waiting for a reply to a terminal query that the harness
+    /// was not configured to answer. Each distinct hint is kept once. Hints
+    /// are printed only when a wait times out or the process exits during it,
+    /// so passing tests stay quiet.
+    pub fn hints(&self) -> &[String] {
+        &self.hints
+    }
+
+    /// Returns how many times the most recent wait polled before it finished.
+    ///
+    /// A wait checks its condition, then pauses for the poll interval (see
+    /// [`with_poll_interval`](Self::with_poll_interval)) before checking again;
+    /// this counts those pauses. A condition that already holds counts 0. A
+    /// high count for a wait that succeeds quickly suggests a shorter timeout
+    /// or a longer poll interval would do, while a count of 1 or 2 for slow
+    /// waits suggests the interval is too coarse.
+    ///
+    /// Covers [`wait_for`](Self::wait_for) and the waits built on it, as well
+    /// as the timeout and cursor variants.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use ratatui_testlib::TuiTestHarness;
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.wait_for_text("Ready")?;
+    /// println!("Ready after {} polls", harness.last_wait_polls());
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn last_wait_polls(&self) -> usize {
+        self.last_wait_polls
+    }
+
+    /// Returns the temporary home directory used for spawned processes.
+    ///
+    /// Returns `None` unless [`with_temp_home`](Self::with_temp_home) is enabled
+    /// and a process has been spawned.
+    pub fn temp_home(&self) -> Option<&Path> {
+        self.temp_home_path.as_deref()
+    }
+
     /// Configures the harness for a specific terminal emulator profile.
     ///
     /// This sets the terminal profile which controls which features are available
@@ -468,30 +1001,173 @@ impl TuiTestHarness {
     ///
     /// * `cmd` - Command to spawn
     ///
+    /// Variables configured with [`TuiTestHarnessBuilder::with_env`] are set on the
+    /// command, and any configured with [`TuiTestHarnessBuilder::with_unset_env`]
+    /// are removed from its environment before it is spawned.
+    ///
     /// # Errors
     ///
     /// Returns an error if spawning fails.
-    pub fn spawn(&mut self, cmd: CommandBuilder) -> Result<()> {
-        self.terminal.spawn(cmd)
+    pub fn spawn(&mut self, mut cmd: CommandBuilder) -> Result<()> {
+        if self.temp_home {
+            let home = self.temp_home_dir()?;
+            cmd.env("HOME", &home);
+            if cfg!(windows) {
+                cmd.env("USERPROFILE", &home);
+            }
+        }
+        for (key, value) in &self.env {
+            cmd.env(key, value);
+        }
+        for name in &self.unset_env {
+            cmd.env_remove(name);
+        }
+        self.last_command = Some(
+            cmd.get_argv()
+                .iter()
+                .map(|arg| {
+                    let arg = arg.to_string_lossy();
+                    if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '"') {
+                        format!("{:?}", arg)
+                    } else {
+                        arg.into_owned()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+        if self.separate_stderr {
+            self.redirect_stderr(&mut cmd)?;
+        }
+        self.terminal.spawn(cmd)?;
+        if self.eof_timeout.is_some() {
+            self.terminal.release_slave();
+        }
+        Ok(())
     }
 
-    /// Sends text to the PTY.
-    ///
-    /// # Arguments
-    ///
-    /// * `text` - Text to send
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if the write fails.
-    pub fn send_text(&mut self, text: &str) -> Result<()> {
-        // Record input timestamp for latency profiling
-        self.timing_recorder.record_event("input_sent");
-        self.latency_profile.mark_input();
+    /// Returns the temporary home directory, creating it on first use.
+    fn temp_home_dir(&mut self) -> Result<PathBuf> {
+        static HOME_ID: AtomicUsize = AtomicUsize::new(0);
 
-        let bytes = text.as_bytes();
-        self.record_input(bytes);
-        self.terminal.write(bytes)?;
+        if let Some(path) = &self.temp_home_path {
+            return Ok(path.clone());
+        }
+
+        let path = std::env::temp_dir().join(format!(
+            "ratatui-testlib-home-{}-{}",
+            std::process::id(),
+            HOME_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        std::fs::create_dir_all(&path)?;
+
+        self.temp_home_path = Some(path.clone());
+        Ok(path)
+    }
+
+    /// Rewrites `cmd` to run through `sh` with stderr sent to a fresh capture file.
+    fn redirect_stderr(&mut self, cmd: &mut CommandBuilder) -> Result<()> {
+        static CAPTURE_ID: AtomicUsize = AtomicUsize::new(0);
+
+        if let Some(old) = self.stderr_path.take() {
+            let _ = std::fs::remove_file(old);
+        }
+
+        let argv = cmd.get_argv_mut();
+        if argv.is_empty() {
+            // Default shell: there is no program to wrap
+            return Ok(());
+        }
+
+        let path = std::env::temp_dir().join(format!(
+            "ratatui-testlib-stderr-{}-{}.log",
+            std::process::id(),
+            CAPTURE_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        File::create(&path)?;
+
+        let program = std::mem::take(argv);
+        argv.push("sh".into());
+        argv.push("-c".into());
+        argv.push("exec \"$@\" 2>>\"$0\"".into());
+        argv.push(path.clone().into_os_string());
+        argv.extend(program);
+
+        self.stderr_path = Some(path);
+        Ok(())
+    }
+
+    /// Returns everything the spawned process wrote to stderr so far.
+    ///
+    /// Only available when the harness was configured with
+    /// [`with_separate_stderr`](Self::with_separate_stderr); otherwise stderr goes
+    /// to the PTY and this returns an empty string.
+    pub fn stderr_contents(&self) -> String {
+        self.stderr_path
+            .as_ref()
+            .and_then(|path| std::fs::read(path).ok())
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Returns the command line of the most recently spawned process.
+    ///
+    /// The program and its arguments are joined with spaces; arguments that are
+    /// empty or contain whitespace or quotes are shown double-quoted. The command
+    /// is recorded even if spawning it failed, which helps when diagnosing the
+    /// failure.
+    ///
+    /// # Returns
+    ///
+    /// The command line, or `None` if nothing has been spawned yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// let mut cmd = CommandBuilder::new("my-app");
+    /// cmd.arg("--config");
+    /// cmd.arg("test config.toml");
+    /// harness.spawn(cmd)?;
+    ///
+    /// assert_eq!(
+    ///     harness.last_command().as_deref(),
+    ///     Some(r#"my-app --config "test config.toml""#)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn last_command(&self) -> Option<String> {
+        self.last_command.clone()
+    }
+
+    /// Sends text to the PTY.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to send
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails.
+    pub fn send_text(&mut self, text: &str) -> Result<()> {
+        self.check_deadline()?;
+
+        // Record input timestamp for latency profiling
+        self.timing_recorder.record_event("input_sent");
+        self.latency_profile.mark_input();
+
+        let bytes = text.as_bytes();
+        self.record_input(bytes);
+        self.terminal.write(bytes)?;
+        if self.local_echo {
+            let echo = text.replace("\r\n", "\n").replace(['\r', '\n'], "\r\n");
+            self.state.feed(echo.as_bytes());
+        }
 
         // Update state, ignoring ProcessExited since the process might exit
         // after receiving input (e.g., sending 'q' to quit)
@@ -505,6 +1181,100 @@ impl TuiTestHarness {
         Ok(())
     }
 
+    /// Sends text as if it were pasted into the terminal.
+    ///
+    /// If the application has enabled bracketed paste mode (`ESC [ ? 2004 h`) and
+    /// the terminal profile supports it, the text is wrapped in `ESC [ 200 ~` and
+    /// `ESC [ 201 ~` so the application receives it as a single paste block.
+    /// Otherwise it is sent like [`send_text`](Self::send_text). Embedded
+    /// newlines are sent unchanged in both cases, so multi-line pastes arrive
+    /// intact.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to paste
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the write fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... spawn an editor that enables bracketed paste ...
+    ///
+    /// harness.send_paste("fn main() {\n    println!(\"hi\");\n}")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_paste(&mut self, text: &str) -> Result<()> {
+        if self.state.bracketed_paste_enabled() && self.supports_feature(Feature::BracketedPaste) {
+            self.send_text(&format!("\x1b[200~{}\x1b[201~", text))
+        } else {
+            self.send_text(text)
+        }
+    }
+
+    /// Sends input in separate writes, pausing between them.
+    ///
+    /// Real terminals sometimes deliver input across several reads, for example
+    /// an escape sequence split over two packets on a slow connection. Sending
+    /// the pieces separately reproduces that, so tests can catch applications
+    /// that only parse input arriving in one read.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunks` - Byte chunks, each written separately and in order
+    /// * `delay` - Pause after each chunk but the last
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a write fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... spawn application ...
+    ///
+    /// // An Up arrow whose ESC arrives before the rest of the sequence
+    /// harness.send_bytes_split(&[b"\x1b", b"[A"], Duration::from_millis(20))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_bytes_split(&mut self, chunks: &[&[u8]], delay: Duration) -> Result<()> {
+        self.check_deadline()?;
+        self.timing_recorder.record_event("input_sent");
+        self.latency_profile.mark_input();
+
+        for (i, chunk) in chunks.iter().enumerate() {
+            if i > 0 {
+                std::thread::sleep(delay);
+            }
+            self.record_input(chunk);
+            self.terminal.write_all(chunk)?;
+        }
+
+        // Update state, ignoring ProcessExited since the process might exit
+        // after receiving input
+        let _ = self.update_state();
+
+        self.timing_recorder.record_event("render_complete");
+        self.latency_profile.mark_render_end();
+        self.latency_profile.mark_frame_ready();
+
+        Ok(())
+    }
+
     /// Sends a single key event to the PTY.
     ///
     /// This is the simplest way to send keyboard input. It handles the conversion
@@ -613,6 +1383,42 @@ impl TuiTestHarness {
         Ok(())
     }
 
+    /// Sends a sequence of keys, one key event at a time.
+    ///
+    /// The batch form of [`send_key`](Self::send_key), for scripted
+    /// interactions that mix characters with navigation and control keys.
+    /// Each key is encoded as by [`send_key`](Self::send_key).
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - The key codes to send, in order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any key send fails. Keys after the failing one are
+    /// not sent.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::{KeyCode, TuiTestHarness};
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... spawn application ...
+    ///
+    /// // Move to the second item and open it
+    /// harness.send_key_sequence(&[KeyCode::Down, KeyCode::Down, KeyCode::Enter])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_key_sequence(&mut self, keys: &[KeyCode]) -> Result<()> {
+        for &key in keys {
+            self.send_key(key)?;
+        }
+        Ok(())
+    }
+
     /// Alias for [`send_keys`](Self::send_keys).
     ///
     /// Types a text string by sending each character as a key event.
@@ -766,6 +1572,194 @@ impl TuiTestHarness {
         Ok(())
     }
 
+    /// Runs a timed input script against the running application.
+    ///
+    /// Each step waits for its delay via [`advance_time`](Self::advance_time), so
+    /// PTY output keeps being processed between steps, and then executes its
+    /// [`ScriptAction`]. Execution stops at the first failing step.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - Sequence of `(delay, action)` pairs to execute in order
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first failing step. An `Expect` step whose text is
+    /// not on screen fails with a `Parse` error that includes the step index and
+    /// the current screen contents.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use ratatui_testlib::{KeyCode, ScriptAction, TuiTestHarness};
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... spawn application ...
+    ///
+    /// harness.run_script(&[
+    ///     (Duration::from_millis(50), ScriptAction::SendKey(KeyCode::Down)),
+    ///     (Duration::from_millis(50), ScriptAction::SendKey(KeyCode::Enter)),
+    ///     (Duration::ZERO, ScriptAction::WaitForText("Opened".into())),
+    /// ])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run_script(&mut self, steps: &[(Duration, ScriptAction)]) -> Result<()> {
+        for (index, (delay, action)) in steps.iter().enumerate() {
+            self.advance_time(*delay)?;
+
+            match action {
+                ScriptAction::SendText(text) => self.send_text(text)?,
+                ScriptAction::SendKey(key) => self.send_key(*key)?,
+                ScriptAction::WaitForText(text) => self.wait_for_text(text)?,
+                ScriptAction::Expect(text) => {
+                    if !self.state.contains(text) {
+                        return Err(TermTestError::Parse(format!(
+                            "Script step {} expected text '{}' on screen\nCurrent screen state:\n{}",
+                            index,
+                            text,
+                            self.state.debug_contents()
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Answers a sequence of interactive prompts.
+    ///
+    /// For each `(prompt, answer)` step this waits until `prompt` appears on
+    /// screen, then types `answer` followed by Enter. This fits CLIs built on
+    /// prompt libraries such as `dialoguer` or `inquire`, which ask one question
+    /// at a time.
+    ///
+    /// Prompts are matched against the whole screen, and earlier prompts usually
+    /// stay visible, so each prompt substring should be unique to its step.
+    ///
+    /// # Arguments
+    ///
+    /// * `steps` - Sequence of `(expected prompt substring, answer)` pairs
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] naming the step and prompt if a prompt
+    /// does not appear before the timeout or the process exits. Other errors,
+    /// including errors from sending an answer, are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.spawn(CommandBuilder::new("my-cli"))?;
+    ///
+    /// harness.prompt_flow(&[
+    ///     ("Project name", "demo"),
+    ///     ("Use git?", "y"),
+    /// ])?;
+    /// harness.wait_for_text("Created demo")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn prompt_flow(&mut self, steps: &[(&str, &str)]) -> Result<()> {
+        for (index, (prompt, answer)) in steps.iter().enumerate() {
+            match self.wait_for_text(prompt) {
+                Ok(()) => {}
+                Err(e @ TermTestError::Timeout { .. }) | Err(e @ TermTestError::ProcessExited) => {
+                    return Err(TermTestError::Parse(format!(
+                        "Prompt step {} never showed prompt {:?} ({})\n\nScreen state:\n{}",
+                        index,
+                        prompt,
+                        e,
+                        self.state.debug_contents()
+                    )));
+                }
+                Err(e) => return Err(e),
+            }
+            self.send_text(answer)?;
+            self.send_key(KeyCode::Enter)?;
+        }
+        Ok(())
+    }
+
+    /// Sends a batch of inputs and asserts the application survives them.
+    ///
+    /// A lightweight smoke/fuzz check for input handlers: each input is written
+    /// as raw bytes, output is processed for `settle`, and then the process must
+    /// still be running and the screen must not show literal escape garbage
+    /// (see [`ScreenState::has_literal_escapes`]).
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - Raw byte sequences to send, in order
+    /// * `settle` - How long to keep processing output after the last input
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if the process exited or literal escape
+    /// sequences appeared on screen. Write errors are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... spawn app ...
+    ///
+    /// let inputs: [&[u8]; 4] = [b"\x1b[A", b"\x1bOP", b"\x03", b"\xff\xfe"];
+    /// harness.assert_survives_input(&inputs, Duration::from_millis(300))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn assert_survives_input(&mut self, inputs: &[&[u8]], settle: Duration) -> Result<()> {
+        for input in inputs {
+            self.record_input(input);
+            self.terminal.write(input)?;
+            match self.update_state() {
+                Ok(()) | Err(TermTestError::ProcessExited) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let start = Instant::now();
+        while start.elapsed() < settle {
+            match self.update_state() {
+                Ok(()) => {}
+                Err(TermTestError::ProcessExited) => break,
+                Err(e) => return Err(e),
+            }
+            self.poll_wait();
+        }
+
+        if !self.terminal.is_running() {
+            return Err(TermTestError::Parse(format!(
+                "Process exited after receiving {} inputs\n\nScreen state:\n{}",
+                inputs.len(),
+                self.state.debug_contents()
+            )));
+        }
+        if self.state.has_literal_escapes() {
+            return Err(TermTestError::Parse(format!(
+                "Literal escape sequences on screen after {} inputs\n\nScreen state:\n{}",
+                inputs.len(),
+                self.state.debug_contents()
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Sends a mouse event to the PTY.
     ///
     /// This simulates mouse interactions like clicks, drags, and scrolling using
@@ -795,6 +1789,8 @@ impl TuiTestHarness {
     /// # }
     /// ```
     pub fn send_mouse_event(&mut self, event: MouseEvent) -> Result<()> {
+        self.check_deadline()?;
+
         // Record input timestamp for latency profiling
         self.timing_recorder.record_event("input_sent");
         self.latency_profile.mark_input();
@@ -918,6 +1914,8 @@ impl TuiTestHarness {
     /// This encodes the key event to bytes, writes to the PTY, adds a small
     /// delay for the application to process the input, and updates the screen state.
     fn send_key_event(&mut self, event: KeyEvent) -> Result<()> {
+        self.check_deadline()?;
+
         // Record input timestamp for latency profiling
         self.timing_recorder.record_event("input_sent");
         self.latency_profile.mark_input();
@@ -950,16 +1948,22 @@ impl TuiTestHarness {
     ///
     /// This reads output in chunks (configured by buffer_size) and feeds it to the
     /// terminal emulator. It handles partial escape sequences correctly by continuing
-    /// to read until no more data is available.
+    /// to read until no more data is available, or for at most one poll interval
+    /// when the application writes without pause.
     ///
     /// This is called automatically by other methods but can be called
-    /// manually if needed.
+    /// manually if needed. Each call starts a new update for
+    /// [`ScreenState::did_scroll_up`] and [`ScreenState::dirty_rows`].
     ///
     /// # Errors
     ///
     /// Returns an error if reading from the PTY fails.
     /// Returns [`TermTestError::ProcessExited`] if the child process has exited.
     pub fn update_state(&mut self) -> Result<()> {
+        self.check_deadline()?;
+        self.state.clear_scrolled();
+        self.state.clear_dirty_rows();
+
         // First check if the child process has exited
         if !self.terminal.is_running() {
             // Process has exited - try to read any remaining buffered output
@@ -971,6 +1975,7 @@ impl TuiTestHarness {
                         self.record_output(&buf[..n]);
                         self.state.feed(&buf[..n]);
                         self.record_state_change();
+                        self.count_output_bytes(n)?;
                     }
                     Err(_) => break, // Any error, just stop reading
                 }
@@ -980,6 +1985,7 @@ impl TuiTestHarness {
         }
 
         let mut buf = vec![0u8; self.buffer_size];
+        let started = Instant::now();
 
         loop {
             match self.terminal.read(&mut buf) {
@@ -988,6 +1994,13 @@ impl TuiTestHarness {
                     self.record_output(&buf[..n]);
                     self.state.feed(&buf[..n]);
                     self.record_state_change();
+                    self.count_output_bytes(n)?;
+                    // An application that never pauses (a spinner, a clock)
+                    // would keep this loop going forever; leave the rest of
+                    // its output to the next update
+                    if started.elapsed() >= self.poll_interval {
+                        break;
+                    }
                 }
                 Err(e) => {
                     // Use proper ErrorKind matching instead of string matching
@@ -1003,13 +2016,138 @@ impl TuiTestHarness {
             }
         }
 
-        Ok(())
+        if let Some(timeout) = self.eof_timeout {
+            if self.terminal.is_eof() {
+                let since = *self.eof_since.get_or_insert_with(Instant::now);
+                if since.elapsed() >= timeout {
+                    return Err(TermTestError::OutputClosed {
+                        timeout_ms: timeout.as_millis() as u64,
+                    });
+                }
+            }
+        }
+
+        self.handle_queries()
     }
 
-    /// Waits for a condition to be true, with timeout.
-    ///
-    /// This method polls the PTY output at the configured interval and checks
-    /// the condition against the current screen state. If the timeout is reached,
+    /// Answers, or records a hint about, terminal queries in the output so far.
+    fn handle_queries(&mut self) -> Result<()> {
+        let identity = match (&self.terminal_identity, self.auto_responses) {
+            (Some(identity), _) => Some(identity.clone()),
+            (None, true) => Some(TerminalIdentity::default()),
+            (None, false) => None,
+        };
+
+        for query in self.state.take_queries() {
+            let reply = match (query, &identity) {
+                (TerminalQuery::CursorPosition(row, col), _) if self.auto_responses => {
+                    format!("\x1b[{};{}R", row + 1, col + 1)
+                }
+                (TerminalQuery::CursorPosition(..), _) => {
+                    self.add_hint(
+                        "App is waiting for a cursor-position report (ESC [ 6 n); \
+                         enable with_auto_responses(true) to answer it",
+                    );
+                    continue;
+                }
+                (TerminalQuery::PrimaryAttributes, Some(identity)) => {
+                    identity.primary_da_response()
+                }
+                (TerminalQuery::SecondaryAttributes, Some(identity)) => {
+                    identity.secondary_da_response()
+                }
+                (TerminalQuery::Setting(setting), Some(identity)) => {
+                    identity.decrqss_response(&setting)
+                }
+                (_, None) => {
+                    self.add_hint(
+                        "App is waiting for a terminal identification reply (device \
+                         attributes or DECRQSS); set with_terminal_identity(..) to answer it",
+                    );
+                    continue;
+                }
+            };
+            self.terminal.write(reply.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Records a diagnostic hint, keeping each distinct hint once.
+    ///
+    /// Hints are only printed when a wait fails; see [`print_hints`](Self::print_hints).
+    fn add_hint(&mut self, hint: &str) {
+        if !self.hints.iter().any(|h| h == hint) {
+            self.hints.push(hint.to_string());
+        }
+    }
+
+    /// Prints the collected hints as part of a wait failure report.
+    fn print_hints(&self) {
+        for hint in &self.hints {
+            eprintln!("Hint: {}", hint);
+        }
+    }
+
+    /// Adds `n` bytes to the output total and enforces the configured limit.
+    fn count_output_bytes(&mut self, n: usize) -> Result<()> {
+        self.output_bytes_read += n;
+        match self.max_output_bytes {
+            Some(limit) if self.output_bytes_read > limit => {
+                Err(TermTestError::OutputLimitExceeded {
+                    limit_bytes: limit,
+                    read_bytes: self.output_bytes_read,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Processes output until none has arrived for one poll interval.
+    ///
+    /// Stops early at the configured timeout or when the process exits, which
+    /// is not an error here.
+    fn process_until_quiet(&mut self) -> Result<()> {
+        let start = Instant::now();
+        let mut last_output = start;
+        let mut last_bytes = self.output_bytes_read;
+
+        loop {
+            match self.update_state() {
+                Ok(()) => {}
+                Err(TermTestError::ProcessExited) => return Ok(()),
+                Err(e) => return Err(e),
+            }
+
+            let now = Instant::now();
+            if self.output_bytes_read != last_bytes {
+                last_bytes = self.output_bytes_read;
+                last_output = now;
+            }
+            if now.duration_since(last_output) >= self.poll_interval
+                || now.duration_since(start) >= self.timeout
+            {
+                return Ok(());
+            }
+            self.poll_wait();
+        }
+    }
+
+    /// Pauses between wait-loop iterations according to the poll strategy.
+    fn poll_wait(&mut self) {
+        match self.poll_strategy {
+            PollStrategy::Sleep => std::thread::sleep(self.poll_interval),
+            PollStrategy::Readable => {
+                if self.terminal.wait_readable(self.poll_interval).is_err() {
+                    std::thread::sleep(self.poll_interval);
+                }
+            }
+        }
+    }
+
+    /// Waits for a condition to be true, with timeout.
+    ///
+    /// This method polls the PTY output at the configured interval and checks
+    /// the condition against the current screen state. If the timeout is reached,
     /// it returns an error with context about what was being waited for and the
     /// current screen state.
     ///
@@ -1057,6 +2195,7 @@ impl TuiTestHarness {
     {
         let start = Instant::now();
         let mut iterations = 0;
+        self.last_wait_polls = 0;
 
         loop {
             // Update state - this may return ProcessExited
@@ -1080,6 +2219,7 @@ impl TuiTestHarness {
                     eprintln!("\n=== Process exited while waiting for: {} ===", description);
                     eprintln!("Waited: {:?} ({} iterations)", start.elapsed(), iterations);
                     eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
+                    self.print_hints();
                     eprintln!("Final screen state:\n{}", current_state);
                     eprintln!("==========================================\n");
 
@@ -1097,6 +2237,7 @@ impl TuiTestHarness {
                 eprintln!("\n=== Timeout waiting for: {} ===", description);
                 eprintln!("Waited: {:?} ({} iterations)", elapsed, iterations);
                 eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
+                self.print_hints();
                 eprintln!("Current screen state:\n{}", current_state);
                 eprintln!("==========================================\n");
 
@@ -1106,7 +2247,8 @@ impl TuiTestHarness {
             }
 
             iterations += 1;
-            std::thread::sleep(self.poll_interval);
+            self.last_wait_polls = iterations;
+            self.poll_wait();
         }
     }
 
@@ -1137,122 +2279,189 @@ impl TuiTestHarness {
         self.wait_for_with_context(move |state| state.contains(&text), &description)
     }
 
-    /// Waits for specific text to appear with a custom timeout.
+    /// Waits for text to appear anywhere on the screen, ignoring case.
     ///
-    /// This allows overriding the configured timeout for a single wait operation.
+    /// Like [`wait_for_text`](Self::wait_for_text), but matches with
+    /// [`ScreenState::contains_ignore_case`], for output whose capitalization
+    /// varies. Uses the configured timeout.
     ///
     /// # Arguments
     ///
     /// * `text` - Text to wait for
-    /// * `timeout` - Timeout duration for this operation
     ///
     /// # Errors
     ///
-    /// Returns a `Timeout` error if the text does not appear within the specified timeout.
-    /// Returns `ProcessExited` if the child process exits before the text appears.
+    /// Returns a `Timeout` error if the text does not appear within the configured timeout.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// # use ratatui_testlib::TuiTestHarness;
-    /// # use std::time::Duration;
     /// # let mut harness = TuiTestHarness::new(80, 24)?;
-    /// harness.wait_for_text_timeout("Ready", Duration::from_secs(2))?;
+    /// harness.wait_for_text_ignore_case("error")?; // also matches "ERROR"
     /// # Ok::<(), ratatui_testlib::TermTestError>(())
     /// ```
-    pub fn wait_for_text_timeout(&mut self, text: &str, timeout: Duration) -> Result<()> {
+    pub fn wait_for_text_ignore_case(&mut self, text: &str) -> Result<()> {
         let text = text.to_string();
-        let description = format!("text '{}'", text);
-
-        let start = Instant::now();
-        let mut iterations = 0;
-
-        loop {
-            // Update state - this may return ProcessExited
-            match self.update_state() {
-                Ok(()) => {
-                    if self.state.contains(&text) {
-                        return Ok(());
-                    }
-                }
-                Err(TermTestError::ProcessExited) => {
-                    // Process exited - check condition one final time
-                    if self.state.contains(&text) {
-                        return Ok(());
-                    }
-
-                    let current_state = self.state.debug_contents();
-                    let cursor = self.state.cursor_position();
-
-                    eprintln!("\n=== Process exited while waiting for: {} ===", description);
-                    eprintln!("Waited: {:?} ({} iterations)", start.elapsed(), iterations);
-                    eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
-                    eprintln!("Final screen state:\n{}", current_state);
-                    eprintln!("==========================================\n");
-
-                    return Err(TermTestError::ProcessExited);
-                }
-                Err(e) => return Err(e),
-            }
-
-            let elapsed = start.elapsed();
-            if elapsed >= timeout {
-                let current_state = self.state.debug_contents();
-                let cursor = self.state.cursor_position();
+        let description = format!("text '{}' (ignoring case)", text);
+        self.wait_for_with_context(move |state| state.contains_ignore_case(&text), &description)
+    }
 
-                eprintln!("\n=== Timeout waiting for: {} ===", description);
-                eprintln!("Waited: {:?} ({} iterations)", elapsed, iterations);
-                eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
-                eprintln!("Current screen state:\n{}", current_state);
-                eprintln!("==========================================\n");
+    /// Waits for text to appear in a specific row.
+    ///
+    /// Unlike [`wait_for_text`](Self::wait_for_text), matches elsewhere on the
+    /// screen are ignored, so a word in a header or stale body text can't end
+    /// the wait early. Uses the configured timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row to watch (0-based)
+    /// * `text` - Text to wait for within that row
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Timeout` error if the text does not appear in the row within
+    /// the configured timeout.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use ratatui_testlib::TuiTestHarness;
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.send_text("\x13")?; // Ctrl+S
+    /// harness.wait_for_text_in_row(23, "Saved")?; // status line
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn wait_for_text_in_row(&mut self, row: u16, text: &str) -> Result<()> {
+        let text = text.to_string();
+        let description = format!("text '{}' in row {}", text, row);
+        self.wait_for_with_context(
+            move |state| state.row_contents(row).contains(&text),
+            &description,
+        )
+    }
 
-                return Err(TermTestError::Timeout { timeout_ms: timeout.as_millis() as u64 });
-            }
+    /// Waits for text to disappear from the screen.
+    ///
+    /// The counterpart of [`wait_for_text`](Self::wait_for_text), for checking
+    /// that a transition finished, such as a "Loading..." spinner being
+    /// dismissed. Returns immediately if the text is not on screen. Uses the
+    /// configured timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text that should no longer be shown
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Timeout` error if the text is still on screen after the
+    /// configured timeout.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use ratatui_testlib::TuiTestHarness;
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.wait_for_text("Loading...")?;
+    /// harness.wait_for_text_absent("Loading...")?;
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn wait_for_text_absent(&mut self, text: &str) -> Result<()> {
+        let text = text.to_string();
+        let description = format!("text '{}' to disappear", text);
+        self.wait_for_with_context(move |state| !state.contains(&text), &description)
+    }
 
-            iterations += 1;
-            std::thread::sleep(self.poll_interval);
-        }
+    /// Waits until any of several texts appears on the screen.
+    ///
+    /// Useful when an operation can end in more than one way, for example
+    /// success or failure, without racing separate waits. Uses the configured
+    /// timeout.
+    ///
+    /// # Arguments
+    ///
+    /// * `texts` - Candidate texts to wait for
+    ///
+    /// # Returns
+    ///
+    /// The index in `texts` of the text found on screen. If several are shown
+    /// at once, the lowest index is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Timeout` error if none of the texts appears within the
+    /// configured timeout (always the case for an empty slice).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use ratatui_testlib::TuiTestHarness;
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// match harness.wait_for_any(&["OK", "Error"])? {
+    ///     0 => println!("succeeded"),
+    ///     _ => println!("failed:\n{}", harness.screen_contents()),
+    /// }
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn wait_for_any(&mut self, texts: &[&str]) -> Result<usize> {
+        let description = format!("any of {:?}", texts);
+        self.wait_for_with_context(
+            |state| texts.iter().any(|text| state.contains(text)),
+            &description,
+        )?;
+
+        Ok(texts
+            .iter()
+            .position(|text| self.state.contains(text))
+            .expect("wait condition guarantees a match"))
     }
 
-    /// Waits for the cursor to reach a specific position.
+    /// Waits until all of several texts are on the screen at the same time.
     ///
-    /// This is useful for verifying cursor movements after sending input
-    /// or for tracking application state changes.
+    /// Stricter than chaining [`wait_for_text`](Self::wait_for_text) calls:
+    /// every text must be present in the same screen state, so one that
+    /// appeared and then scrolled away or was cleared doesn't count. Returns
+    /// immediately for an empty slice. Uses the configured timeout.
     ///
     /// # Arguments
     ///
-    /// * `pos` - Target cursor position as (row, col) tuple (0-based)
+    /// * `texts` - Texts that must all be shown
     ///
     /// # Errors
     ///
-    /// Returns a `Timeout` error if the cursor does not reach the position within the configured
-    /// timeout.
+    /// Returns a `Timeout` error if the texts are not all shown together
+    /// within the configured timeout.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// # use ratatui_testlib::TuiTestHarness;
     /// # let mut harness = TuiTestHarness::new(80, 24)?;
-    /// harness.wait_for_cursor((5, 10))?;
+    /// // The dashboard is ready once every widget has rendered
+    /// harness.wait_for_all(&["CPU", "Memory", "Network"])?;
     /// # Ok::<(), ratatui_testlib::TermTestError>(())
     /// ```
-    pub fn wait_for_cursor(&mut self, pos: (u16, u16)) -> Result<()> {
-        let description = format!("cursor at ({}, {})", pos.0, pos.1);
-        self.wait_for_with_context(move |state| state.cursor_position() == pos, &description)
+    pub fn wait_for_all(&mut self, texts: &[&str]) -> Result<()> {
+        let description = format!("all of {:?}", texts);
+        self.wait_for_with_context(
+            |state| texts.iter().all(|text| state.contains(text)),
+            &description,
+        )
     }
 
-    /// Waits for the cursor to reach a specific position with a custom timeout.
+    /// Waits for specific text to appear with a custom timeout.
+    ///
+    /// This allows overriding the configured timeout for a single wait operation.
     ///
     /// # Arguments
     ///
-    /// * `pos` - Target cursor position as (row, col) tuple (0-based)
+    /// * `text` - Text to wait for
     /// * `timeout` - Timeout duration for this operation
     ///
     /// # Errors
     ///
-    /// Returns a `Timeout` error if the cursor does not reach the position within the specified
-    /// timeout. Returns `ProcessExited` if the child process exits before the cursor reaches
-    /// the position.
+    /// Returns a `Timeout` error if the text does not appear within the specified timeout.
+    /// Returns `ProcessExited` if the child process exits before the text appears.
     ///
     /// # Example
     ///
@@ -1260,26 +2469,28 @@ impl TuiTestHarness {
     /// # use ratatui_testlib::TuiTestHarness;
     /// # use std::time::Duration;
     /// # let mut harness = TuiTestHarness::new(80, 24)?;
-    /// harness.wait_for_cursor_timeout((5, 10), Duration::from_millis(500))?;
+    /// harness.wait_for_text_timeout("Ready", Duration::from_secs(2))?;
     /// # Ok::<(), ratatui_testlib::TermTestError>(())
     /// ```
-    pub fn wait_for_cursor_timeout(&mut self, pos: (u16, u16), timeout: Duration) -> Result<()> {
-        let description = format!("cursor at ({}, {})", pos.0, pos.1);
+    pub fn wait_for_text_timeout(&mut self, text: &str, timeout: Duration) -> Result<()> {
+        let text = text.to_string();
+        let description = format!("text '{}'", text);
 
         let start = Instant::now();
         let mut iterations = 0;
+        self.last_wait_polls = 0;
 
         loop {
             // Update state - this may return ProcessExited
             match self.update_state() {
                 Ok(()) => {
-                    if self.state.cursor_position() == pos {
+                    if self.state.contains(&text) {
                         return Ok(());
                     }
                 }
                 Err(TermTestError::ProcessExited) => {
                     // Process exited - check condition one final time
-                    if self.state.cursor_position() == pos {
+                    if self.state.contains(&text) {
                         return Ok(());
                     }
 
@@ -1289,6 +2500,7 @@ impl TuiTestHarness {
                     eprintln!("\n=== Process exited while waiting for: {} ===", description);
                     eprintln!("Waited: {:?} ({} iterations)", start.elapsed(), iterations);
                     eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
+                    self.print_hints();
                     eprintln!("Final screen state:\n{}", current_state);
                     eprintln!("==========================================\n");
 
@@ -1305,6 +2517,7 @@ impl TuiTestHarness {
                 eprintln!("\n=== Timeout waiting for: {} ===", description);
                 eprintln!("Waited: {:?} ({} iterations)", elapsed, iterations);
                 eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
+                self.print_hints();
                 eprintln!("Current screen state:\n{}", current_state);
                 eprintln!("==========================================\n");
 
@@ -1312,575 +2525,653 @@ impl TuiTestHarness {
             }
 
             iterations += 1;
-            std::thread::sleep(self.poll_interval);
+            self.last_wait_polls = iterations;
+            self.poll_wait();
         }
     }
 
-    /// Returns the current screen contents as a string.
-    pub fn screen_contents(&self) -> String {
-        self.state.contents()
-    }
-
-    /// Returns the current cursor position as (row, col).
+    /// Waits for text to appear, failing immediately if an unwanted text appears.
     ///
-    /// Both row and column are 0-based indices. This is required for Phase 3
-    /// Sixel position verification.
+    /// Like an expect script's error pattern: when `bad` shows up (an error
+    /// message, say), waiting for `good` to time out would only waste time.
+    /// If both appear within the same poll, `bad` wins.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A tuple of (row, col) where both are 0-based.
+    /// * `good` - Text to wait for
+    /// * `bad` - Text that must not appear
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] as soon as `bad` is on screen.
+    /// Returns a `Timeout` error if neither appears within the configured
+    /// timeout, and `ProcessExited` if the process exits first.
     ///
     /// # Example
     ///
     /// ```rust,no_run
     /// # use ratatui_testlib::TuiTestHarness;
-    /// # let harness = TuiTestHarness::new(80, 24)?;
-    /// let (row, col) = harness.cursor_position();
-    /// println!("Cursor at: row={}, col={}", row, col);
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.wait_for_text_expecting("Connected", "Connection refused")?;
     /// # Ok::<(), ratatui_testlib::TermTestError>(())
     /// ```
-    pub fn cursor_position(&self) -> (u16, u16) {
-        self.state.cursor_position()
+    pub fn wait_for_text_expecting(&mut self, good: &str, bad: &str) -> Result<()> {
+        let description = format!("text '{}' (failing on '{}')", good, bad);
+        let (good_text, bad_text) = (good.to_string(), bad.to_string());
+        self.wait_for_with_context(
+            move |state| state.contains(&good_text) || state.contains(&bad_text),
+            &description,
+        )?;
+
+        if self.state.contains(bad) {
+            return Err(TermTestError::Parse(format!(
+                "Unexpected text '{}' appeared while waiting for '{}'\n\nScreen state:\n{}",
+                bad,
+                good,
+                self.state.debug_contents()
+            )));
+        }
+        Ok(())
     }
 
-    /// Alias for `cursor_position()` for convenience.
+    /// Asserts that the application transitions through a sequence of frames.
     ///
-    /// Returns the current cursor position as (row, col).
-    pub fn get_cursor_position(&self) -> (u16, u16) {
-        self.cursor_position()
-    }
-
-    /// Returns the current screen state.
+    /// Waits for each frame's text to appear on screen, in order, allowing up to
+    /// `per_frame_timeout` per frame. This is intended for animations and
+    /// multi-step transitions, e.g. spinners or progress indicators.
     ///
-    /// Provides immutable access to the terminal screen state for inspecting
-    /// rendered content without modifying it.
+    /// # Skipped Frames
     ///
-    /// # Returns
+    /// Frames are observed by polling: all output that arrives between two polls
+    /// is processed at once, and only the resulting screen is inspected. A frame
+    /// that is rendered and then overwritten before the next poll is therefore
+    /// never seen, and the assertion fails with a `Timeout` for that frame even
+    /// though the application drew it. Use a poll interval (see
+    /// [`with_poll_interval`](Self::with_poll_interval)) shorter than the
+    /// animation's frame duration to avoid this.
     ///
-    /// A reference to the current [`ScreenState`].
+    /// A frame that is still visible when the previous one is confirmed (e.g.,
+    /// two frames on screen at once) is accepted immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - Text expected on screen for each frame, in order
+    /// * `per_frame_timeout` - Maximum time to wait for each individual frame
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Timeout` error for the first frame that is not observed in time.
+    /// Returns `ProcessExited` if the process exits before all frames are seen.
     ///
     /// # Example
     ///
     /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(80, 24)?;
-    /// let state = harness.state();
-    /// println!("Screen size: {:?}", state.size());
+    /// let mut harness = TuiTestHarness::new(80, 24)?.with_poll_interval(Duration::from_millis(10));
+    /// // ... spawn application ...
+    ///
+    /// harness.assert_frame_sequence(&["Loading.", "Loading..", "Loading..."], Duration::from_secs(1))?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn state(&self) -> &ScreenState {
-        &self.state
+    pub fn assert_frame_sequence(
+        &mut self,
+        frames: &[&str],
+        per_frame_timeout: Duration,
+    ) -> Result<()> {
+        for (index, frame) in frames.iter().enumerate() {
+            if let Err(e) = self.wait_for_text_timeout(frame, per_frame_timeout) {
+                eprintln!(
+                    "Frame {} of {} ('{}') was not observed; it may have been skipped",
+                    index + 1,
+                    frames.len(),
+                    frame
+                );
+                return Err(e);
+            }
+        }
+        Ok(())
     }
 
-    /// Returns a mutable reference to the screen state.
+    /// Waits for `count` new lines of output, requiring a steady pace.
     ///
-    /// Allows direct manipulation of the screen state, which can be useful
-    /// for testing specific scenarios or feeding mock data.
+    /// Lines are counted as line feeds processed after this call starts (see
+    /// [`ScreenState::line_feeds`]), so scrolling output is counted correctly.
+    /// The time between consecutive lines, and before the first one, must not
+    /// exceed `max_gap`. This catches throughput regressions in streaming
+    /// applications that a plain [`wait_for_text`](Self::wait_for_text) would
+    /// hide.
     ///
-    /// # Returns
+    /// Arrival times are observed by polling, so gaps are measured with the
+    /// resolution of the poll interval; lines that arrive within one poll count
+    /// as arriving together.
     ///
-    /// A mutable reference to the [`ScreenState`].
+    /// # Arguments
     ///
-    /// # Example
-    ///
-    /// ```rust,no_run
-    /// use ratatui_testlib::TuiTestHarness;
-    ///
-    /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// harness.state_mut().feed(b"Test data");
-    /// assert!(harness.screen_contents().contains("Test"));
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn state_mut(&mut self) -> &mut ScreenState {
-        &mut self.state
-    }
-
-    /// Resizes the terminal.
-    ///
-    /// Changes the terminal dimensions and resets the screen state.
-    /// This can be useful for testing responsive TUI layouts.
-    ///
-    /// # Arguments
-    ///
-    /// * `width` - New width in columns
-    /// * `height` - New height in rows
+    /// * `count` - Number of lines to wait for
+    /// * `max_gap` - Maximum allowed time between consecutive lines
     ///
     /// # Errors
     ///
-    /// Returns an error if the resize operation fails.
+    /// Returns [`TermTestError::Parse`] as soon as a gap exceeds `max_gap`.
+    /// Returns a `Timeout` error if the configured timeout expires first, and
+    /// `ProcessExited` if the process exits before `count` lines arrived.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use ratatui_testlib::TuiTestHarness;
-    ///
-    /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// harness.resize(120, 40)?;
-    /// assert_eq!(harness.state().size(), (120, 40));
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub fn resize(&mut self, width: u16, height: u16) -> Result<()> {
-        self.terminal.resize(width, height)?;
-        self.state = ScreenState::new(width, height);
-        Ok(())
-    }
-
-    /// Checks if the child process is still running.
-    ///
-    /// # Returns
-    ///
-    /// `true` if a process is currently running in the PTY, `false` otherwise.
-    ///
-    /// # Example
+    /// use std::time::Duration;
     ///
-    /// ```rust,no_run
-    /// use portable_pty::CommandBuilder;
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
     /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// let cmd = CommandBuilder::new("sleep");
-    /// harness.spawn(cmd)?;
-    /// assert!(harness.is_running());
+    /// // ... spawn a log streamer ...
+    ///
+    /// harness.wait_for_lines_paced(20, Duration::from_millis(500))?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn is_running(&mut self) -> bool {
-        self.terminal.is_running()
+    pub fn wait_for_lines_paced(&mut self, count: u16, max_gap: Duration) -> Result<()> {
+        let description = format!("{} lines at most {:?} apart", count, max_gap);
+        let baseline = self.state.line_feeds();
+        let start = Instant::now();
+        let mut last_arrival = start;
+        let mut seen = 0;
+        let mut iterations = 0;
+        self.last_wait_polls = 0;
+
+        loop {
+            let update = self.update_state();
+            let now = Instant::now();
+
+            let lines = self.state.line_feeds() - baseline;
+            if lines > seen {
+                let gap = now.duration_since(last_arrival);
+                if gap > max_gap {
+                    return Err(TermTestError::Parse(format!(
+                        "Line {} arrived {:?} after the previous one (max gap {:?})\n\nScreen state:\n{}",
+                        seen + 1,
+                        gap,
+                        max_gap,
+                        self.state.debug_contents()
+                    )));
+                }
+                seen = lines;
+                last_arrival = now;
+            }
+            if seen >= count as usize {
+                return Ok(());
+            }
+
+            match update {
+                Ok(()) => {}
+                Err(TermTestError::ProcessExited) => {
+                    eprintln!("\n=== Process exited while waiting for: {} ===", description);
+                    eprintln!("Waited: {:?} ({} iterations)", start.elapsed(), iterations);
+                    eprintln!("Lines received: {}", seen);
+                    self.print_hints();
+                    eprintln!("Final screen state:\n{}", self.state.debug_contents());
+                    eprintln!("==========================================\n");
+
+                    return Err(TermTestError::ProcessExited);
+                }
+                Err(e) => return Err(e),
+            }
+
+            let gap = now.duration_since(last_arrival);
+            if gap > max_gap {
+                return Err(TermTestError::Parse(format!(
+                    "Line {} did not arrive within {:?} of the previous one ({} of {} lines received)\n\nScreen state:\n{}",
+                    seen + 1,
+                    max_gap,
+                    seen,
+                    count,
+                    self.state.debug_contents()
+                )));
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= self.timeout {
+                eprintln!("\n=== Timeout waiting for: {} ===", description);
+                eprintln!("Waited: {:?} ({} iterations)", elapsed, iterations);
+                eprintln!("Lines received: {}", seen);
+                self.print_hints();
+                eprintln!("Current screen state:\n{}", self.state.debug_contents());
+                eprintln!("==========================================\n");
+
+                return Err(TermTestError::Timeout {
+                    timeout_ms: self.timeout.as_millis() as u64,
+                });
+            }
+
+            iterations += 1;
+            self.last_wait_polls = iterations;
+            self.poll_wait();
+        }
     }
 
-    /// Waits for the child process to exit.
+    /// Waits until the process appears to be waiting for input.
     ///
-    /// Blocks until the spawned process terminates and returns its exit status.
+    /// The process is considered idle once it has produced no output for
+    /// `quiet`. This is the natural checkpoint in REPL-style tests: send a
+    /// command, wait until idle, then send the next one, without needing to
+    /// know what the command prints.
     ///
-    /// # Returns
+    /// Idleness is inferred from silence, so a process that is busy computing
+    /// without printing also looks idle; pick `quiet` longer than the gaps in
+    /// the application's normal output.
     ///
-    /// The [`ExitStatus`] of the terminated process.
+    /// # Arguments
+    ///
+    /// * `quiet` - How long the output must stay silent
     ///
     /// # Errors
     ///
-    /// Returns [`TermTestError::NoProcessRunning`] if no process is currently running.
+    /// Returns a `Timeout` error if output doesn't stop for `quiet` within the
+    /// configured timeout, and `ProcessExited` if the process exits first.
     ///
     /// # Example
     ///
     /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
     /// use portable_pty::CommandBuilder;
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
     /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// let mut cmd = CommandBuilder::new("echo");
-    /// cmd.arg("test");
-    /// harness.spawn(cmd)?;
+    /// harness.spawn(CommandBuilder::new("python3"))?;
     ///
-    /// let status = harness.wait_exit()?;
-    /// assert!(status.success());
+    /// harness.wait_until_idle(Duration::from_millis(200))?;
+    /// harness.send_text("print(6 * 7)\n")?;
+    /// harness.wait_until_idle(Duration::from_millis(200))?;
+    /// assert!(harness.screen_contents().contains("42"));
     /// # Ok(())
     /// # }
     /// ```
-    pub fn wait_exit(&mut self) -> Result<ExitStatus> {
-        self.terminal.wait()
-    }
+    pub fn wait_until_idle(&mut self, quiet: Duration) -> Result<()> {
+        let description = format!("process idle for {:?}", quiet);
+        let start = Instant::now();
+        let mut last_output = start;
+        let mut last_bytes = self.output_bytes_read;
+        let mut iterations = 0;
+        self.last_wait_polls = 0;
 
-    // ========================================================================
-    // Memory Profiling
-    // ========================================================================
+        loop {
+            let update = self.update_state();
+            let now = Instant::now();
 
-    /// Gets current memory usage estimate.
+            if self.output_bytes_read != last_bytes {
+                last_bytes = self.output_bytes_read;
+                last_output = now;
+            }
+
+            match update {
+                Ok(()) => {}
+                Err(TermTestError::ProcessExited) => {
+                    eprintln!("\n=== Process exited while waiting for: {} ===", description);
+                    eprintln!("Waited: {:?} ({} iterations)", start.elapsed(), iterations);
+                    self.print_hints();
+                    eprintln!("Final screen state:\n{}", self.state.debug_contents());
+                    eprintln!("==========================================\n");
+
+                    return Err(TermTestError::ProcessExited);
+                }
+                Err(e) => return Err(e),
+            }
+
+            if now.duration_since(last_output) >= quiet {
+                return Ok(());
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= self.timeout {
+                eprintln!("\n=== Timeout waiting for: {} ===", description);
+                eprintln!("Waited: {:?} ({} iterations)", elapsed, iterations);
+                self.print_hints();
+                eprintln!("Current screen state:\n{}", self.state.debug_contents());
+                eprintln!("==========================================\n");
+
+                return Err(TermTestError::Timeout {
+                    timeout_ms: self.timeout.as_millis() as u64,
+                });
+            }
+
+            iterations += 1;
+            self.last_wait_polls = iterations;
+            self.poll_wait();
+        }
+    }
+
+    /// Waits until the screen content stops changing.
     ///
-    /// Returns estimated memory usage based on:
-    /// - Screen buffer: `width * height * size_of::<Cell>()`
-    /// - Sixel regions: Sum of all Sixel data sizes
+    /// Keeps reading output and returns once the visible characters (compared
+    /// with [`ScreenState::content_hash`]) have stayed the same for
+    /// `quiet_period`. Unlike [`wait_until_idle`](Self::wait_until_idle), output
+    /// that doesn't change what is shown, such as cursor movement, color
+    /// changes or redrawing identical text, does not restart the period. A
+    /// process that exits counts as stable once its last output has been
+    /// processed.
     ///
-    /// This is a size estimate, not precise heap measurement. It's useful
-    /// for detecting memory regressions in tests.
+    /// # Arguments
     ///
-    /// # Returns
+    /// * `quiet_period` - How long the screen must stay unchanged
     ///
-    /// A [`MemoryResults`] struct with current and peak memory estimates.
+    /// # Errors
+    ///
+    /// Returns a `Timeout` error if the screen keeps changing past the
+    /// configured timeout.
     ///
     /// # Example
     ///
     /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use portable_pty::CommandBuilder;
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.spawn(CommandBuilder::new("my-app"))?;
     ///
-    /// let memory = harness.memory_usage();
-    /// println!("Current memory: {} bytes", memory.current_bytes);
-    /// println!("Peak memory: {} bytes", memory.peak_bytes);
+    /// // Instead of sleeping and hoping rendering is done
+    /// harness.wait_for_stable(Duration::from_millis(300))?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn memory_usage(&self) -> MemoryResults {
-        use std::mem::size_of;
+    pub fn wait_for_stable(&mut self, quiet_period: Duration) -> Result<()> {
+        let description = format!("screen stable for {:?}", quiet_period);
+        let start = Instant::now();
+        let mut last_hash = self.state.content_hash();
+        let mut unchanged_since = start;
+        let mut iterations = 0;
+        self.last_wait_polls = 0;
 
-        // Get screen dimensions
-        let (width, height) = self.state.size();
+        loop {
+            match self.update_state() {
+                Ok(()) | Err(TermTestError::ProcessExited) => {}
+                Err(e) => return Err(e),
+            }
 
-        // Estimate screen buffer size
-        // Each cell contains: char (4 bytes) + 2 Option<u8> (2 bytes) + 3 bool (3 bytes) = ~9 bytes
-        // But with padding/alignment it's likely more
-        let screen_buffer_size =
-            (width as usize) * (height as usize) * size_of::<crate::screen::Cell>();
+            let now = Instant::now();
+            let hash = self.state.content_hash();
+            if hash != last_hash {
+                last_hash = hash;
+                unchanged_since = now;
+            } else if now.duration_since(unchanged_since) >= quiet_period {
+                return Ok(());
+            }
 
-        // Estimate Sixel regions size
-        let sixel_size: usize = self
-            .state
-            .sixel_regions()
-            .iter()
-            .map(|region| region.data.len())
-            .sum();
+            let elapsed = start.elapsed();
+            if elapsed >= self.timeout {
+                eprintln!("\n=== Timeout waiting for: {} ===", description);
+                eprintln!("Waited: {:?} ({} iterations)", elapsed, iterations);
+                self.print_hints();
+                eprintln!("Current screen state:\n{}", self.state.debug_contents());
+                eprintln!("==========================================\n");
 
-        let total = screen_buffer_size + sixel_size;
+                return Err(TermTestError::Timeout {
+                    timeout_ms: self.timeout.as_millis() as u64,
+                });
+            }
 
-        // Note: peak_bytes is currently the same as current_bytes
-        // Future versions may track historical peaks
-        MemoryResults::new(total, total)
+            iterations += 1;
+            self.last_wait_polls = iterations;
+            self.poll_wait();
+        }
     }
 
-    /// Asserts memory is under a limit.
+    /// Waits for the cursor to reach a specific position.
     ///
-    /// Checks that the current estimated memory usage is below the specified
-    /// limit in bytes. This is useful for regression testing to ensure memory
-    /// usage doesn't grow unexpectedly.
+    /// This is useful for verifying cursor movements after sending input
+    /// or for tracking application state changes.
     ///
     /// # Arguments
     ///
-    /// * `limit_bytes` - Maximum allowed memory usage in bytes
+    /// * `pos` - Target cursor position as (row, col) tuple (0-based)
     ///
     /// # Errors
     ///
-    /// Returns an error if the current memory usage exceeds the limit.
-    /// The error message includes the current usage and the limit.
+    /// Returns a `Timeout` error if the cursor does not reach the position within the configured
+    /// timeout.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use ratatui_testlib::TuiTestHarness;
-    ///
-    /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(80, 24)?;
-    ///
-    /// // Assert memory stays under 1 MB
-    /// harness.assert_memory_under(1_000_000)?;
-    ///
-    /// // Assert memory stays under 100 KB
-    /// harness.assert_memory_under(100_000)?;
-    /// # Ok(())
-    /// # }
+    /// # use ratatui_testlib::TuiTestHarness;
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.wait_for_cursor((5, 10))?;
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
     /// ```
-    pub fn assert_memory_under(&self, limit_bytes: usize) -> Result<()> {
-        let memory = self.memory_usage();
-
-        if memory.current_bytes > limit_bytes {
-            return Err(TermTestError::Parse(format!(
-                "Memory usage exceeds limit: {} bytes > {} bytes (limit)\n{}",
-                memory.current_bytes,
-                limit_bytes,
-                memory.summary()
-            )));
-        }
-
-        Ok(())
+    pub fn wait_for_cursor(&mut self, pos: (u16, u16)) -> Result<()> {
+        let description = format!("cursor at ({}, {})", pos.0, pos.1);
+        self.wait_for_with_context(move |state| state.cursor_position() == pos, &description)
     }
 
-    // ========================================================================
-    // Position and Layout Assertions
-    // ========================================================================
-
-    /// Asserts that text appears at a specific position on the screen.
-    ///
-    /// This verifies that the given text starts at the exact (row, col) position.
-    /// The text must match character-by-character at that position.
+    /// Waits for the cursor to reach a specific position with a custom timeout.
     ///
     /// # Arguments
     ///
-    /// * `text` - The text to search for
-    /// * `row` - Row position (0-indexed)
-    /// * `col` - Column position (0-indexed)
+    /// * `pos` - Target cursor position as (row, col) tuple (0-based)
+    /// * `timeout` - Timeout duration for this operation
     ///
     /// # Errors
     ///
-    /// Returns [`TermTestError::Parse`] if the text is not found at the specified position.
-    /// The error message includes the actual content found at that position.
+    /// Returns a `Timeout` error if the cursor does not reach the position within the specified
+    /// timeout. Returns `ProcessExited` if the child process exits before the cursor reaches
+    /// the position.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use ratatui_testlib::TuiTestHarness;
-    ///
-    /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(80, 24)?;
-    /// // ... render UI with tab bar at bottom ...
-    ///
-    /// // Verify "Tab 1" appears at row 22, column 0
-    /// harness.assert_text_at_position("Tab 1", 22, 0)?;
-    /// # Ok(())
-    /// # }
+    /// # use ratatui_testlib::TuiTestHarness;
+    /// # use std::time::Duration;
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.wait_for_cursor_timeout((5, 10), Duration::from_millis(500))?;
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
     /// ```
-    pub fn assert_text_at_position(&self, text: &str, row: u16, col: u16) -> Result<()> {
-        let (width, height) = self.state.size();
+    pub fn wait_for_cursor_timeout(&mut self, pos: (u16, u16), timeout: Duration) -> Result<()> {
+        let description = format!("cursor at ({}, {})", pos.0, pos.1);
 
-        // Validate coordinates
-        if row >= height {
-            return Err(TermTestError::Parse(format!(
-                "Row {} is out of bounds (screen height: {})",
-                row, height
-            )));
-        }
-        if col >= width {
-            return Err(TermTestError::Parse(format!(
-                "Column {} is out of bounds (screen width: {})",
-                col, width
-            )));
-        }
+        let start = Instant::now();
+        let mut iterations = 0;
+        self.last_wait_polls = 0;
 
-        // Extract text at the position
-        let mut actual = String::new();
-        for (i, _) in text.chars().enumerate() {
-            let current_col = col.saturating_add(i as u16);
-            if current_col >= width {
-                break;
+        loop {
+            // Update state - this may return ProcessExited
+            match self.update_state() {
+                Ok(()) => {
+                    if self.state.cursor_position() == pos {
+                        return Ok(());
+                    }
+                }
+                Err(TermTestError::ProcessExited) => {
+                    // Process exited - check condition one final time
+                    if self.state.cursor_position() == pos {
+                        return Ok(());
+                    }
+
+                    let current_state = self.state.debug_contents();
+                    let cursor = self.state.cursor_position();
+
+                    eprintln!("\n=== Process exited while waiting for: {} ===", description);
+                    eprintln!("Waited: {:?} ({} iterations)", start.elapsed(), iterations);
+                    eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
+                    self.print_hints();
+                    eprintln!("Final screen state:\n{}", current_state);
+                    eprintln!("==========================================\n");
+
+                    return Err(TermTestError::ProcessExited);
+                }
+                Err(e) => return Err(e),
             }
-            if let Some(cell) = self.state.get_cell(row, current_col) {
-                actual.push(cell.c);
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                let current_state = self.state.debug_contents();
+                let cursor = self.state.cursor_position();
+
+                eprintln!("\n=== Timeout waiting for: {} ===", description);
+                eprintln!("Waited: {:?} ({} iterations)", elapsed, iterations);
+                eprintln!("Cursor position: row={}, col={}", cursor.0, cursor.1);
+                self.print_hints();
+                eprintln!("Current screen state:\n{}", current_state);
+                eprintln!("==========================================\n");
+
+                return Err(TermTestError::Timeout { timeout_ms: timeout.as_millis() as u64 });
             }
-        }
 
-        // Compare
-        if actual != text {
-            return Err(TermTestError::Parse(format!(
-                "Text mismatch at position ({}, {})\n  Expected: {:?}\n  Found:    {:?}\n\nScreen state:\n{}",
-                row, col, text, actual, self.state.debug_contents()
-            )));
+            iterations += 1;
+            self.last_wait_polls = iterations;
+            self.poll_wait();
         }
-
-        Ok(())
     }
 
-    /// Asserts that text appears anywhere within a specified rectangular area.
+    /// Waits for the cursor to come within `tolerance` cells of a position.
     ///
-    /// This searches for the text within the given bounds and succeeds if found
-    /// anywhere in that region.
+    /// The cursor matches when both its row and column differ from the target by
+    /// at most `tolerance`. This absorbs single-cell drift such as mixing up the
+    /// 1-based coordinates of escape sequences with this 0-based API. Use
+    /// [`wait_for_cursor`](Self::wait_for_cursor) when the position must match
+    /// exactly.
     ///
     /// # Arguments
     ///
-    /// * `text` - The text to search for
-    /// * `area` - The rectangular area to search within
+    /// * `pos` - Target cursor position as (row, col) tuple (0-based)
+    /// * `tolerance` - Maximum allowed distance in rows and in columns
     ///
     /// # Errors
     ///
-    /// Returns [`TermTestError::Parse`] if the text is not found within the area.
+    /// Returns a `Timeout` error if the cursor does not come near the position
+    /// within the configured timeout. Returns `ProcessExited` if the child
+    /// process exits first.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use ratatui_testlib::{Rect, TuiTestHarness};
-    ///
-    /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(80, 24)?;
-    /// // ... render UI ...
-    ///
-    /// // Verify "Preview" appears somewhere in the preview area
-    /// let preview_area = Rect::new(5, 40, 35, 15);
-    /// harness.assert_text_within_bounds("Preview", preview_area)?;
-    /// # Ok(())
-    /// # }
+    /// # use ratatui_testlib::TuiTestHarness;
+    /// # let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // Accepts any position from (4, 9) to (6, 11)
+    /// harness.wait_for_cursor_near((5, 10), 1)?;
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
     /// ```
-    pub fn assert_text_within_bounds(&self, text: &str, area: crate::screen::Rect) -> Result<()> {
-        let (width, height) = self.state.size();
-
-        // Search within the area
-        for row in area.y..area.bottom().min(height) {
-            for col in area.x..area.right().min(width) {
-                // Try to match text starting at this position
-                let mut matches = true;
-                for (i, expected_char) in text.chars().enumerate() {
-                    let current_col = col.saturating_add(i as u16);
-                    if current_col >= area.right() || current_col >= width {
-                        matches = false;
-                        break;
-                    }
-                    if let Some(cell) = self.state.get_cell(row, current_col) {
-                        if cell.c != expected_char {
-                            matches = false;
-                            break;
-                        }
-                    } else {
-                        matches = false;
-                        break;
-                    }
-                }
-                if matches {
-                    return Ok(()); // Found it!
-                }
-            }
-        }
+    pub fn wait_for_cursor_near(&mut self, pos: (u16, u16), tolerance: u16) -> Result<()> {
+        let description = format!("cursor within {} of ({}, {})", tolerance, pos.0, pos.1);
+        self.wait_for_with_context(
+            move |state| {
+                let (row, col) = state.cursor_position();
+                row.abs_diff(pos.0) <= tolerance && col.abs_diff(pos.1) <= tolerance
+            },
+            &description,
+        )
+    }
 
-        // Not found
-        Err(TermTestError::Parse(format!(
-            "Text {:?} not found within bounds (x={}, y={}, width={}, height={})\n\nScreen state:\n{}",
-            text, area.x, area.y, area.width, area.height,
-            self.state.debug_contents()
-        )))
+    /// Returns the current screen contents as a string.
+    pub fn screen_contents(&self) -> String {
+        self.state.contents()
     }
 
-    /// Asserts that two rectangular areas do not overlap.
+    /// Asserts the screen text matches an insta snapshot, ignoring styling.
     ///
-    /// This is useful for verifying that UI components don't render on top of
-    /// each other incorrectly.
+    /// Snapshots [`ScreenState::contents_trimmed`], which contains no colors,
+    /// attributes or escape sequences, so restyling the application doesn't
+    /// churn text-focused golden tests.
     ///
     /// # Arguments
     ///
-    /// * `rect1` - First rectangle
-    /// * `rect2` - Second rectangle
+    /// * `name` - Name of the snapshot
     ///
-    /// # Errors
+    /// # Panics
     ///
-    /// Returns [`TermTestError::Parse`] if the rectangles overlap.
+    /// Panics if the text doesn't match the stored snapshot, like
+    /// `insta::assert_snapshot!`.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use ratatui_testlib::{Rect, TuiTestHarness};
+    /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(80, 24)?;
-    ///
-    /// let sidebar = Rect::new(0, 0, 20, 24);
-    /// let preview = Rect::new(20, 0, 60, 24);
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... spawn app and wait for the main view ...
     ///
-    /// // Verify sidebar and preview don't overlap
-    /// harness.assert_no_overlap(sidebar, preview)?;
+    /// harness.assert_snapshot_text_only("main_view");
     /// # Ok(())
     /// # }
     /// ```
-    pub fn assert_no_overlap(
-        &self,
-        rect1: crate::screen::Rect,
-        rect2: crate::screen::Rect,
-    ) -> Result<()> {
-        if rect1.intersects(&rect2) {
-            return Err(TermTestError::Parse(format!(
-                "Rectangles overlap!\n\
-                 Rect 1: (x={}, y={}, width={}, height={})\n\
-                 Rect 2: (x={}, y={}, width={}, height={})\n\
-                 Overlap region exists between x=[{}, {}) and y=[{}, {})",
-                rect1.x,
-                rect1.y,
-                rect1.width,
-                rect1.height,
-                rect2.x,
-                rect2.y,
-                rect2.width,
-                rect2.height,
-                rect1.x.max(rect2.x),
-                rect1.right().min(rect2.right()),
-                rect1.y.max(rect2.y),
-                rect1.bottom().min(rect2.bottom())
-            )));
-        }
-        Ok(())
+    #[cfg(feature = "snapshot-insta")]
+    pub fn assert_snapshot_text_only(&self, name: &str) {
+        let text = self.state.contents_trimmed();
+        insta::assert_snapshot!(name, text);
     }
 
-    /// Asserts that two rectangles are aligned along a specified axis.
+    /// Returns the current cursor position as (row, col).
     ///
-    /// For horizontal alignment, the Y coordinates must match.
-    /// For vertical alignment, the X coordinates must match.
+    /// Both row and column are 0-based indices. This is required for Phase 3
+    /// Sixel position verification.
     ///
-    /// # Arguments
+    /// # Returns
     ///
-    /// * `rect1` - First rectangle
-    /// * `rect2` - Second rectangle
-    /// * `axis` - The axis to check alignment on
+    /// A tuple of (row, col) where both are 0-based.
     ///
-    /// # Errors
+    /// # Example
     ///
-    /// Returns [`TermTestError::Parse`] if the rectangles are not aligned.
+    /// ```rust,no_run
+    /// # use ratatui_testlib::TuiTestHarness;
+    /// # let harness = TuiTestHarness::new(80, 24)?;
+    /// let (row, col) = harness.cursor_position();
+    /// println!("Cursor at: row={}, col={}", row, col);
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn cursor_position(&self) -> (u16, u16) {
+        self.state.cursor_position()
+    }
+
+    /// Alias for `cursor_position()` for convenience.
+    ///
+    /// Returns the current cursor position as (row, col).
+    pub fn get_cursor_position(&self) -> (u16, u16) {
+        self.cursor_position()
+    }
+
+    /// Returns the current screen state.
+    ///
+    /// Provides immutable access to the terminal screen state for inspecting
+    /// rendered content without modifying it.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the current [`ScreenState`].
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use ratatui_testlib::{Axis, Rect, TuiTestHarness};
+    /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
     /// let harness = TuiTestHarness::new(80, 24)?;
-    ///
-    /// let button1 = Rect::new(10, 20, 15, 3);
-    /// let button2 = Rect::new(30, 20, 15, 3);
-    ///
-    /// // Verify both buttons are horizontally aligned (same Y)
-    /// harness.assert_aligned(button1, button2, Axis::Horizontal)?;
+    /// let state = harness.state();
+    /// println!("Screen size: {:?}", state.size());
     /// # Ok(())
     /// # }
     /// ```
-    pub fn assert_aligned(
-        &self,
-        rect1: crate::screen::Rect,
-        rect2: crate::screen::Rect,
-        axis: Axis,
-    ) -> Result<()> {
-        match axis {
-            Axis::Horizontal => {
-                if rect1.y != rect2.y {
-                    return Err(TermTestError::Parse(format!(
-                        "Rectangles not horizontally aligned (different Y coordinates)\n\
-                         Rect 1: y={} (x={}, width={}, height={})\n\
-                         Rect 2: y={} (x={}, width={}, height={})",
-                        rect1.y,
-                        rect1.x,
-                        rect1.width,
-                        rect1.height,
-                        rect2.y,
-                        rect2.x,
-                        rect2.width,
-                        rect2.height
-                    )));
-                }
-            }
-            Axis::Vertical => {
-                if rect1.x != rect2.x {
-                    return Err(TermTestError::Parse(format!(
-                        "Rectangles not vertically aligned (different X coordinates)\n\
-                         Rect 1: x={} (y={}, width={}, height={})\n\
-                         Rect 2: x={} (y={}, width={}, height={})",
-                        rect1.x,
-                        rect1.y,
-                        rect1.width,
-                        rect1.height,
-                        rect2.x,
-                        rect2.y,
-                        rect2.width,
-                        rect2.height
-                    )));
-                }
-            }
-        }
-        Ok(())
+    pub fn state(&self) -> &ScreenState {
+        &self.state
     }
 
-    // ========================================================================
-    // Sixel Graphics Validation APIs
-    // ========================================================================
-
-    /// Returns all captured Sixel regions from the screen state.
+    /// Returns a mutable reference to the screen state.
     ///
-    /// This provides direct access to all Sixel graphics detected in the terminal
-    /// output. Each region includes position and dimension information.
+    /// Allows direct manipulation of the screen state, which can be useful
+    /// for testing specific scenarios or feeding mock data.
     ///
     /// # Returns
     ///
-    /// A slice containing all [`SixelRegion`](crate::screen::SixelRegion) instances
-    /// currently on screen.
+    /// A mutable reference to the [`ScreenState`].
     ///
     /// # Example
     ///
@@ -1889,64 +3180,29 @@ impl TuiTestHarness {
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
     /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// // ... spawn app that renders Sixel graphics ...
-    ///
-    /// let regions = harness.sixel_regions();
-    /// for (i, region) in regions.iter().enumerate() {
-    ///     println!(
-    ///         "Sixel {}: position ({}, {}), size {}x{}",
-    ///         i, region.start_row, region.start_col, region.width, region.height
-    ///     );
-    /// }
-    /// # Ok(())
-    /// # }
-    /// ```
-    #[cfg(feature = "sixel")]
-    pub fn sixel_regions(&self) -> &[crate::screen::SixelRegion] {
-        self.state.sixel_regions()
-    }
-
-    /// Returns the count of Sixel graphics currently on screen.
-    ///
-    /// This is a convenience method equivalent to `harness.sixel_regions().len()`.
-    /// Useful for quick assertions about the number of graphics present.
-    ///
-    /// # Returns
-    ///
-    /// The number of Sixel graphics regions detected.
-    ///
-    /// # Example
-    ///
-    /// ```rust,no_run
-    /// use ratatui_testlib::TuiTestHarness;
-    ///
-    /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(80, 24)?;
-    /// // ... render graphics ...
-    ///
-    /// assert_eq!(harness.sixel_count(), 1, "Expected exactly one image");
+    /// harness.state_mut().feed(b"Test data");
+    /// assert!(harness.screen_contents().contains("Test"));
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "sixel")]
-    pub fn sixel_count(&self) -> usize {
-        self.sixel_regions().len()
+    pub fn state_mut(&mut self) -> &mut ScreenState {
+        &mut self.state
     }
 
-    /// Finds a Sixel graphic at a specific position.
+    /// Resizes the terminal.
     ///
-    /// Searches for a Sixel region that starts at the exact (row, col) position.
-    /// Returns `None` if no Sixel is found at that position.
+    /// Changes the terminal dimensions and resizes the screen state with
+    /// [`ScreenState::resize`], so output already rendered survives. This can
+    /// be useful for testing responsive TUI layouts.
     ///
     /// # Arguments
     ///
-    /// * `row` - Row to search (0-based)
-    /// * `col` - Column to search (0-based)
+    /// * `width` - New width in columns
+    /// * `height` - New height in rows
     ///
-    /// # Returns
+    /// # Errors
     ///
-    /// A reference to the [`SixelRegion`](crate::screen::SixelRegion) at that position,
-    /// or `None` if no Sixel starts there.
+    /// Returns an error if the resize operation fails.
     ///
     /// # Example
     ///
@@ -1954,208 +3210,268 @@ impl TuiTestHarness {
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(80, 24)?;
-    /// // ... render Sixel at (5, 10) ...
-    ///
-    /// if let Some(region) = harness.sixel_at(5, 10) {
-    ///     println!("Found Sixel: {}x{} pixels", region.width, region.height);
-    /// } else {
-    ///     println!("No Sixel at that position");
-    /// }
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.resize(120, 40)?;
+    /// assert_eq!(harness.state().size(), (120, 40));
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "sixel")]
-    pub fn sixel_at(&self, row: u16, col: u16) -> Option<&crate::screen::SixelRegion> {
-        self.state
-            .sixel_regions()
-            .iter()
-            .find(|r| r.start_row == row && r.start_col == col)
+    pub fn resize(&mut self, width: u16, height: u16) -> Result<()> {
+        self.terminal.resize(width, height)?;
+        self.state.resize(width, height);
+        Ok(())
     }
 
-    /// Asserts that all Sixel graphics are within the specified area.
+    /// Asserts that the application reports the expected terminal size.
     ///
-    /// This validates that every Sixel bounding rectangle is completely contained
-    /// within the given area. If any Sixel extends beyond the area, an error is
-    /// returned with details about which sequences are out of bounds.
+    /// Waits for the application to print its perceived size (e.g., via
+    /// `stty size` or `tput cols`) in a form matching `marker_regex`, then checks
+    /// that it equals `expected`. A mismatch usually indicates a resize
+    /// propagation bug.
+    ///
+    /// The regex must capture the two numbers. Named groups `cols` and `rows` are
+    /// used when present; otherwise the first capture group is taken as the
+    /// column count and the second as the row count.
     ///
     /// # Arguments
     ///
-    /// * `area` - Bounding area as (row, col, width, height) tuple
+    /// * `expected` - Expected size as (cols, rows), the same order as
+    ///   [`ScreenState::size`]
+    /// * `marker_regex` - Pattern locating the size report on screen
     ///
     /// # Errors
     ///
-    /// Returns [`TermTestError::SixelValidation`] if any Sixel is outside the area.
-    /// The error message includes the positions of all out-of-bounds graphics.
+    /// Returns a `Parse` error if the regex is invalid, lacks the required
+    /// captures, or the reported size differs from `expected`. Returns a `Timeout`
+    /// error if no match appears within the configured timeout.
     ///
     /// # Example
     ///
     /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(80, 24)?;
-    /// // ... render graphics ...
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.spawn(CommandBuilder::new("my-app"))?;
     ///
-    /// // Verify all graphics are in the preview panel
-    /// let preview_area = (5, 40, 35, 15); // (row, col, width, height)
-    /// harness.assert_sixel_within_bounds(preview_area)?;
+    /// // App prints "size: 80x24" in its status bar
+    /// harness.assert_size_reported_as((80, 24), r"size: (\d+)x(\d+)")?;
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "sixel")]
-    pub fn assert_sixel_within_bounds(&self, area: (u16, u16, u16, u16)) -> Result<()> {
-        use crate::sixel::SixelCapture;
-        let capture = SixelCapture::from_screen_state(&self.state);
-        capture.assert_all_within(area)
+    pub fn assert_size_reported_as(
+        &mut self,
+        expected: (u16, u16),
+        marker_regex: &str,
+    ) -> Result<()> {
+        let re = regex::Regex::new(marker_regex)
+            .map_err(|e| TermTestError::Parse(format!("Invalid size regex: {}", e)))?;
+
+        let description = format!("size report matching /{}/", marker_regex);
+        self.wait_for_with_context(|state| re.is_match(&state.contents()), &description)?;
+
+        let contents = self.state.contents();
+        let caps = re
+            .captures(&contents)
+            .ok_or_else(|| TermTestError::Parse("Size report disappeared".to_string()))?;
+
+        let (cols, rows) = match (caps.name("cols"), caps.name("rows")) {
+            (Some(cols), Some(rows)) => (cols, rows),
+            _ => match (caps.get(1), caps.get(2)) {
+                (Some(cols), Some(rows)) => (cols, rows),
+                _ => {
+                    return Err(TermTestError::Parse(format!(
+                        "Size regex /{}/ must capture cols and rows",
+                        marker_regex
+                    )))
+                }
+            },
+        };
+
+        let parse = |m: regex::Match<'_>| {
+            m.as_str().trim().parse::<u16>().map_err(|e| {
+                TermTestError::Parse(format!("Invalid size value '{}': {}", m.as_str(), e))
+            })
+        };
+        let reported = (parse(cols)?, parse(rows)?);
+
+        if reported != expected {
+            return Err(TermTestError::Parse(format!(
+                "Application reported size {}x{} (cols x rows), expected {}x{}\nCurrent screen state:\n{}",
+                reported.0,
+                reported.1,
+                expected.0,
+                expected.1,
+                self.state.debug_contents()
+            )));
+        }
+        Ok(())
     }
 
-    /// Checks if any Sixel graphics overlap with the specified area.
-    ///
-    /// Returns `true` if at least one Sixel bounding rectangle intersects with
-    /// the given area, even partially. This is useful for detecting graphics in
-    /// specific screen regions.
-    ///
-    /// # Arguments
-    ///
-    /// * `area` - Area to check as (row, col, width, height) tuple
+    /// Checks if the child process is still running.
     ///
     /// # Returns
     ///
-    /// `true` if any Sixel overlaps with the area, `false` otherwise.
+    /// `true` if a process is currently running in the PTY, `false` otherwise.
     ///
     /// # Example
     ///
     /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(80, 24)?;
-    /// // ... render graphics ...
-    ///
-    /// let preview_area = (5, 40, 35, 15);
-    /// if harness.has_sixel_in_area(preview_area) {
-    ///     println!("Graphics detected in preview area");
-    /// }
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// let cmd = CommandBuilder::new("sleep");
+    /// harness.spawn(cmd)?;
+    /// assert!(harness.is_running());
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "sixel")]
-    pub fn has_sixel_in_area(&self, area: (u16, u16, u16, u16)) -> bool {
-        use crate::sixel::SixelCapture;
-        let capture = SixelCapture::from_screen_state(&self.state);
-        !capture.sequences_in_area(area).is_empty()
+    pub fn is_running(&mut self) -> bool {
+        self.terminal.is_running()
     }
 
-    /// Verifies that Sixel graphics were cleared after a screen update.
+    /// Waits for the child process to exit.
     ///
-    /// This method records the current Sixel count, calls [`update`](Self::update)
-    /// to refresh the screen state, and then checks if the count decreased.
-    /// It's useful for verifying that graphics are properly cleared during
-    /// screen transitions (e.g., switching between files in a previewer).
+    /// Blocks until the spawned process terminates and returns its exit status.
+    /// With a [global deadline](Self::with_global_deadline) set, blocks at most
+    /// until the deadline.
     ///
     /// # Returns
     ///
-    /// `Ok(true)` if the Sixel count decreased, `Ok(false)` if it stayed the same
-    /// or increased.
+    /// The [`ExitStatus`] of the terminated process.
     ///
     /// # Errors
     ///
-    /// Returns an error if the screen update fails.
+    /// Returns [`TermTestError::NoProcessRunning`] if no process is currently running.
+    /// Returns [`TermTestError::GlobalDeadlineExceeded`] if the global deadline
+    /// passes first.
     ///
     /// # Example
     ///
     /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
     /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// // ... render some graphics ...
-    ///
-    /// // Simulate screen transition (e.g., press a key to switch files)
-    /// harness.send_key(ratatui_testlib::KeyCode::Down)?;
+    /// let mut cmd = CommandBuilder::new("echo");
+    /// cmd.arg("test");
+    /// harness.spawn(cmd)?;
     ///
-    /// // Verify graphics were cleared
-    /// if harness.verify_sixel_cleared()? {
-    ///     println!("Graphics properly cleared on transition");
-    /// }
+    /// let status = harness.wait_exit()?;
+    /// assert!(status.success());
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "sixel")]
-    pub fn verify_sixel_cleared(&mut self) -> Result<bool> {
-        let before = self.sixel_count();
-        self.update_state()?;
-        let after = self.sixel_count();
-        Ok(after < before)
+    pub fn wait_exit(&mut self) -> Result<ExitStatus> {
+        loop {
+            self.check_deadline()?;
+            let Some(at) = self.deadline_at else {
+                return self.terminal.wait();
+            };
+            match self
+                .terminal
+                .wait_timeout(at.saturating_duration_since(Instant::now()))
+            {
+                Err(TermTestError::Timeout { .. }) => continue,
+                result => return result,
+            }
+        }
     }
 
-    /// Asserts that a Sixel graphic appears in a typical preview area.
+    /// Waits for the child process to exit, giving up after `timeout`.
     ///
-    /// This is a convenience method for the common dgx-pixels use case where
-    /// image previews are displayed in a standard preview panel layout.
+    /// Unlike [`wait_exit`](Self::wait_exit), this never blocks indefinitely,
+    /// which makes it safe for tests that expect the application to quit (for
+    /// example after pressing `q`). Output is still read while waiting, so the
+    /// screen reflects what the process printed before exiting. Checks happen
+    /// at the configured poll interval.
     ///
-    /// The default preview area is:
-    /// - Rows: 5-35 (30 rows, leaving space for header/footer)
-    /// - Cols: 40-75 (35 columns, typical split-pane layout)
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for the process to exit
+    ///
+    /// # Returns
+    ///
+    /// The [`ExitStatus`] of the terminated process.
     ///
     /// # Errors
     ///
-    /// Returns [`TermTestError::SixelValidation`] if no Sixel graphics are
-    /// found in the preview area.
+    /// Returns a `Timeout` error if the process is still running after
+    /// `timeout`, or [`TermTestError::NoProcessRunning`] if no process was
+    /// spawned.
     ///
     /// # Example
     ///
     /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use portable_pty::CommandBuilder;
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(80, 24)?;
-    /// // ... render image preview ...
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.spawn(CommandBuilder::new("my-app"))?;
+    /// harness.wait_for_text("Ready")?;
     ///
-    /// // Quick assertion for standard preview layout
-    /// harness.assert_preview_has_sixel()?;
+    /// harness.send_text("q")?;
+    /// let status = harness.wait_for_exit(Duration::from_secs(2))?;
+    /// assert!(status.success());
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "sixel")]
-    pub fn assert_preview_has_sixel(&self) -> Result<()> {
-        // Standard dgx-pixels preview area layout
-        // Assumes 80x24 terminal with:
-        // - Left sidebar: cols 0-39
-        // - Preview area: cols 40-75, rows 5-20
-        let preview_area = (5, 40, 35, 15);
+    pub fn wait_for_exit(&mut self, timeout: Duration) -> Result<ExitStatus> {
+        let start = Instant::now();
+        let mut iterations = 0;
+        self.last_wait_polls = 0;
 
-        if !self.has_sixel_in_area(preview_area) {
-            return Err(TermTestError::SixelValidation(format!(
-                "No Sixel graphics found in standard preview area {:?}. \
-                    Current Sixel count: {}. \
-                    Regions: {:?}",
-                preview_area,
-                self.sixel_count(),
-                self.sixel_regions()
-                    .iter()
-                    .map(|r| (r.start_row, r.start_col, r.width, r.height))
-                    .collect::<Vec<_>>()
-            )));
+        loop {
+            match self.update_state() {
+                Ok(()) | Err(TermTestError::ProcessExited) => {}
+                Err(e) => return Err(e),
+            }
+
+            if !self.is_running() {
+                // The child has already been reaped, so this returns at once
+                return self.terminal.wait();
+            }
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                eprintln!("\n=== Timeout waiting for: process exit ===");
+                eprintln!("Waited: {:?} ({} iterations)", elapsed, iterations);
+                self.print_hints();
+                eprintln!("Current screen state:\n{}", self.state.debug_contents());
+                eprintln!("==========================================\n");
+
+                return Err(TermTestError::Timeout { timeout_ms: timeout.as_millis() as u64 });
+            }
+
+            iterations += 1;
+            self.last_wait_polls = iterations;
+            self.poll_wait();
         }
-        Ok(())
     }
 
-    /// Asserts that a Sixel graphic appears in a custom preview area.
-    ///
-    /// Similar to [`assert_preview_has_sixel`](Self::assert_preview_has_sixel),
-    /// but allows specifying a custom preview area. This is useful for applications
-    /// with non-standard layouts.
+    // ========================================================================
+    // Memory Profiling
+    // ========================================================================
+
+    /// Gets current memory usage estimate.
     ///
-    /// # Arguments
+    /// Returns estimated memory usage based on:
+    /// - Screen buffer: `width * height * size_of::<Cell>()`
+    /// - Sixel regions: Sum of all Sixel data sizes
     ///
-    /// * `preview_area` - Custom preview area as (row, col, width, height)
+    /// This is a size estimate, not precise heap measurement. It's useful
+    /// for detecting memory regressions in tests.
     ///
-    /// # Errors
+    /// # Returns
     ///
-    /// Returns [`TermTestError::SixelValidation`] if no Sixel graphics are
-    /// found in the specified preview area.
+    /// A [`MemoryResults`] struct with current and peak memory estimates.
     ///
     /// # Example
     ///
@@ -2163,179 +3479,231 @@ impl TuiTestHarness {
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::new(120, 40)?;
-    /// // ... render image preview in large terminal ...
+    /// let harness = TuiTestHarness::new(80, 24)?;
     ///
-    /// // Custom preview area for larger terminal
-    /// let custom_area = (10, 50, 60, 25);
-    /// harness.assert_preview_has_sixel_in(custom_area)?;
+    /// let memory = harness.memory_usage();
+    /// println!("Current memory: {} bytes", memory.current_bytes);
+    /// println!("Peak memory: {} bytes", memory.peak_bytes);
     /// # Ok(())
     /// # }
     /// ```
-    #[cfg(feature = "sixel")]
-    pub fn assert_preview_has_sixel_in(&self, preview_area: (u16, u16, u16, u16)) -> Result<()> {
-        if !self.has_sixel_in_area(preview_area) {
-            return Err(TermTestError::SixelValidation(format!(
-                "No Sixel graphics found in preview area {:?}. \
-                    Current Sixel count: {}. \
-                    Regions: {:?}",
-                preview_area,
-                self.sixel_count(),
-                self.sixel_regions()
-                    .iter()
-                    .map(|r| (r.start_row, r.start_col, r.width, r.height))
-                    .collect::<Vec<_>>()
-            )));
-        }
-        Ok(())
-    }
+    pub fn memory_usage(&self) -> MemoryResults {
+        use std::mem::size_of;
 
-    // ========================================================================
-    // Golden File Testing (Visual Regression)
-    // ========================================================================
+        // Get screen dimensions
+        let (width, height) = self.state.size();
 
-    /// Save the current screen state as a golden file.
+        // Estimate screen buffer size
+        // Each cell contains: char (4 bytes) + 2 Option<u8> (2 bytes) + 3 bool (3 bytes) = ~9 bytes
+        // But with padding/alignment it's likely more
+        let screen_buffer_size =
+            (width as usize) * (height as usize) * size_of::<crate::screen::Cell>();
+
+        // Estimate Sixel regions size
+        let sixel_size: usize = self
+            .state
+            .sixel_regions()
+            .iter()
+            .map(|region| region.data.len())
+            .sum();
+
+        let total = screen_buffer_size + sixel_size;
+
+        // Note: peak_bytes is currently the same as current_bytes
+        // Future versions may track historical peaks
+        MemoryResults::new(total, total)
+    }
+
+    /// Asserts memory is under a limit.
     ///
-    /// Golden files capture the expected terminal output for visual regression testing.
-    /// They are saved in the directory specified by the `GOLDEN_DIR` environment variable,
-    /// or `tests/golden/` by default.
+    /// Checks that the current estimated memory usage is below the specified
+    /// limit in bytes. This is useful for regression testing to ensure memory
+    /// usage doesn't grow unexpectedly.
     ///
     /// # Arguments
     ///
-    /// * `name` - Name for the golden file (used as filename without extension)
-    ///
-    /// # Returns
-    ///
-    /// The path where the golden file was saved.
+    /// * `limit_bytes` - Maximum allowed memory usage in bytes
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be written or the directory cannot be created.
+    /// Returns an error if the current memory usage exceeds the limit.
+    /// The error message includes the current usage and the limit.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use portable_pty::CommandBuilder;
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// let cmd = CommandBuilder::new("my-app");
-    /// harness.spawn(cmd)?;
-    /// harness.wait_for_text("Welcome")?;
+    /// let harness = TuiTestHarness::new(80, 24)?;
     ///
-    /// // Save current state as golden
-    /// let path = harness.save_golden("welcome_screen")?;
-    /// println!("Saved golden file to: {}", path.display());
+    /// // Assert memory stays under 1 MB
+    /// harness.assert_memory_under(1_000_000)?;
+    ///
+    /// // Assert memory stays under 100 KB
+    /// harness.assert_memory_under(100_000)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn save_golden(&self, name: &str) -> Result<std::path::PathBuf> {
-        crate::golden::save_golden(name, &self.state)
+    pub fn assert_memory_under(&self, limit_bytes: usize) -> Result<()> {
+        let memory = self.memory_usage();
+
+        if memory.current_bytes > limit_bytes {
+            return Err(TermTestError::Parse(format!(
+                "Memory usage exceeds limit: {} bytes > {} bytes (limit)\n{}",
+                memory.current_bytes,
+                limit_bytes,
+                memory.summary()
+            )));
+        }
+
+        Ok(())
     }
 
-    /// Compare current screen state against a golden file.
-    ///
-    /// This method loads a previously saved golden file and compares it against
-    /// the current screen state. If they don't match, it generates a detailed
-    /// unified diff showing the differences.
+    // ========================================================================
+    // Position and Layout Assertions
+    // ========================================================================
+
+    /// Asserts that text appears at a specific position on the screen.
     ///
-    /// If the `UPDATE_GOLDENS=1` environment variable is set, this will update
-    /// the golden file instead of comparing (useful for updating all goldens at once).
+    /// This verifies that the given text starts at the exact (row, col) position.
+    /// The text must match character-by-character at that position.
     ///
     /// # Arguments
     ///
-    /// * `name` - Name of the golden file to compare against (without extension)
-    ///
-    /// # Returns
-    ///
-    /// `Ok(())` if the states match (or golden was updated in update mode).
+    /// * `text` - The text to search for
+    /// * `row` - Row position (0-indexed)
+    /// * `col` - Column position (0-indexed)
     ///
     /// # Errors
     ///
-    /// Returns an error with a detailed diff if the states don't match, or if
-    /// the golden file cannot be read.
+    /// Returns [`TermTestError::Parse`] if the text is not found at the specified position.
+    /// The error message includes the actual content found at that position.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use portable_pty::CommandBuilder;
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// let cmd = CommandBuilder::new("my-app");
-    /// harness.spawn(cmd)?;
-    /// harness.wait_for_text("Welcome")?;
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render UI with tab bar at bottom ...
     ///
-    /// // Compare against previously saved golden
-    /// harness.assert_matches_golden("welcome_screen")?;
+    /// // Verify "Tab 1" appears at row 22, column 0
+    /// harness.assert_text_at_position("Tab 1", 22, 0)?;
     /// # Ok(())
     /// # }
     /// ```
-    ///
-    /// # Update Mode
-    ///
-    /// To update all golden files at once:
-    ///
-    /// ```bash
-    /// UPDATE_GOLDENS=1 cargo test
-    /// ```
-    pub fn assert_matches_golden(&self, name: &str) -> Result<()> {
-        crate::golden::assert_matches_golden(name, &self.state)
+    pub fn assert_text_at_position(&self, text: &str, row: u16, col: u16) -> Result<()> {
+        let (width, height) = self.state.size();
+
+        // Validate coordinates
+        if row >= height {
+            return Err(TermTestError::Parse(format!(
+                "Row {} is out of bounds (screen height: {})",
+                row, height
+            )));
+        }
+        if col >= width {
+            return Err(TermTestError::Parse(format!(
+                "Column {} is out of bounds (screen width: {})",
+                col, width
+            )));
+        }
+
+        // Extract text at the position
+        let mut actual = String::new();
+        for (i, _) in text.chars().enumerate() {
+            let current_col = col.saturating_add(i as u16);
+            if current_col >= width {
+                break;
+            }
+            if let Some(cell) = self.state.get_cell(row, current_col) {
+                actual.push(cell.c);
+            }
+        }
+
+        // Compare
+        if actual != text {
+            return Err(TermTestError::Parse(format!(
+                "Text mismatch at position ({}, {})\n  Expected: {:?}\n  Found:    {:?}\n\nScreen state:\n{}",
+                row, col, text, actual, self.state.debug_contents()
+            )));
+        }
+
+        Ok(())
     }
 
-    /// Update a golden file with the current screen state.
+    /// Asserts that two rows have identical trimmed contents.
     ///
-    /// This is equivalent to `save_golden()` but makes the intent clearer when
-    /// you're explicitly updating an existing golden file.
+    /// Useful for table and alignment checks, such as verifying that a header
+    /// underline spans the same width as the header. See
+    /// [`ScreenState::rows_equal`] for the comparison rules.
     ///
     /// # Arguments
     ///
-    /// * `name` - Name of the golden file to update (without extension)
-    ///
-    /// # Returns
-    ///
-    /// The path where the golden file was saved.
+    /// * `a` - First row index (0-based)
+    /// * `b` - Second row index (0-based)
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be written.
+    /// Returns [`TermTestError::Parse`] if the rows differ. The error message shows
+    /// both rows and marks the first differing column.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use portable_pty::CommandBuilder;
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// let cmd = CommandBuilder::new("my-app");
-    /// harness.spawn(cmd)?;
-    /// harness.wait_for_text("New Layout")?;
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render a bordered table ...
     ///
-    /// // Update golden file after intentional UI changes
-    /// harness.update_golden("main_screen")?;
+    /// // Top and bottom borders should match
+    /// harness.assert_rows_equal(0, 10)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn update_golden(&self, name: &str) -> Result<std::path::PathBuf> {
-        crate::golden::update_golden(name, &self.state)
-    }
+    pub fn assert_rows_equal(&self, a: u16, b: u16) -> Result<()> {
+        if self.state.rows_equal(a, b) {
+            return Ok(());
+        }
 
-    // ============================================================================
-    // Recording and Debug Methods
-    // ============================================================================
+        let row_a = self.state.row_contents(a);
+        let row_b = self.state.row_contents(b);
+        let (row_a, row_b) = (row_a.trim_end(), row_b.trim_end());
+        let diff_col = row_a
+            .chars()
+            .zip(row_b.chars())
+            .position(|(x, y)| x != y)
+            .unwrap_or_else(|| row_a.chars().count().min(row_b.chars().count()));
 
-    /// Starts recording all I/O events and state changes.
+        Err(TermTestError::Parse(format!(
+            "Rows {} and {} differ at column {}\n  Row {:>3}: {:?}\n  Row {:>3}: {:?}\n           {}^\n\nScreen state:\n{}",
+            a,
+            b,
+            diff_col,
+            a,
+            row_a,
+            b,
+            row_b,
+            " ".repeat(diff_col + 1),
+            self.state.debug_contents()
+        )))
+    }
+
+    /// Asserts that the line starting at a row fits on one row.
     ///
-    /// When recording is enabled, the harness will capture:
-    /// - All input sent to the PTY
-    /// - All output received from the PTY
-    /// - Screen state changes after processing escape sequences
+    /// Checks [`ScreenState::line_wrapped_at`]. Useful for responsive layouts,
+    /// e.g. checking that a status line fits at 80 columns.
     ///
-    /// Recordings can be saved to disk using [`save_recording`](Self::save_recording)
-    /// for debugging failed tests or understanding application behavior.
+    /// # Arguments
+    ///
+    /// * `row` - Row where the line starts (0-based)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if the line wrapped onto the next row.
     ///
     /// # Example
     ///
@@ -2343,349 +3711,326 @@ impl TuiTestHarness {
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// harness.start_recording();
-    ///
-    /// // Test operations...
-    /// harness.send_text("hello\n")?;
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render the status line on the last row but one ...
     ///
-    /// // Save recording for debugging
-    /// harness.save_recording("test_recording.json")?;
+    /// harness.assert_no_wrap(22)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn start_recording(&mut self) {
-        self.recording = true;
-        self.recording_start = Some(Instant::now());
-        self.recorded_events.clear();
+    pub fn assert_no_wrap(&self, row: u16) -> Result<()> {
+        if !self.state.line_wrapped_at(row) {
+            return Ok(());
+        }
+        Err(TermTestError::Parse(format!(
+            "Line at row {} wrapped past column {}\n  Row {:>3}: {:?}\n  Row {:>3}: {:?}\n\nScreen state:\n{}",
+            row,
+            self.state.cols(),
+            row,
+            self.state.row_contents(row),
+            row + 1,
+            self.state.row_contents(row + 1).trim_end(),
+            self.state.debug_contents()
+        )))
     }
 
-    /// Stops recording I/O events.
+    /// Asserts that the line starting at a row wrapped onto the next row.
     ///
-    /// Recording can be stopped without saving, or you can call
-    /// [`save_recording`](Self::save_recording) to persist the events.
-    pub fn stop_recording(&mut self) {
-        self.recording = false;
+    /// The counterpart of [`assert_no_wrap`](Self::assert_no_wrap), for
+    /// checking that long content wraps rather than being truncated.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row where the line starts (0-based)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if the line fits on its row.
+    pub fn assert_wraps(&self, row: u16) -> Result<()> {
+        if self.state.line_wrapped_at(row) {
+            return Ok(());
+        }
+        Err(TermTestError::Parse(format!(
+            "Line at row {} did not wrap\n  Row {:>3}: {:?}\n\nScreen state:\n{}",
+            row,
+            row,
+            self.state.row_contents(row).trim_end(),
+            self.state.debug_contents()
+        )))
     }
 
-    /// Saves the current recording to a file in JSON format.
+    /// Asserts that text appears anywhere within a specified rectangular area.
     ///
-    /// The recording includes all captured events with timestamps, which can be
-    /// useful for debugging test failures or understanding application behavior.
+    /// This searches for the text within the given bounds and succeeds if found
+    /// anywhere in that region.
     ///
     /// # Arguments
     ///
-    /// * `path` - Path where the recording should be saved
+    /// * `text` - The text to search for
+    /// * `area` - The rectangular area to search within
     ///
     /// # Errors
     ///
-    /// Returns an error if:
-    /// - The file cannot be created
-    /// - Serialization fails
-    /// - Writing to the file fails
+    /// Returns [`TermTestError::Parse`] if the text is not found within the area.
     ///
-    /// # Format
+    /// # Example
     ///
-    /// The saved file is a JSON array containing timestamped events:
+    /// ```rust,no_run
+    /// use ratatui_testlib::{Rect, TuiTestHarness};
     ///
-    /// ```json
-    /// [
-    ///   {
-    ///     "timestamp_ms": 0,
-    ///     "event": {
-    ///       "type": "Input",
-    ///       "data": [104, 101, 108, 108, 111]
-    ///     }
-    ///   },
-    ///   {
-    ///     "timestamp_ms": 150,
-    ///     "event": {
-    ///       "type": "Output",
-    ///       "data": [27, 91, 72, 27, 91, 50, 74]
-    ///     }
-    ///   }
-    /// ]
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render UI ...
+    ///
+    /// // Verify "Preview" appears somewhere in the preview area
+    /// let preview_area = Rect::new(5, 40, 35, 15);
+    /// harness.assert_text_within_bounds("Preview", preview_area)?;
+    /// # Ok(())
+    /// # }
     /// ```
-    pub fn save_recording<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        use std::io::BufWriter;
-
-        let file = File::create(path.as_ref()).map_err(|e| {
-            TermTestError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to create recording file: {}", e),
-            ))
-        })?;
-
-        let mut writer = BufWriter::new(file);
-
-        // Write JSON array
-        writeln!(writer, "[").map_err(|e| {
-            TermTestError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to write recording: {}", e),
-            ))
-        })?;
-
-        for (i, event) in self.recorded_events.iter().enumerate() {
-            let comma = if i < self.recorded_events.len() - 1 {
-                ","
-            } else {
-                ""
-            };
+    pub fn assert_text_within_bounds(&self, text: &str, area: crate::screen::Rect) -> Result<()> {
+        let (width, height) = self.state.size();
 
-            // Format event as JSON
-            let event_json = match &event.event {
-                RecordedEvent::Input(data) => {
-                    format!(
-                        r#"  {{"timestamp_ms": {}, "event": {{"type": "Input", "data": {:?}}}}}{}"#,
-                        event.timestamp.as_millis(),
-                        data,
-                        comma
-                    )
-                }
-                RecordedEvent::Output(data) => {
-                    format!(
-                        r#"  {{"timestamp_ms": {}, "event": {{"type": "Output", "data": {:?}}}}}{}"#,
-                        event.timestamp.as_millis(),
-                        data,
-                        comma
-                    )
+        // Search within the area
+        for row in area.y..area.bottom().min(height) {
+            for col in area.x..area.right().min(width) {
+                // Try to match text starting at this position
+                let mut matches = true;
+                for (i, expected_char) in text.chars().enumerate() {
+                    let current_col = col.saturating_add(i as u16);
+                    if current_col >= area.right() || current_col >= width {
+                        matches = false;
+                        break;
+                    }
+                    if let Some(cell) = self.state.get_cell(row, current_col) {
+                        if cell.c != expected_char {
+                            matches = false;
+                            break;
+                        }
+                    } else {
+                        matches = false;
+                        break;
+                    }
                 }
-                RecordedEvent::StateChange { contents, cursor } => {
-                    let escaped_contents = contents
-                        .replace('\\', "\\\\")
-                        .replace('"', "\\\"")
-                        .replace('\n', "\\n")
-                        .replace('\r', "\\r")
-                        .replace('\t', "\\t");
-                    format!(
-                        r#"  {{"timestamp_ms": {}, "event": {{"type": "StateChange", "contents": "{}", "cursor": [{}, {}]}}}}{}"#,
-                        event.timestamp.as_millis(),
-                        escaped_contents,
-                        cursor.0,
-                        cursor.1,
-                        comma
-                    )
+                if matches {
+                    return Ok(()); // Found it!
                 }
-            };
-
-            writeln!(writer, "{}", event_json).map_err(|e| {
-                TermTestError::Io(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    format!("Failed to write event: {}", e),
-                ))
-            })?;
+            }
         }
 
-        writeln!(writer, "]").map_err(|e| {
-            TermTestError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to write recording: {}", e),
-            ))
-        })?;
-
-        writer.flush().map_err(|e| {
-            TermTestError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to flush recording: {}", e),
-            ))
-        })?;
-
-        Ok(())
-    }
-
-    /// Checks if recording is currently active.
-    ///
-    /// # Returns
-    ///
-    /// `true` if recording is active, `false` otherwise.
-    pub fn is_recording(&self) -> bool {
-        self.recording
+        // Not found
+        Err(TermTestError::Parse(format!(
+            "Text {:?} not found within bounds (x={}, y={}, width={}, height={})\n\nScreen state:\n{}",
+            text, area.x, area.y, area.width, area.height,
+            self.state.debug_contents()
+        )))
     }
 
-    /// Saves the current screen state to a file.
+    /// Asserts that two rectangular areas do not overlap.
     ///
-    /// This is useful for capturing the screen state when a test fails,
-    /// allowing you to inspect what was actually displayed.
+    /// This is useful for verifying that UI components don't render on top of
+    /// each other incorrectly.
     ///
     /// # Arguments
     ///
-    /// * `path` - Path where the screenshot should be saved
+    /// * `rect1` - First rectangle
+    /// * `rect2` - Second rectangle
     ///
     /// # Errors
     ///
-    /// Returns an error if the file cannot be created or written.
+    /// Returns [`TermTestError::Parse`] if the rectangles overlap.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use ratatui_testlib::TuiTestHarness;
+    /// use ratatui_testlib::{Rect, TuiTestHarness};
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// // ... spawn app and interact ...
+    /// let harness = TuiTestHarness::new(80, 24)?;
     ///
-    /// // Save screenshot on failure
-    /// if !harness.screen_contents().contains("expected") {
-    ///     harness.save_screenshot("failure_screenshot.txt")?;
-    ///     panic!("Test failed, screenshot saved");
-    /// }
+    /// let sidebar = Rect::new(0, 0, 20, 24);
+    /// let preview = Rect::new(20, 0, 60, 24);
+    ///
+    /// // Verify sidebar and preview don't overlap
+    /// harness.assert_no_overlap(sidebar, preview)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn save_screenshot<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let mut file = File::create(path.as_ref()).map_err(|e| {
-            TermTestError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to create screenshot file: {}", e),
-            ))
-        })?;
-
-        let contents = self.screenshot_string();
-        file.write_all(contents.as_bytes()).map_err(|e| {
-            TermTestError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Failed to write screenshot: {}", e),
-            ))
-        })?;
-
+    pub fn assert_no_overlap(
+        &self,
+        rect1: crate::screen::Rect,
+        rect2: crate::screen::Rect,
+    ) -> Result<()> {
+        if rect1.intersects(&rect2) {
+            return Err(TermTestError::Parse(format!(
+                "Rectangles overlap!\n\
+                 Rect 1: (x={}, y={}, width={}, height={})\n\
+                 Rect 2: (x={}, y={}, width={}, height={})\n\
+                 Overlap region exists between x=[{}, {}) and y=[{}, {})",
+                rect1.x,
+                rect1.y,
+                rect1.width,
+                rect1.height,
+                rect2.x,
+                rect2.y,
+                rect2.width,
+                rect2.height,
+                rect1.x.max(rect2.x),
+                rect1.right().min(rect2.right()),
+                rect1.y.max(rect2.y),
+                rect1.bottom().min(rect2.bottom())
+            )));
+        }
         Ok(())
     }
 
-    /// Returns the current screen state as a string.
+    /// Asserts that two rectangles are aligned along a specified axis.
     ///
-    /// This includes the screen contents with a header showing dimensions
-    /// and cursor position, formatted for easy reading in logs.
+    /// For horizontal alignment, the Y coordinates must match.
+    /// For vertical alignment, the X coordinates must match.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A formatted string containing screen state information.
+    /// * `rect1` - First rectangle
+    /// * `rect2` - Second rectangle
+    /// * `axis` - The axis to check alignment on
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if the rectangles are not aligned.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use ratatui_testlib::TuiTestHarness;
+    /// use ratatui_testlib::{Axis, Rect, TuiTestHarness};
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// // ... spawn app and interact ...
+    /// let harness = TuiTestHarness::new(80, 24)?;
     ///
-    /// // Log current state
-    /// eprintln!("{}", harness.screenshot_string());
+    /// let button1 = Rect::new(10, 20, 15, 3);
+    /// let button2 = Rect::new(30, 20, 15, 3);
+    ///
+    /// // Verify both buttons are horizontally aligned (same Y)
+    /// harness.assert_aligned(button1, button2, Axis::Horizontal)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn screenshot_string(&self) -> String {
-        let cursor = self.state.cursor_position();
-        let contents = self.state.debug_contents();
-        let (width, height) = self.state.size();
-
-        format!(
-            "=== Screen State ===\n\
-             Size: {}x{}\n\
-             Cursor: row={}, col={}\n\
-             {}\n\
-             ===================",
-            width, height, cursor.0, cursor.1, contents
-        )
+    pub fn assert_aligned(
+        &self,
+        rect1: crate::screen::Rect,
+        rect2: crate::screen::Rect,
+        axis: Axis,
+    ) -> Result<()> {
+        match axis {
+            Axis::Horizontal => {
+                if rect1.y != rect2.y {
+                    return Err(TermTestError::Parse(format!(
+                        "Rectangles not horizontally aligned (different Y coordinates)\n\
+                         Rect 1: y={} (x={}, width={}, height={})\n\
+                         Rect 2: y={} (x={}, width={}, height={})",
+                        rect1.y,
+                        rect1.x,
+                        rect1.width,
+                        rect1.height,
+                        rect2.y,
+                        rect2.x,
+                        rect2.width,
+                        rect2.height
+                    )));
+                }
+            }
+            Axis::Vertical => {
+                if rect1.x != rect2.x {
+                    return Err(TermTestError::Parse(format!(
+                        "Rectangles not vertically aligned (different X coordinates)\n\
+                         Rect 1: x={} (y={}, width={}, height={})\n\
+                         Rect 2: x={} (y={}, width={}, height={})",
+                        rect1.x,
+                        rect1.y,
+                        rect1.width,
+                        rect1.height,
+                        rect2.x,
+                        rect2.y,
+                        rect2.width,
+                        rect2.height
+                    )));
+                }
+            }
+        }
+        Ok(())
     }
 
-    /// Enables or disables verbose escape sequence logging.
+    /// Asserts that the cursor stays within a region while an action runs.
     ///
-    /// When verbose mode is enabled, all escape sequences received from the PTY
-    /// are logged to stderr in a human-readable format. This is useful for
-    /// debugging terminal emulation issues or understanding what escape sequences
-    /// your application is generating.
+    /// Unlike checking [`cursor_position()`](Self::cursor_position) afterwards, this
+    /// verifies a temporal invariant: every cursor position produced by output
+    /// processed during `action`, and afterwards until no output has arrived for
+    /// one poll interval (at most the configured timeout), is recorded. A cursor
+    /// that briefly leaves the region and comes back is still caught.
     ///
     /// # Arguments
     ///
-    /// * `verbose` - `true` to enable verbose logging, `false` to disable
+    /// * `region` - The area the cursor must stay within
+    /// * `action` - The action to run (e.g., sending keys to an editor pane)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`] if the cursor left the region at any point.
+    /// Errors from `action` are returned unchanged.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use ratatui_testlib::TuiTestHarness;
+    /// use ratatui_testlib::{Rect, TuiTestHarness};
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
     /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// harness.set_verbose(true);
+    /// // ... spawn an editor with its text pane at rows 1-20 ...
     ///
-    /// // All escape sequences will now be logged to stderr
-    /// harness.send_text("hello\n")?;
+    /// let editor_pane = Rect::new(0, 1, 80, 20);
+    /// harness.assert_cursor_stays_in(editor_pane, |h| h.send_text("hello world"))?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn set_verbose(&mut self, verbose: bool) {
-        self.verbose = verbose;
-    }
-
-    /// Records an input event if recording is active.
-    fn record_input(&mut self, data: &[u8]) {
-        if self.recording {
-            if let Some(start) = self.recording_start {
-                let timestamp = start.elapsed();
-                self.recorded_events.push(TimestampedEvent {
-                    timestamp,
-                    event: RecordedEvent::Input(data.to_vec()),
-                });
-            }
-        }
-    }
+    pub fn assert_cursor_stays_in<F>(
+        &mut self,
+        region: crate::screen::Rect,
+        action: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(&mut Self) -> Result<()>,
+    {
+        self.state.start_cursor_trace();
 
-    /// Records an output event if recording is active.
-    fn record_output(&mut self, data: &[u8]) {
-        if self.recording {
-            if let Some(start) = self.recording_start {
-                let timestamp = start.elapsed();
-                self.recorded_events.push(TimestampedEvent {
-                    timestamp,
-                    event: RecordedEvent::Output(data.to_vec()),
-                });
-            }
-        }
+        let result = action(self).and_then(|()| self.process_until_quiet());
+        let trace = self.state.take_cursor_trace();
+        result?;
 
-        // Verbose logging
-        if self.verbose {
-            eprintln!("[VERBOSE] Received {} bytes: {:?}", data.len(), data);
-            // Try to display as string if it's printable
-            if let Ok(s) = std::str::from_utf8(data) {
-                eprintln!("[VERBOSE] As string: {:?}", s);
-            }
+        if let Some(&(row, col)) = trace.iter().find(|&&(row, col)| !region.contains(col, row)) {
+            return Err(TermTestError::Parse(format!(
+                "Cursor left region {:?}: moved to (row {}, col {})\n  Cursor trace: {:?}\n\nScreen state:\n{}",
+                region,
+                row,
+                col,
+                trace,
+                self.state.debug_contents()
+            )));
         }
-    }
 
-    /// Records a state change event if recording is active.
-    fn record_state_change(&mut self) {
-        if self.recording {
-            if let Some(start) = self.recording_start {
-                let timestamp = start.elapsed();
-                let contents = self.state.debug_contents();
-                let cursor = self.state.cursor_position();
-                self.recorded_events.push(TimestampedEvent {
-                    timestamp,
-                    event: RecordedEvent::StateChange { contents, cursor },
-                });
-            }
-        }
+        Ok(())
     }
 
-    // =========================================================================
-    // Parallel Testing Support
-    // =========================================================================
-
-    /// Runs a test closure with an isolated terminal context.
-    ///
-    /// This method provides a simple way to run tests in isolation without
-    /// manually managing terminal acquisition and release. The closure receives
-    /// a mutable reference to the harness.
-    ///
-    /// # Arguments
+    /// Asserts that the application cleared the screen when it started.
     ///
-    /// * `f` - Closure to run with the harness
+    /// Well-behaved full-screen applications either erase the display
+    /// (`ESC [ 2 J`) or switch to the alternate screen before drawing, so no
+    /// leftover shell output shows through. This waits up to the configured
+    /// timeout for either to happen.
     ///
     /// # Errors
     ///
-    /// Returns an error if the closure returns an error.
+    /// Returns [`TermTestError::Parse`] if neither an erase-display nor an
+    /// alternate screen switch was seen before the timeout or process exit.
     ///
     /// # Example
     ///
@@ -2694,203 +4039,327 @@ impl TuiTestHarness {
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// TuiTestHarness::with_isolation(|harness| {
-    ///     let mut cmd = CommandBuilder::new("echo");
-    ///     cmd.arg("test");
-    ///     harness.spawn(cmd)?;
-    ///     harness.wait_for_text("test")?;
-    ///     Ok(())
-    /// })?;
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.spawn(CommandBuilder::new("htop"))?;
+    ///
+    /// harness.assert_cleared_on_start()?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn with_isolation<F>(f: F) -> Result<()>
-    where
-        F: FnOnce(&mut TuiTestHarness) -> Result<()>,
-    {
-        let mut harness = TuiTestHarness::new(80, 24)?;
-        f(&mut harness)
+    pub fn assert_cleared_on_start(&mut self) -> Result<()> {
+        let result = self.wait_for_with_context(
+            |state| state.erase_display_count() > 0 || state.alt_screen_entries() > 0,
+            "screen clear or alternate screen on start",
+        );
+
+        match result {
+            Ok(()) => Ok(()),
+            Err(TermTestError::Timeout { .. }) | Err(TermTestError::ProcessExited) => {
+                Err(TermTestError::Parse(format!(
+                    "Application did not clear the screen or enter the alternate screen on start\n\nScreen state:\n{}",
+                    self.state.debug_contents()
+                )))
+            }
+            Err(e) => Err(e),
+        }
     }
 
-    /// Runs a test closure with an isolated terminal context and custom dimensions.
+    /// Asserts that an action causes at most `max` full-screen redraws.
     ///
-    /// This is similar to `with_isolation` but allows specifying custom terminal dimensions.
+    /// Counts `ESC [ 2 J` and `ESC [ 3 J` clears (see
+    /// [`ScreenState::full_redraw_count`]) in the output processed during
+    /// `action` and until the application's output settles. Applications
+    /// that clear the whole screen on every keypress instead of updating only
+    /// what changed flicker and waste bandwidth; this catches regressions.
     ///
     /// # Arguments
     ///
-    /// * `width` - Terminal width in columns
-    /// * `height` - Terminal height in rows
-    /// * `f` - Closure to run with the harness
+    /// * `max` - Maximum number of full redraws allowed
+    /// * `action` - The action to run (e.g., navigating a list)
     ///
     /// # Errors
     ///
-    /// Returns an error if the closure returns an error.
+    /// Returns [`TermTestError::Parse`] if more than `max` redraws happened.
+    /// Errors from `action` are returned unchanged.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use portable_pty::CommandBuilder;
-    /// use ratatui_testlib::TuiTestHarness;
+    /// use ratatui_testlib::{KeyCode, TuiTestHarness};
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// TuiTestHarness::with_isolation_sized(100, 30, |harness| {
-    ///     let mut cmd = CommandBuilder::new("echo");
-    ///     cmd.arg("test");
-    ///     harness.spawn(cmd)?;
-    ///     harness.wait_for_text("test")?;
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... spawn app ...
+    ///
+    /// harness.assert_redraws_at_most(0, |h| {
+    ///     for _ in 0..10 {
+    ///         h.send_key(KeyCode::Down)?;
+    ///     }
     ///     Ok(())
     /// })?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn with_isolation_sized<F>(width: u16, height: u16, f: F) -> Result<()>
+    pub fn assert_redraws_at_most<F>(&mut self, max: usize, action: F) -> Result<()>
     where
-        F: FnOnce(&mut TuiTestHarness) -> Result<()>,
+        F: FnOnce(&mut Self) -> Result<()>,
     {
-        let mut harness = TuiTestHarness::new(width, height)?;
-        f(&mut harness)
+        let before = self.state.full_redraw_count();
+
+        action(self)?;
+        self.process_until_quiet()?;
+
+        let redraws = self.state.full_redraw_count() - before;
+        if redraws > max {
+            return Err(TermTestError::Parse(format!(
+                "Expected at most {} full redraws, but the screen was cleared {} times\n\nScreen state:\n{}",
+                max,
+                redraws,
+                self.state.debug_contents()
+            )));
+        }
+
+        Ok(())
     }
 
-    /// Creates a builder for parallel-safe harness configuration.
+    // ========================================================================
+    // Sixel Graphics Validation APIs
+    // ========================================================================
+
+    /// Returns all captured Sixel regions from the screen state.
     ///
-    /// This builder ensures that all configuration is done before the harness
-    /// is created, making it safe to use in parallel test contexts.
+    /// This provides direct access to all Sixel graphics detected in the terminal
+    /// output. Each region includes position and dimension information.
     ///
-    /// # Example
+    /// # Returns
     ///
-    /// ```rust,no_run
-    /// use std::time::Duration;
+    /// A slice containing all [`SixelRegion`](crate::screen::SixelRegion) instances
+    /// currently on screen.
     ///
+    /// # Example
+    ///
+    /// ```rust,no_run
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let harness = TuiTestHarness::parallel_harness_builder()
-    ///     .with_size(100, 30)
-    ///     .with_timeout(Duration::from_secs(10))
-    ///     .build()?;
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... spawn app that renders Sixel graphics ...
+    ///
+    /// let regions = harness.sixel_regions();
+    /// for (i, region) in regions.iter().enumerate() {
+    ///     println!(
+    ///         "Sixel {}: position ({}, {}), size {}x{}",
+    ///         i, region.start_row, region.start_col, region.width, region.height
+    ///     );
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub fn parallel_harness_builder() -> TuiTestHarnessBuilder {
-        TuiTestHarnessBuilder::default()
+    #[cfg(feature = "sixel")]
+    pub fn sixel_regions(&self) -> &[crate::screen::SixelRegion] {
+        self.state.sixel_regions()
     }
 
-    /// Measures the input-to-render latency from the most recent input event.
+    /// Returns the count of Sixel graphics currently on screen.
     ///
-    /// This returns the time from when input was sent to when rendering completed.
+    /// This is a convenience method equivalent to `harness.sixel_regions().len()`.
+    /// Useful for quick assertions about the number of graphics present.
     ///
     /// # Returns
     ///
-    /// `Some(Duration)` if both input and render timestamps are recorded, `None` otherwise.
+    /// The number of Sixel graphics regions detected.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use std::time::Duration;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render graphics ...
+    ///
+    /// assert_eq!(harness.sixel_count(), 1, "Expected exactly one image");
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "sixel")]
+    pub fn sixel_count(&self) -> usize {
+        self.sixel_regions().len()
+    }
+
+    /// Finds a Sixel graphic at a specific position.
+    ///
+    /// Searches for a Sixel region that starts at the exact (row, col) position.
+    /// Returns `None` if no Sixel is found at that position.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row to search (0-based)
+    /// * `col` - Column to search (0-based)
+    ///
+    /// # Returns
+    ///
+    /// A reference to the [`SixelRegion`](crate::screen::SixelRegion) at that position,
+    /// or `None` if no Sixel starts there.
+    ///
+    /// # Example
     ///
+    /// ```rust,no_run
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// harness.send_text("hello")?;
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render Sixel at (5, 10) ...
     ///
-    /// let latency = harness.measure_input_to_render_latency();
-    /// if let Some(latency) = latency {
-    ///     println!("Input→Render latency: {:.2}ms", latency.as_secs_f64() * 1000.0);
+    /// if let Some(region) = harness.sixel_at(5, 10) {
+    ///     println!("Found Sixel: {}x{} pixels", region.width, region.height);
+    /// } else {
+    ///     println!("No Sixel at that position");
     /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub fn measure_input_to_render_latency(&self) -> Option<Duration> {
-        self.latency_profile.input_to_render()
+    #[cfg(feature = "sixel")]
+    pub fn sixel_at(&self, row: u16, col: u16) -> Option<&crate::screen::SixelRegion> {
+        self.state
+            .sixel_regions()
+            .iter()
+            .find(|r| r.start_row == row && r.start_col == col)
     }
 
-    /// Asserts that input-to-render latency is within the specified budget.
+    /// Asserts that all Sixel graphics are within the specified area.
     ///
-    /// Uses the most recent input→render timing for the assertion.
+    /// This validates that every Sixel bounding rectangle is completely contained
+    /// within the given area. If any Sixel extends beyond the area, an error is
+    /// returned with details about which sequences are out of bounds.
     ///
     /// # Arguments
     ///
-    /// * `budget` - Maximum allowed latency
+    /// * `area` - Bounding area as (row, col, width, height) tuple
     ///
     /// # Errors
     ///
-    /// Returns an error if:
-    /// - No timing data is available
-    /// - The latency exceeds the budget
+    /// Returns [`TermTestError::SixelValidation`] if any Sixel is outside the area.
+    /// The error message includes the positions of all out-of-bounds graphics.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use std::time::Duration;
-    ///
     /// use ratatui_testlib::TuiTestHarness;
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
-    /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// harness.send_text("hello")?;
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render graphics ...
     ///
-    /// // Assert input→render completes within 16.67ms (60 FPS)
-    /// harness.assert_input_latency_within(Duration::from_micros(16670))?;
+    /// // Verify all graphics are in the preview panel
+    /// let preview_area = (5, 40, 35, 15); // (row, col, width, height)
+    /// harness.assert_sixel_within_bounds(preview_area)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn assert_input_latency_within(&self, budget: Duration) -> Result<()> {
-        let latency = self.latency_profile.input_to_render().ok_or_else(|| {
-            TermTestError::Timing(
-                "Cannot measure input latency: no input or render events recorded".to_string(),
-            )
-        })?;
-
-        if latency > budget {
-            return Err(TermTestError::Timing(format!(
-                "Input latency exceeded budget: {:.3}ms > {:.3}ms",
-                latency.as_secs_f64() * 1000.0,
-                budget.as_secs_f64() * 1000.0
-            )));
-        }
+    #[cfg(feature = "sixel")]
+    pub fn assert_sixel_within_bounds(&self, area: (u16, u16, u16, u16)) -> Result<()> {
+        use crate::sixel::SixelCapture;
+        let capture = SixelCapture::from_screen_state(&self.state);
+        capture.assert_all_within(area)
+    }
 
-        Ok(())
+    /// Checks if any Sixel graphics overlap with the specified area.
+    ///
+    /// Returns `true` if at least one Sixel bounding rectangle intersects with
+    /// the given area, even partially. This is useful for detecting graphics in
+    /// specific screen regions.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - Area to check as (row, col, width, height) tuple
+    ///
+    /// # Returns
+    ///
+    /// `true` if any Sixel overlaps with the area, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render graphics ...
+    ///
+    /// let preview_area = (5, 40, 35, 15);
+    /// if harness.has_sixel_in_area(preview_area) {
+    ///     println!("Graphics detected in preview area");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "sixel")]
+    pub fn has_sixel_in_area(&self, area: (u16, u16, u16, u16)) -> bool {
+        use crate::sixel::SixelCapture;
+        let capture = SixelCapture::from_screen_state(&self.state);
+        !capture.sequences_in_area(area).is_empty()
     }
 
-    /// Asserts that rendering meets a target FPS budget.
+    /// Waits until a Sixel graphic appears in the specified area.
     ///
-    /// This calculates the frame time budget from the target FPS and asserts
-    /// that input→render latency is within that budget.
+    /// Unlike [`has_sixel_in_area`](Self::has_sixel_in_area), which checks the
+    /// current state once, this keeps processing PTY output until a Sixel overlaps
+    /// the area. Use it for image viewers that render asynchronously after
+    /// navigation.
     ///
     /// # Arguments
     ///
-    /// * `fps_target` - Target frames per second (e.g., 60.0 for 60 FPS)
+    /// * `area` - Area to check as (row, col, width, height) tuple
     ///
     /// # Errors
     ///
-    /// Returns an error if:
-    /// - No timing data is available
-    /// - The latency exceeds the FPS budget
+    /// Returns a `Timeout` error if no Sixel appears within the configured timeout.
+    /// Returns `ProcessExited` if the process exits before a Sixel appears.
     ///
     /// # Example
     ///
     /// ```rust,no_run
-    /// use ratatui_testlib::TuiTestHarness;
+    /// use ratatui_testlib::{KeyCode, TuiTestHarness};
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
     /// let mut harness = TuiTestHarness::new(80, 24)?;
-    /// harness.send_text("hello")?;
+    /// // ... spawn image viewer ...
     ///
-    /// // Assert rendering meets 60 FPS target (16.67ms per frame)
-    /// harness.assert_render_budget(60.0)?;
+    /// harness.send_key(KeyCode::Down)?;
+    /// harness.wait_for_sixel_in_area((5, 40, 35, 15))?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn assert_render_budget(&self, fps_target: f64) -> Result<()> {
-        let budget = fps_to_frame_budget(fps_target);
-        self.assert_input_latency_within(budget)
+    #[cfg(feature = "sixel")]
+    pub fn wait_for_sixel_in_area(&mut self, area: (u16, u16, u16, u16)) -> Result<()> {
+        use crate::sixel::SixelCapture;
+        let description = format!("Sixel in area {:?}", area);
+        self.wait_for_with_context(
+            move |state| {
+                !SixelCapture::from_screen_state(state)
+                    .sequences_in_area(area)
+                    .is_empty()
+            },
+            &description,
+        )
     }
 
-    /// Resets all timing data.
+    /// Verifies that Sixel graphics were cleared after a screen update.
     ///
-    /// This clears the timing recorder and latency profile, useful for
-    /// starting fresh measurements after warm-up operations.
+    /// This method records the current Sixel count, calls [`update`](Self::update)
+    /// to refresh the screen state, and then checks if the count decreased.
+    /// It's useful for verifying that graphics are properly cleared during
+    /// screen transitions (e.g., switching between files in a previewer).
+    ///
+    /// # Returns
+    ///
+    /// `Ok(true)` if the Sixel count decreased, `Ok(false)` if it stayed the same
+    /// or increased.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the screen update fails.
     ///
     /// # Example
     ///
@@ -2899,1543 +4368,3885 @@ impl TuiTestHarness {
     ///
     /// # fn test() -> ratatui_testlib::Result<()> {
     /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render some graphics ...
     ///
-    /// // Warm-up
-    /// harness.send_text("hello")?;
-    ///
-    /// // Reset timing data before real measurements
-    /// harness.reset_timing();
+    /// // Simulate screen transition (e.g., press a key to switch files)
+    /// harness.send_key(ratatui_testlib::KeyCode::Down)?;
     ///
-    /// // Now measure actual performance
-    /// harness.send_text("world")?;
-    /// harness.assert_render_budget(60.0)?;
+    /// // Verify graphics were cleared
+    /// if harness.verify_sixel_cleared()? {
+    ///     println!("Graphics properly cleared on transition");
+    /// }
     /// # Ok(())
     /// # }
     /// ```
-    pub fn reset_timing(&mut self) {
-        self.timing_recorder.reset();
-        self.latency_profile.reset();
+    #[cfg(feature = "sixel")]
+    pub fn verify_sixel_cleared(&mut self) -> Result<bool> {
+        let before = self.sixel_count();
+        self.update_state()?;
+        let after = self.sixel_count();
+        Ok(after < before)
     }
 
-    /// Gets a reference to the latency profile for detailed analysis.
+    /// Asserts that a Sixel graphic appears in a typical preview area.
     ///
-    /// # Returns
+    /// This is a convenience method for the common dgx-pixels use case where
+    /// image previews are displayed in a standard preview panel layout.
     ///
-    /// A reference to the [`LatencyProfile`] for this harness.
-    pub fn latency_profile(&self) -> &LatencyProfile {
-        &self.latency_profile
-    }
-}
-
-/// Implementation of `TimingHooks` trait for `TuiTestHarness`.
-impl TimingHooks for TuiTestHarness {
-    fn record_event(&mut self, event_name: &str) {
-        self.timing_recorder.record_event(event_name);
-    }
-
-    fn measure_latency(&self, start_event: &str, end_event: &str) -> Option<Duration> {
-        self.timing_recorder.measure_latency(start_event, end_event)
-    }
-
-    fn get_timings(&self) -> &TimingRecorder {
-        &self.timing_recorder
-    }
-
-    fn assert_latency_within(
-        &self,
-        start_event: &str,
-        end_event: &str,
-        budget: Duration,
-    ) -> Result<()> {
+    /// The default preview area is:
+    /// - Rows: 5-35 (30 rows, leaving space for header/footer)
+    /// - Cols: 40-75 (35 columns, typical split-pane layout)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::SixelValidation`] if no Sixel graphics are
+    /// found in the preview area.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(80, 24)?;
+    /// // ... render image preview ...
+    ///
+    /// // Quick assertion for standard preview layout
+    /// harness.assert_preview_has_sixel()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "sixel")]
+    pub fn assert_preview_has_sixel(&self) -> Result<()> {
+        // Standard dgx-pixels preview area layout
+        // Assumes 80x24 terminal with:
+        // - Left sidebar: cols 0-39
+        // - Preview area: cols 40-75, rows 5-20
+        let preview_area = (5, 40, 35, 15);
+
+        if !self.has_sixel_in_area(preview_area) {
+            return Err(TermTestError::SixelValidation(format!(
+                "No Sixel graphics found in standard preview area {:?}. \
+                    Current Sixel count: {}. \
+                    Regions: {:?}",
+                preview_area,
+                self.sixel_count(),
+                self.sixel_regions()
+                    .iter()
+                    .map(|r| (r.start_row, r.start_col, r.width, r.height))
+                    .collect::<Vec<_>>()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Asserts that a Sixel graphic appears in a custom preview area.
+    ///
+    /// Similar to [`assert_preview_has_sixel`](Self::assert_preview_has_sixel),
+    /// but allows specifying a custom preview area. This is useful for applications
+    /// with non-standard layouts.
+    ///
+    /// # Arguments
+    ///
+    /// * `preview_area` - Custom preview area as (row, col, width, height)
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::SixelValidation`] if no Sixel graphics are
+    /// found in the specified preview area.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let harness = TuiTestHarness::new(120, 40)?;
+    /// // ... render image preview in large terminal ...
+    ///
+    /// // Custom preview area for larger terminal
+    /// let custom_area = (10, 50, 60, 25);
+    /// harness.assert_preview_has_sixel_in(custom_area)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "sixel")]
+    pub fn assert_preview_has_sixel_in(&self, preview_area: (u16, u16, u16, u16)) -> Result<()> {
+        if !self.has_sixel_in_area(preview_area) {
+            return Err(TermTestError::SixelValidation(format!(
+                "No Sixel graphics found in preview area {:?}. \
+                    Current Sixel count: {}. \
+                    Regions: {:?}",
+                preview_area,
+                self.sixel_count(),
+                self.sixel_regions()
+                    .iter()
+                    .map(|r| (r.start_row, r.start_col, r.width, r.height))
+                    .collect::<Vec<_>>()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Asserts that a Sixel graphic in an area is cleared by an action.
+    ///
+    /// This encodes the show/clear lifecycle of an image preview in one call:
+    /// it first asserts that a Sixel is present in `area`, then runs `action`
+    /// (e.g., pressing a key to navigate away), and keeps processing output
+    /// until the area no longer contains Sixel graphics or the configured
+    /// timeout expires.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - Area to check as (row, col, width, height) tuple
+    /// * `action` - Action expected to clear the graphics
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::SixelValidation`] if no Sixel is present before the
+    /// action, or one is still present when the timeout expires or the process
+    /// exits. Errors from `action` are returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::{KeyCode, TuiTestHarness};
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... open an image preview ...
+    ///
+    /// harness.assert_sixel_cleared_after((5, 40, 35, 15), |h| h.send_key(KeyCode::Esc))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "sixel")]
+    pub fn assert_sixel_cleared_after<F>(
+        &mut self,
+        area: (u16, u16, u16, u16),
+        action: F,
+    ) -> Result<()>
+    where
+        F: FnOnce(&mut Self) -> Result<()>,
+    {
+        self.assert_preview_has_sixel_in(area)?;
+
+        action(self)?;
+
+        // Keep processing output until the area clears; the process may
+        // legitimately exit as part of the action.
+        use crate::sixel::SixelCapture;
+        let description = format!("Sixel in area {:?} to clear", area);
+        match self.wait_for_with_context(
+            move |state| {
+                SixelCapture::from_screen_state(state)
+                    .sequences_in_area(area)
+                    .is_empty()
+            },
+            &description,
+        ) {
+            Ok(()) => Ok(()),
+            Err(TermTestError::Timeout { .. }) | Err(TermTestError::ProcessExited) => {
+                Err(TermTestError::SixelValidation(format!(
+                    "Sixel graphics still present in area {:?} after action. \
+                        Regions: {:?}",
+                    area,
+                    self.sixel_regions()
+                        .iter()
+                        .map(|r| (r.start_row, r.start_col, r.width, r.height))
+                        .collect::<Vec<_>>()
+                )))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // ========================================================================
+    // Golden File Testing (Visual Regression)
+    // ========================================================================
+
+    /// Save the current screen state as a golden file.
+    ///
+    /// Golden files capture the expected terminal output for visual regression testing.
+    /// They are saved in the directory specified by the `GOLDEN_DIR` environment variable,
+    /// or `tests/golden/` by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name for the golden file (used as filename without extension)
+    ///
+    /// # Returns
+    ///
+    /// The path where the golden file was saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written or the directory cannot be created.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// let cmd = CommandBuilder::new("my-app");
+    /// harness.spawn(cmd)?;
+    /// harness.wait_for_text("Welcome")?;
+    ///
+    /// // Save current state as golden
+    /// let path = harness.save_golden("welcome_screen")?;
+    /// println!("Saved golden file to: {}", path.display());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save_golden(&self, name: &str) -> Result<std::path::PathBuf> {
+        crate::golden::save_golden(name, &self.state)
+    }
+
+    /// Compare current screen state against a golden file.
+    ///
+    /// This method loads a previously saved golden file and compares it against
+    /// the current screen state. If they don't match, it generates a detailed
+    /// unified diff showing the differences.
+    ///
+    /// If the `UPDATE_GOLDENS=1` environment variable is set, this will update
+    /// the golden file instead of comparing (useful for updating all goldens at once).
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the golden file to compare against (without extension)
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the states match (or golden was updated in update mode).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with a detailed diff if the states don't match, or if
+    /// the golden file cannot be read.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// let cmd = CommandBuilder::new("my-app");
+    /// harness.spawn(cmd)?;
+    /// harness.wait_for_text("Welcome")?;
+    ///
+    /// // Compare against previously saved golden
+    /// harness.assert_matches_golden("welcome_screen")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Update Mode
+    ///
+    /// To update all golden files at once:
+    ///
+    /// ```bash
+    /// UPDATE_GOLDENS=1 cargo test
+    /// ```
+    pub fn assert_matches_golden(&self, name: &str) -> Result<()> {
+        crate::golden::assert_matches_golden(name, &self.state)
+    }
+
+    /// Update a golden file with the current screen state.
+    ///
+    /// This is equivalent to `save_golden()` but makes the intent clearer when
+    /// you're explicitly updating an existing golden file.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the golden file to update (without extension)
+    ///
+    /// # Returns
+    ///
+    /// The path where the golden file was saved.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// let cmd = CommandBuilder::new("my-app");
+    /// harness.spawn(cmd)?;
+    /// harness.wait_for_text("New Layout")?;
+    ///
+    /// // Update golden file after intentional UI changes
+    /// harness.update_golden("main_screen")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn update_golden(&self, name: &str) -> Result<std::path::PathBuf> {
+        crate::golden::update_golden(name, &self.state)
+    }
+
+    // ============================================================================
+    // Recording and Debug Methods
+    // ============================================================================
+
+    /// Starts recording all I/O events and state changes.
+    ///
+    /// When recording is enabled, the harness will capture:
+    /// - All input sent to the PTY
+    /// - All output received from the PTY
+    /// - Screen state changes after processing escape sequences
+    ///
+    /// Recordings can be saved to disk using [`save_recording`](Self::save_recording)
+    /// for debugging failed tests or understanding application behavior.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.start_recording();
+    ///
+    /// // Test operations...
+    /// harness.send_text("hello\n")?;
+    ///
+    /// // Save recording for debugging
+    /// harness.save_recording("test_recording.json")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn start_recording(&mut self) {
+        self.recording = true;
+        self.recording_start = Some(Instant::now());
+        self.recorded_events.clear();
+    }
+
+    /// Returns all output bytes recorded since [`start_recording`](Self::start_recording).
+    ///
+    /// When recording was started before any output was read and has not been
+    /// stopped, this is the exact stream fed to the screen state, so
+    /// [`ScreenState::sixel_stream_range`] indexes directly into it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.start_recording();
+    /// harness.spawn(CommandBuilder::new("my-app"))?;
+    /// harness.wait_for_text("Ready")?;
+    ///
+    /// let output = harness.recorded_output();
+    /// println!("App wrote {} bytes", output.len());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn recorded_output(&self) -> Vec<u8> {
+        self.recorded_events
+            .iter()
+            .filter_map(|e| match &e.event {
+                RecordedEvent::Output(data) => Some(data.as_slice()),
+                _ => None,
+            })
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// Stops recording I/O events.
+    ///
+    /// Recording can be stopped without saving, or you can call
+    /// [`save_recording`](Self::save_recording) to persist the events.
+    pub fn stop_recording(&mut self) {
+        self.recording = false;
+    }
+
+    /// Saves the current recording to a file in JSON format.
+    ///
+    /// The recording includes all captured events with timestamps, which can be
+    /// useful for debugging test failures or understanding application behavior.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path where the recording should be saved
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - The file cannot be created
+    /// - Serialization fails
+    /// - Writing to the file fails
+    ///
+    /// # Format
+    ///
+    /// The saved file is a JSON array containing timestamped events:
+    ///
+    /// ```json
+    /// [
+    ///   {
+    ///     "timestamp_ms": 0,
+    ///     "event": {
+    ///       "type": "Input",
+    ///       "data": [104, 101, 108, 108, 111]
+    ///     }
+    ///   },
+    ///   {
+    ///     "timestamp_ms": 150,
+    ///     "event": {
+    ///       "type": "Output",
+    ///       "data": [27, 91, 72, 27, 91, 50, 74]
+    ///     }
+    ///   }
+    /// ]
+    /// ```
+    pub fn save_recording<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        use std::io::BufWriter;
+
+        let file = File::create(path.as_ref()).map_err(|e| {
+            TermTestError::Io(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to create recording file: {}", e),
+            ))
+        })?;
+
+        let mut writer = BufWriter::new(file);
+
+        // Write JSON array
+        writeln!(writer, "[").map_err(|e| {
+            TermTestError::Io(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to write recording: {}", e),
+            ))
+        })?;
+
+        for (i, event) in self.recorded_events.iter().enumerate() {
+            let comma = if i < self.recorded_events.len() - 1 {
+                ","
+            } else {
+                ""
+            };
+
+            // Format event as JSON
+            let event_json = match &event.event {
+                RecordedEvent::Input(data) => {
+                    format!(
+                        r#"  {{"timestamp_ms": {}, "event": {{"type": "Input", "data": {:?}}}}}{}"#,
+                        event.timestamp.as_millis(),
+                        data,
+                        comma
+                    )
+                }
+                RecordedEvent::Output(data) => {
+                    format!(
+                        r#"  {{"timestamp_ms": {}, "event": {{"type": "Output", "data": {:?}}}}}{}"#,
+                        event.timestamp.as_millis(),
+                        data,
+                        comma
+                    )
+                }
+                RecordedEvent::StateChange { contents, cursor } => {
+                    let escaped_contents = contents
+                        .replace('\\', "\\\\")
+                        .replace('"', "\\\"")
+                        .replace('\n', "\\n")
+                        .replace('\r', "\\r")
+                        .replace('\t', "\\t");
+                    format!(
+                        r#"  {{"timestamp_ms": {}, "event": {{"type": "StateChange", "contents": "{}", "cursor": [{}, {}]}}}}{}"#,
+                        event.timestamp.as_millis(),
+                        escaped_contents,
+                        cursor.0,
+                        cursor.1,
+                        comma
+                    )
+                }
+            };
+
+            writeln!(writer, "{}", event_json).map_err(|e| {
+                TermTestError::Io(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!("Failed to write event: {}", e),
+                ))
+            })?;
+        }
+
+        writeln!(writer, "]").map_err(|e| {
+            TermTestError::Io(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to write recording: {}", e),
+            ))
+        })?;
+
+        writer.flush().map_err(|e| {
+            TermTestError::Io(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to flush recording: {}", e),
+            ))
+        })?;
+
+        Ok(())
+    }
+
+    /// Checks if recording is currently active.
+    ///
+    /// # Returns
+    ///
+    /// `true` if recording is active, `false` otherwise.
+    pub fn is_recording(&self) -> bool {
+        self.recording
+    }
+
+    /// Saves the current screen state to a file.
+    ///
+    /// This is useful for capturing the screen state when a test fails,
+    /// allowing you to inspect what was actually displayed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path where the screenshot should be saved
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be created or written.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... spawn app and interact ...
+    ///
+    /// // Save screenshot on failure
+    /// if !harness.screen_contents().contains("expected") {
+    ///     harness.save_screenshot("failure_screenshot.txt")?;
+    ///     panic!("Test failed, screenshot saved");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn save_screenshot<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut file = File::create(path.as_ref()).map_err(|e| {
+            TermTestError::Io(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to create screenshot file: {}", e),
+            ))
+        })?;
+
+        let contents = self.screenshot_string();
+        file.write_all(contents.as_bytes()).map_err(|e| {
+            TermTestError::Io(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to write screenshot: {}", e),
+            ))
+        })?;
+
+        Ok(())
+    }
+
+    /// Returns the current screen state as a string.
+    ///
+    /// This includes the screen contents with a header showing dimensions
+    /// and cursor position, formatted for easy reading in logs.
+    ///
+    /// # Returns
+    ///
+    /// A formatted string containing screen state information.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// // ... spawn app and interact ...
+    ///
+    /// // Log current state
+    /// eprintln!("{}", harness.screenshot_string());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn screenshot_string(&self) -> String {
+        let cursor = self.state.cursor_position();
+        let contents = self.state.debug_contents();
+        let (width, height) = self.state.size();
+
+        format!(
+            "=== Screen State ===\n\
+             Size: {}x{}\n\
+             Cursor: row={}, col={}\n\
+             {}\n\
+             ===================",
+            width, height, cursor.0, cursor.1, contents
+        )
+    }
+
+    /// Enables or disables verbose escape sequence logging.
+    ///
+    /// When verbose mode is enabled, all escape sequences received from the PTY
+    /// are logged to stderr in a human-readable format. This is useful for
+    /// debugging terminal emulation issues or understanding what escape sequences
+    /// your application is generating.
+    ///
+    /// # Arguments
+    ///
+    /// * `verbose` - `true` to enable verbose logging, `false` to disable
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.set_verbose(true);
+    ///
+    /// // All escape sequences will now be logged to stderr
+    /// harness.send_text("hello\n")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_verbose(&mut self, verbose: bool) {
+        self.verbose = verbose;
+    }
+
+    /// Records an input event if recording is active.
+    fn record_input(&mut self, data: &[u8]) {
+        if self.recording {
+            if let Some(start) = self.recording_start {
+                let timestamp = start.elapsed();
+                self.recorded_events.push(TimestampedEvent {
+                    timestamp,
+                    event: RecordedEvent::Input(data.to_vec()),
+                });
+            }
+        }
+    }
+
+    /// Records an output event if recording is active.
+    fn record_output(&mut self, data: &[u8]) {
+        if self.recording {
+            if let Some(start) = self.recording_start {
+                let timestamp = start.elapsed();
+                self.recorded_events.push(TimestampedEvent {
+                    timestamp,
+                    event: RecordedEvent::Output(data.to_vec()),
+                });
+            }
+        }
+
+        // Verbose logging
+        if self.verbose {
+            eprintln!("[VERBOSE] Received {} bytes: {:?}", data.len(), data);
+            // Try to display as string if it's printable
+            if let Ok(s) = std::str::from_utf8(data) {
+                eprintln!("[VERBOSE] As string: {:?}", s);
+            }
+        }
+    }
+
+    /// Records a state change event if recording is active.
+    fn record_state_change(&mut self) {
+        if self.recording {
+            if let Some(start) = self.recording_start {
+                let timestamp = start.elapsed();
+                let contents = self.state.debug_contents();
+                let cursor = self.state.cursor_position();
+                self.recorded_events.push(TimestampedEvent {
+                    timestamp,
+                    event: RecordedEvent::StateChange { contents, cursor },
+                });
+            }
+        }
+    }
+
+    // =========================================================================
+    // Parallel Testing Support
+    // =========================================================================
+
+    /// Runs a test closure with an isolated terminal context.
+    ///
+    /// This method provides a simple way to run tests in isolation without
+    /// manually managing terminal acquisition and release. The closure receives
+    /// a mutable reference to the harness.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Closure to run with the harness
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the closure returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// TuiTestHarness::with_isolation(|harness| {
+    ///     let mut cmd = CommandBuilder::new("echo");
+    ///     cmd.arg("test");
+    ///     harness.spawn(cmd)?;
+    ///     harness.wait_for_text("test")?;
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_isolation<F>(f: F) -> Result<()>
+    where
+        F: FnOnce(&mut TuiTestHarness) -> Result<()>,
+    {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        f(&mut harness)
+    }
+
+    /// Runs a test closure with an isolated terminal context and custom dimensions.
+    ///
+    /// This is similar to `with_isolation` but allows specifying custom terminal dimensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Terminal width in columns
+    /// * `height` - Terminal height in rows
+    /// * `f` - Closure to run with the harness
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the closure returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use portable_pty::CommandBuilder;
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// TuiTestHarness::with_isolation_sized(100, 30, |harness| {
+    ///     let mut cmd = CommandBuilder::new("echo");
+    ///     cmd.arg("test");
+    ///     harness.spawn(cmd)?;
+    ///     harness.wait_for_text("test")?;
+    ///     Ok(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_isolation_sized<F>(width: u16, height: u16, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut TuiTestHarness) -> Result<()>,
+    {
+        let mut harness = TuiTestHarness::new(width, height)?;
+        f(&mut harness)
+    }
+
+    /// Creates a builder for parallel-safe harness configuration.
+    ///
+    /// This builder ensures that all configuration is done before the harness
+    /// is created, making it safe to use in parallel test contexts.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let harness = TuiTestHarness::parallel_harness_builder()
+    ///     .with_size(100, 30)
+    ///     .with_timeout(Duration::from_secs(10))
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn parallel_harness_builder() -> TuiTestHarnessBuilder {
+        TuiTestHarnessBuilder::default()
+    }
+
+    /// Measures the input-to-render latency from the most recent input event.
+    ///
+    /// This returns the time from when input was sent to when rendering completed.
+    ///
+    /// # Returns
+    ///
+    /// `Some(Duration)` if both input and render timestamps are recorded, `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.send_text("hello")?;
+    ///
+    /// let latency = harness.measure_input_to_render_latency();
+    /// if let Some(latency) = latency {
+    ///     println!("Input→Render latency: {:.2}ms", latency.as_secs_f64() * 1000.0);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn measure_input_to_render_latency(&self) -> Option<Duration> {
+        self.latency_profile.input_to_render()
+    }
+
+    /// Asserts that input-to-render latency is within the specified budget.
+    ///
+    /// Uses the most recent input→render timing for the assertion.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - Maximum allowed latency
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No timing data is available
+    /// - The latency exceeds the budget
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    ///
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.send_text("hello")?;
+    ///
+    /// // Assert input→render completes within 16.67ms (60 FPS)
+    /// harness.assert_input_latency_within(Duration::from_micros(16670))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn assert_input_latency_within(&self, budget: Duration) -> Result<()> {
+        let latency = self.latency_profile.input_to_render().ok_or_else(|| {
+            TermTestError::Timing(
+                "Cannot measure input latency: no input or render events recorded".to_string(),
+            )
+        })?;
+
+        if latency > budget {
+            return Err(TermTestError::Timing(format!(
+                "Input latency exceeded budget: {:.3}ms > {:.3}ms",
+                latency.as_secs_f64() * 1000.0,
+                budget.as_secs_f64() * 1000.0
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Asserts that rendering meets a target FPS budget.
+    ///
+    /// This calculates the frame time budget from the target FPS and asserts
+    /// that input→render latency is within that budget.
+    ///
+    /// # Arguments
+    ///
+    /// * `fps_target` - Target frames per second (e.g., 60.0 for 60 FPS)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - No timing data is available
+    /// - The latency exceeds the FPS budget
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    /// harness.send_text("hello")?;
+    ///
+    /// // Assert rendering meets 60 FPS target (16.67ms per frame)
+    /// harness.assert_render_budget(60.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn assert_render_budget(&self, fps_target: f64) -> Result<()> {
+        let budget = fps_to_frame_budget(fps_target);
+        self.assert_input_latency_within(budget)
+    }
+
+    /// Resets all timing data.
+    ///
+    /// This clears the timing recorder and latency profile, useful for
+    /// starting fresh measurements after warm-up operations.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// # fn test() -> ratatui_testlib::Result<()> {
+    /// let mut harness = TuiTestHarness::new(80, 24)?;
+    ///
+    /// // Warm-up
+    /// harness.send_text("hello")?;
+    ///
+    /// // Reset timing data before real measurements
+    /// harness.reset_timing();
+    ///
+    /// // Now measure actual performance
+    /// harness.send_text("world")?;
+    /// harness.assert_render_budget(60.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reset_timing(&mut self) {
+        self.timing_recorder.reset();
+        self.latency_profile.reset();
+    }
+
+    /// Gets a reference to the latency profile for detailed analysis.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the [`LatencyProfile`] for this harness.
+    pub fn latency_profile(&self) -> &LatencyProfile {
+        &self.latency_profile
+    }
+}
+
+/// Implementation of `TimingHooks` trait for `TuiTestHarness`.
+impl TimingHooks for TuiTestHarness {
+    fn record_event(&mut self, event_name: &str) {
+        self.timing_recorder.record_event(event_name);
+    }
+
+    fn measure_latency(&self, start_event: &str, end_event: &str) -> Option<Duration> {
+        self.timing_recorder.measure_latency(start_event, end_event)
+    }
+
+    fn get_timings(&self) -> &TimingRecorder {
+        &self.timing_recorder
+    }
+
+    fn assert_latency_within(
+        &self,
+        start_event: &str,
+        end_event: &str,
+        budget: Duration,
+    ) -> Result<()> {
         self.timing_recorder
             .assert_latency_within(start_event, end_event, budget)
     }
-}
+}
+
+impl Drop for TuiTestHarness {
+    fn drop(&mut self) {
+        if let Some(path) = self.stderr_path.take() {
+            let _ = std::fs::remove_file(path);
+        }
+        if let Some(path) = self.temp_home_path.take() {
+            let _ = std::fs::remove_dir_all(path);
+        }
+    }
+}
+
+/// Complete configuration for a [`TuiTestHarness`], as a plain struct.
+///
+/// An alternative to [`TuiTestHarnessBuilder`] for shared test fixtures: build
+/// the configuration once and pass it to [`TuiTestHarness::from_config`]. The
+/// defaults match those of the builder.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use ratatui_testlib::HarnessConfig;
+///
+/// let config = HarnessConfig {
+///     size: (120, 40),
+///     poll_interval: Duration::from_millis(10),
+///     ..HarnessConfig::default()
+/// };
+/// assert_eq!(config.timeout, HarnessConfig::default().timeout);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HarnessConfig {
+    /// Terminal size as (width, height).
+    pub size: (u16, u16),
+    /// Timeout for wait operations.
+    pub timeout: Duration,
+    /// Polling interval for wait operations.
+    pub poll_interval: Duration,
+    /// How wait operations pause between checks.
+    pub poll_strategy: PollStrategy,
+    /// Buffer size for reading PTY output.
+    pub buffer_size: usize,
+    /// Terminal emulator profile.
+    pub terminal_profile: TerminalProfile,
+    /// Optional limit on total bytes read from the PTY.
+    pub max_output_bytes: Option<usize>,
+    /// Environment variables set on spawned processes.
+    pub env: Vec<(String, String)>,
+    /// Environment variables removed from spawned processes.
+    pub unset_env: Vec<String>,
+    /// Capture stderr separately instead of through the PTY.
+    pub separate_stderr: bool,
+    /// Run spawned processes with `HOME` set to a temporary directory.
+    pub temp_home: bool,
+    /// Feed sent text into the screen state as if the terminal echoed it.
+    pub local_echo: bool,
+    /// Answer terminal queries such as cursor-position requests.
+    pub auto_responses: bool,
+    /// Replies to device attribute and DECRQSS queries.
+    pub terminal_identity: Option<TerminalIdentity>,
+    /// Fail waits once output has been at EOF this long while the process runs.
+    pub eof_timeout: Option<Duration>,
+    /// Kill the process and fail every operation once this much time has passed.
+    pub global_deadline: Option<Duration>,
+}
+
+impl Default for HarnessConfig {
+    fn default() -> Self {
+        Self {
+            size: (80, 24),
+            timeout: DEFAULT_TIMEOUT,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            poll_strategy: PollStrategy::default(),
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            terminal_profile: TerminalProfile::default(),
+            max_output_bytes: None,
+            env: Vec::new(),
+            unset_env: Vec::new(),
+            separate_stderr: false,
+            temp_home: false,
+            local_echo: false,
+            auto_responses: false,
+            terminal_identity: None,
+            eof_timeout: None,
+            global_deadline: None,
+        }
+    }
+}
+
+/// Builder for configuring a `TuiTestHarness`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use std::time::Duration;
+///
+/// use ratatui_testlib::TuiTestHarness;
+///
+/// let mut harness = TuiTestHarness::builder()
+///     .with_size(80, 24)
+///     .with_timeout(Duration::from_secs(10))
+///     .with_poll_interval(Duration::from_millis(50))
+///     .with_buffer_size(8192)
+///     .build()?;
+/// # Ok::<(), ratatui_testlib::TermTestError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TuiTestHarnessBuilder {
+    width: u16,
+    height: u16,
+    timeout: Duration,
+    poll_interval: Duration,
+    buffer_size: usize,
+    terminal_profile: TerminalProfile,
+    max_output_bytes: Option<usize>,
+    env: Vec<(String, String)>,
+    unset_env: Vec<String>,
+    poll_strategy: PollStrategy,
+    separate_stderr: bool,
+    temp_home: bool,
+    local_echo: bool,
+    auto_responses: bool,
+    terminal_identity: Option<TerminalIdentity>,
+    eof_timeout: Option<Duration>,
+    global_deadline: Option<Duration>,
+}
+
+impl Default for TuiTestHarnessBuilder {
+    fn default() -> Self {
+        Self {
+            width: 80,
+            height: 24,
+            timeout: DEFAULT_TIMEOUT,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            terminal_profile: TerminalProfile::default(),
+            max_output_bytes: None,
+            env: Vec::new(),
+            unset_env: Vec::new(),
+            poll_strategy: PollStrategy::default(),
+            separate_stderr: false,
+            temp_home: false,
+            local_echo: false,
+            auto_responses: false,
+            terminal_identity: None,
+            eof_timeout: None,
+            global_deadline: None,
+        }
+    }
+}
+
+impl TuiTestHarnessBuilder {
+    /// Sets the terminal size.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Terminal width in columns
+    /// * `height` - Terminal height in rows
+    pub fn with_size(mut self, width: u16, height: u16) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets the timeout for wait operations.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Timeout duration
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Sets the polling interval for wait operations.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - Polling interval
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Sets how wait operations pause between screen checks.
+    ///
+    /// # Arguments
+    ///
+    /// * `strategy` - The poll strategy to use
+    pub fn with_poll_strategy(mut self, strategy: PollStrategy) -> Self {
+        self.poll_strategy = strategy;
+        self
+    }
+
+    /// Sets the buffer size for reading PTY output.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Buffer size in bytes
+    pub fn with_buffer_size(mut self, size: usize) -> Self {
+        self.buffer_size = size;
+        self
+    }
+
+    /// Sets the terminal profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The terminal profile to use
+    pub fn with_terminal_profile(mut self, profile: TerminalProfile) -> Self {
+        self.terminal_profile = profile;
+        self
+    }
+
+    /// Limits the total number of bytes read from the PTY.
+    ///
+    /// See [`TuiTestHarness::with_max_output_bytes`].
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - Maximum number of output bytes to accept
+    pub fn with_max_output_bytes(mut self, limit: usize) -> Self {
+        self.max_output_bytes = Some(limit);
+        self
+    }
+
+    /// Sets an environment variable on every process spawned by the harness.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - Variable name
+    /// * `value` - Variable value
+    pub fn with_env(mut self, key: &str, value: &str) -> Self {
+        self.env.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    /// Removes environment variables from every process spawned by the harness.
+    ///
+    /// Spawned commands normally inherit the test's environment. Use this to make
+    /// applications that probe variables like `DISPLAY` or `WAYLAND_DISPLAY` take
+    /// their headless code path deterministically. Variables are removed even if
+    /// they were set explicitly on the [`CommandBuilder`].
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - Names of the variables to remove
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ratatui_testlib::TuiTestHarness;
+    ///
+    /// let mut harness = TuiTestHarness::builder()
+    ///     .with_unset_env(&["DISPLAY", "WAYLAND_DISPLAY"])
+    ///     .build()?;
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn with_unset_env(mut self, names: &[&str]) -> Self {
+        self.unset_env
+            .extend(names.iter().map(|name| name.to_string()));
+        self
+    }
+
+    /// Captures the stderr of spawned processes separately from the PTY.
+    ///
+    /// See [`TuiTestHarness::with_separate_stderr`].
+    pub fn with_separate_stderr(mut self) -> Self {
+        self.separate_stderr = true;
+        self
+    }
+
+    /// Runs spawned processes with an isolated temporary home directory.
+    ///
+    /// See [`TuiTestHarness::with_temp_home`].
+    pub fn with_temp_home(mut self) -> Self {
+        self.temp_home = true;
+        self
+    }
+
+    /// Enables or disables local echo of sent text.
+    ///
+    /// See [`TuiTestHarness::with_local_echo`].
+    pub fn with_local_echo(mut self, enabled: bool) -> Self {
+        self.local_echo = enabled;
+        self
+    }
+
+    /// Enables or disables automatic answers to terminal queries.
+    ///
+    /// See [`TuiTestHarness::with_auto_responses`].
+    pub fn with_auto_responses(mut self, enabled: bool) -> Self {
+        self.auto_responses = enabled;
+        self
+    }
+
+    /// Sets what the terminal reports itself to be.
+    ///
+    /// See [`TuiTestHarness::with_terminal_identity`].
+    pub fn with_terminal_identity(mut self, identity: TerminalIdentity) -> Self {
+        self.terminal_identity = Some(identity);
+        self
+    }
+
+    /// Fails waits early when the process closes its output but keeps running.
+    ///
+    /// See [`TuiTestHarness::with_eof_timeout`].
+    pub fn with_eof_timeout(mut self, timeout: Duration) -> Self {
+        self.eof_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets an overall deadline for every operation on the harness.
+    ///
+    /// See [`TuiTestHarness::with_global_deadline`]. The deadline starts when
+    /// the harness is built.
+    pub fn with_global_deadline(mut self, deadline: Duration) -> Self {
+        self.global_deadline = Some(deadline);
+        self
+    }
+
+    /// Builds the test harness with the configured settings.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if terminal creation fails.
+    pub fn build(self) -> Result<TuiTestHarness> {
+        let terminal = TestTerminal::new(self.width, self.height)?;
+        let state = ScreenState::new(self.width, self.height);
+
+        Ok(TuiTestHarness {
+            terminal,
+            event_delay: Duration::ZERO,
+            state,
+            timeout: self.timeout,
+            poll_interval: self.poll_interval,
+            buffer_size: self.buffer_size,
+            recording: false,
+            recorded_events: Vec::new(),
+            recording_start: None,
+            verbose: false,
+            terminal_profile: self.terminal_profile,
+            timing_recorder: TimingRecorder::new(),
+            latency_profile: LatencyProfile::new(),
+            max_output_bytes: self.max_output_bytes,
+            output_bytes_read: 0,
+            env: self.env,
+            unset_env: self.unset_env,
+            poll_strategy: self.poll_strategy,
+            last_command: None,
+            separate_stderr: self.separate_stderr,
+            stderr_path: None,
+            temp_home: self.temp_home,
+            temp_home_path: None,
+            local_echo: self.local_echo,
+            auto_responses: self.auto_responses,
+            terminal_identity: self.terminal_identity,
+            hints: Vec::new(),
+            eof_timeout: self.eof_timeout,
+            eof_since: None,
+            last_wait_polls: 0,
+            global_deadline: self.global_deadline,
+            deadline_at: self
+                .global_deadline
+                .map(|deadline| Instant::now() + deadline),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_harness() {
+        let harness = TuiTestHarness::new(80, 24);
+        assert!(harness.is_ok());
+        let harness = harness.unwrap();
+        assert_eq!(harness.timeout, DEFAULT_TIMEOUT);
+        assert_eq!(harness.poll_interval, DEFAULT_POLL_INTERVAL);
+        assert_eq!(harness.buffer_size, DEFAULT_BUFFER_SIZE);
+    }
+
+    #[test]
+    fn test_with_timeout() {
+        let harness = TuiTestHarness::new(80, 24)
+            .unwrap()
+            .with_timeout(Duration::from_secs(10));
+        assert_eq!(harness.timeout, Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_with_poll_interval() {
+        let harness = TuiTestHarness::new(80, 24)
+            .unwrap()
+            .with_poll_interval(Duration::from_millis(50));
+        assert_eq!(harness.poll_interval, Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_builder_default() {
+        let harness = TuiTestHarness::builder().build();
+        assert!(harness.is_ok());
+        let harness = harness.unwrap();
+        assert_eq!(harness.timeout, DEFAULT_TIMEOUT);
+        assert_eq!(harness.poll_interval, DEFAULT_POLL_INTERVAL);
+        assert_eq!(harness.buffer_size, DEFAULT_BUFFER_SIZE);
+    }
+
+    #[test]
+    fn test_builder_with_size() {
+        let harness = TuiTestHarness::builder()
+            .with_size(120, 40)
+            .build()
+            .unwrap();
+        let (width, height) = harness.state.size();
+        assert_eq!(width, 120);
+        assert_eq!(height, 40);
+    }
+
+    #[test]
+    fn test_builder_with_timeout() {
+        let timeout = Duration::from_secs(15);
+        let harness = TuiTestHarness::builder()
+            .with_timeout(timeout)
+            .build()
+            .unwrap();
+        assert_eq!(harness.timeout, timeout);
+    }
+
+    #[test]
+    fn test_builder_with_poll_interval() {
+        let interval = Duration::from_millis(25);
+        let harness = TuiTestHarness::builder()
+            .with_poll_interval(interval)
+            .build()
+            .unwrap();
+        assert_eq!(harness.poll_interval, interval);
+    }
+
+    #[test]
+    fn test_builder_with_buffer_size() {
+        let buffer_size = 8192;
+        let harness = TuiTestHarness::builder()
+            .with_buffer_size(buffer_size)
+            .build()
+            .unwrap();
+        assert_eq!(harness.buffer_size, buffer_size);
+    }
+
+    #[test]
+    fn test_builder_chaining() {
+        let harness = TuiTestHarness::builder()
+            .with_size(100, 30)
+            .with_timeout(Duration::from_secs(20))
+            .with_poll_interval(Duration::from_millis(75))
+            .with_buffer_size(16384)
+            .build()
+            .unwrap();
+
+        assert_eq!(harness.state.size(), (100, 30));
+        assert_eq!(harness.timeout, Duration::from_secs(20));
+        assert_eq!(harness.poll_interval, Duration::from_millis(75));
+        assert_eq!(harness.buffer_size, 16384);
+    }
+
+    #[test]
+    fn test_cursor_position() {
+        let harness = TuiTestHarness::new(80, 24).unwrap();
+        let (row, col) = harness.cursor_position();
+        // Initial cursor position should be at (0, 0)
+        assert_eq!(row, 0);
+        assert_eq!(col, 0);
+    }
+
+    #[test]
+    fn test_get_cursor_position_alias() {
+        let harness = TuiTestHarness::new(80, 24).unwrap();
+        let pos1 = harness.cursor_position();
+        let pos2 = harness.get_cursor_position();
+        assert_eq!(pos1, pos2);
+    }
+
+    #[test]
+    fn test_wait_for_text_helper_exists() {
+        // Test that all wait_for methods exist and compile (signature test)
+        let harness = TuiTestHarness::new(80, 24).unwrap();
+
+        // Test method signatures exist (don't call them as they require a running process)
+        let _: fn(&mut TuiTestHarness, &str) -> Result<()> = TuiTestHarness::wait_for_text;
+
+        // Verify state access methods exist
+        assert_eq!(harness.cursor_position(), (0, 0));
+        assert_eq!(harness.get_cursor_position(), (0, 0));
+    }
+
+    #[test]
+    fn test_state_manipulation() {
+        // Test that we can manipulate state directly
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+
+        // Feed text directly to state
+        harness.state_mut().feed(b"Test Data");
+
+        // Verify we can read it back
+        let contents = harness.screen_contents();
+        assert!(contents.contains("Test"));
+    }
+
+    #[test]
+    fn test_cursor_position_tracking() {
+        // Test cursor position tracking
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+
+        // Initial position
+        assert_eq!(harness.cursor_position(), (0, 0));
+
+        // Feed escape sequence to move cursor
+        harness.state_mut().feed(b"\x1b[2;5H"); // Move to row 2, col 5
+
+        // Check new position (note: escape sequences use 1-based indexing, we return 0-based)
+        let (row, col) = harness.cursor_position();
+        assert!(row >= 0); // Just verify we get valid coordinates
+        assert!(col >= 0);
+    }
+
+    #[test]
+    fn test_screen_state_access() {
+        let harness = TuiTestHarness::new(80, 24).unwrap();
+        let state = harness.state();
+        assert_eq!(state.size(), (80, 24));
+
+        let contents = harness.screen_contents();
+        assert!(contents.len() > 0 || contents.is_empty()); // Just verify it returns something
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+        let result = harness.resize(100, 30);
+        assert!(result.is_ok());
+        assert_eq!(harness.state.size(), (100, 30));
+    }
+
+    #[test]
+    fn test_resize_keeps_screen_contents() -> Result<()> {
+        let mut harness = TuiTestHarness::new(40, 10)?;
+        harness.state_mut().feed(b"Already rendered");
+
+        harness.resize(80, 24)?;
+        assert_eq!(harness.state().size(), (80, 24));
+        assert!(harness.screen_contents().contains("Already rendered"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_running_no_process() {
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+        assert!(!harness.is_running());
+    }
+
+    #[test]
+    fn test_spawn_and_check_running() {
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+        let mut cmd = CommandBuilder::new("sleep");
+        cmd.arg("0.1");
+
+        let spawn_result = harness.spawn(cmd);
+        if spawn_result.is_ok() {
+            // Should be running initially
+            assert!(harness.is_running());
+
+            // Wait for it to exit
+            std::thread::sleep(Duration::from_millis(200));
+
+            // Should have exited
+            assert!(!harness.is_running());
+        }
+    }
+
+    #[test]
+    fn test_wait_for_text_success() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+
+        let mut cmd = CommandBuilder::new("echo");
+        cmd.arg("hello world");
+        harness.spawn(cmd)?;
+
+        // wait_for_text may return ProcessExited since echo exits immediately
+        // but that's ok if the text is present
+        match harness.wait_for_text("hello") {
+            Ok(()) => {
+                assert!(harness.screen_contents().contains("hello"));
+            }
+            Err(TermTestError::ProcessExited) => {
+                // Process exited, but check if we still got the output
+                assert!(
+                    harness.screen_contents().contains("hello"),
+                    "Expected 'hello' in output even though process exited"
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_text_timeout() {
+        let mut harness = TuiTestHarness::new(80, 24)
+            .unwrap()
+            .with_timeout(Duration::from_millis(300));
+
+        let mut cmd = CommandBuilder::new("sleep");
+        cmd.arg("1");
+        harness.spawn(cmd).unwrap();
+
+        // Should timeout waiting for text that never appears
+        let result = harness.wait_for_text("never_appears");
+        assert!(result.is_err());
+
+        match result {
+            Err(TermTestError::Timeout { timeout_ms }) => {
+                assert_eq!(timeout_ms, 300);
+            }
+            Err(TermTestError::ProcessExited) => {
+                // Also acceptable - process may exit before timeout
+            }
+            _ => panic!("Expected Timeout or ProcessExited error"),
+        }
+    }
+
+    #[test]
+    fn test_wait_for_text_with_custom_timeout() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        let mut cmd = CommandBuilder::new("echo");
+        cmd.arg("quick test");
+        harness.spawn(cmd)?;
+
+        // Use custom timeout (shorter than default)
+        // May return ProcessExited since echo exits immediately
+        match harness.wait_for_text_timeout("quick", Duration::from_millis(500)) {
+            Ok(()) => {
+                assert!(harness.screen_contents().contains("quick"));
+            }
+            Err(TermTestError::ProcessExited) => {
+                // Process exited, but check if we still got the output
+                assert!(
+                    harness.screen_contents().contains("quick"),
+                    "Expected 'quick' in output even though process exited"
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_cursor_success() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Feed escape sequence to move cursor
+        harness.state_mut().feed(b"\x1b[10;20H"); // Move to row 10, col 20
+
+        // Wait for cursor to be at the position (1-based in CSI, 0-based in our API)
+        // Since no process is running, this will return ProcessExited immediately
+        // but that's fine - the cursor position should already be set
+
+        match harness.wait_for_cursor((9, 19)) {
+            Ok(()) => {
+                let pos = harness.cursor_position();
+                assert_eq!(pos, (9, 19));
+            }
+            Err(TermTestError::ProcessExited) => {
+                // No process running, but cursor should still be in the right position
+                let pos = harness.cursor_position();
+                assert_eq!(
+                    pos,
+                    (9, 19),
+                    "Cursor should be at (9, 19) even though no process is running"
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_cursor_timeout() {
+        let mut harness = TuiTestHarness::new(80, 24)
+            .unwrap()
+            .with_timeout(Duration::from_millis(300));
+
+        // Cursor is at (0, 0) initially
+        let result = harness.wait_for_cursor((50, 50));
+        assert!(result.is_err());
+
+        match result {
+            Err(TermTestError::Timeout { .. }) => {
+                // Expected timeout
+            }
+            Err(TermTestError::ProcessExited) => {
+                // Also acceptable - no process is running
+            }
+            _ => panic!("Expected Timeout or ProcessExited error"),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "snapshot-insta")]
+    fn test_assert_snapshot_text_only() -> Result<()> {
+        let mut harness = TuiTestHarness::new(40, 5)?;
+        harness
+            .state_mut()
+            .feed(b"\x1b[1;31mError:\x1b[0m disk \x1b[42mfull\x1b[0m\r\n\x1b[34mretry?\x1b[0m");
+
+        let text = harness.state().contents_trimmed();
+        assert!(!text.contains('\x1b'));
+        assert_eq!(text, "Error: disk full\nretry?");
+
+        harness.assert_snapshot_text_only("text_only_colored");
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_cursor_near() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_millis(300));
+
+        // 1-based CSI coordinates mistaken for the 0-based target
+        harness.state_mut().feed(b"\x1b[10;20H");
+        assert_eq!(harness.cursor_position(), (9, 19));
+
+        harness.wait_for_cursor_near((10, 20), 1)?;
+        harness.wait_for_cursor_near((9, 19), 0)?;
+        assert!(harness.wait_for_cursor_near((11, 19), 1).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_cursor_with_custom_timeout() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Feed escape sequence to move cursor
+        harness.state_mut().feed(b"\x1b[5;10H");
+
+        // Use custom timeout - may return ProcessExited since no process is running
+        match harness.wait_for_cursor_timeout((4, 9), Duration::from_millis(500)) {
+            Ok(()) => {
+                let pos = harness.cursor_position();
+                assert_eq!(pos, (4, 9));
+            }
+            Err(TermTestError::ProcessExited) => {
+                // No process running, but cursor should still be in the right position
+                let pos = harness.cursor_position();
+                assert_eq!(
+                    pos,
+                    (4, 9),
+                    "Cursor should be at (4, 9) even though no process is running"
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_custom_predicate() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+
+        let mut cmd = CommandBuilder::new("echo");
+        cmd.arg("test123");
+        harness.spawn(cmd)?;
+
+        // Wait for custom condition: screen contains a digit
+        // May return ProcessExited since echo exits immediately
+        match harness.wait_for(|state| state.contents().chars().any(|c| c.is_numeric())) {
+            Ok(()) => {
+                assert!(harness.screen_contents().contains('1'));
+            }
+            Err(TermTestError::ProcessExited) => {
+                // Process exited, but check if we still got the output
+                assert!(
+                    harness.screen_contents().contains('1'),
+                    "Expected digit in output even though process exited"
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_multiline_output() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo 'line1'; echo 'line2'; echo 'line3'");
+        harness.spawn(cmd)?;
+
+        // Wait for all lines to appear - may return ProcessExited
+        match harness.wait_for(|state| {
+            let contents = state.contents();
+            contents.contains("line1") && contents.contains("line2") && contents.contains("line3")
+        }) {
+            Ok(()) => {
+                let contents = harness.screen_contents();
+                assert!(contents.contains("line1"));
+                assert!(contents.contains("line2"));
+                assert!(contents.contains("line3"));
+            }
+            Err(TermTestError::ProcessExited) => {
+                // Process exited, check if we still got all output
+                let contents = harness.screen_contents();
+                assert!(contents.contains("line1"), "Expected line1 in output");
+                assert!(contents.contains("line2"), "Expected line2 in output");
+                assert!(contents.contains("line3"), "Expected line3 in output");
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_complex_predicate() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+
+        let mut cmd = CommandBuilder::new("echo");
+        cmd.arg("Ready: 100%");
+        harness.spawn(cmd)?;
+
+        // Complex predicate: check for pattern - may return ProcessExited
+        match harness.wait_for(|state| {
+            let contents = state.contents();
+            contents.contains("Ready") && contents.contains("%")
+        }) {
+            Ok(()) => {
+                assert!(harness.screen_contents().contains("Ready: 100%"));
+            }
+            Err(TermTestError::ProcessExited) => {
+                // Process exited, but check if we still got the output
+                assert!(
+                    harness.screen_contents().contains("Ready: 100%"),
+                    "Expected 'Ready: 100%' in output even though process exited"
+                );
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_state_multiple_times() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        let mut cmd = CommandBuilder::new("echo");
+        cmd.arg("data");
+        harness.spawn(cmd)?;
+
+        // Multiple updates - first may succeed, subsequent ones may return ProcessExited
+        let _ = harness.update_state(); // First update may get data
+
+        // Give echo time to finish
+        std::thread::sleep(Duration::from_millis(100));
+
+        // Subsequent updates will likely return ProcessExited
+        match harness.update_state() {
+            Ok(()) | Err(TermTestError::ProcessExited) => {
+                // Either is fine
+            }
+            Err(e) => return Err(e),
+        }
+
+        // Check that we got the data at some point
+        assert!(harness.screen_contents().contains("data"), "Expected 'data' in output");
+        Ok(())
+    }
+
+    // ========================================================================
+    // Sixel Validation API Tests
+    // ========================================================================
+
+    #[cfg(feature = "sixel")]
+    #[test]
+    fn test_sixel_count() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Initially no Sixel graphics
+        assert_eq!(harness.sixel_count(), 0);
+
+        // Feed a Sixel sequence directly to the screen state
+        harness.state_mut().feed(b"\x1b[5;10H"); // Move cursor
+        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\"); // Sixel sequence
+
+        // Should now have one Sixel
+        assert_eq!(harness.sixel_count(), 1);
+
+        // Feed another Sixel
+        harness.state_mut().feed(b"\x1b[10;20H");
+        harness.state_mut().feed(b"\x1bPq\"1;1;80;60#0~\x1b\\");
+
+        // Should now have two Sixels
+        assert_eq!(harness.sixel_count(), 2);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "sixel")]
+    #[test]
+    fn test_sixel_regions() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Feed Sixel with known dimensions
+        harness.state_mut().feed(b"\x1b[5;10H"); // Position (4, 9) in 0-based
+        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
+
+        let regions = harness.sixel_regions();
+        assert_eq!(regions.len(), 1);
+
+        let region = &regions[0];
+        assert_eq!(region.start_row, 4); // 5-1 (CSI is 1-based, we use 0-based)
+        assert_eq!(region.start_col, 9); // 10-1
+        assert_eq!(region.width, 100);
+        assert_eq!(region.height, 50);
+
+        Ok(())
+    }
+
+    #[cfg(feature = "sixel")]
+    #[test]
+    fn test_sixel_at_position() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Feed Sixel at position (5, 10) [1-based in CSI]
+        harness.state_mut().feed(b"\x1b[5;10H");
+        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
+
+        // Should find Sixel at (4, 9) [0-based]
+        let region = harness.sixel_at(4, 9);
+        assert!(region.is_some());
+
+        let region = region.unwrap();
+        assert_eq!(region.width, 100);
+        assert_eq!(region.height, 50);
+
+        // Should not find Sixel at other positions
+        assert!(harness.sixel_at(0, 0).is_none());
+        assert!(harness.sixel_at(10, 10).is_none());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "sixel")]
+    #[test]
+    fn test_assert_sixel_within_bounds_success() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Place Sixel well within screen bounds
+        harness.state_mut().feed(b"\x1b[5;10H");
+        harness.state_mut().feed(b"\x1bPq\"1;1;10;10#0~\x1b\\");
+
+        // Large area that encompasses the Sixel
+        let area = (0, 0, 80, 24);
+        assert!(harness.assert_sixel_within_bounds(area).is_ok());
+
+        // Smaller area that still contains the Sixel
+        let area = (3, 8, 20, 15);
+        assert!(harness.assert_sixel_within_bounds(area).is_ok());
+
+        Ok(())
+    }
+
+    #[cfg(feature = "sixel")]
+    #[test]
+    fn test_assert_sixel_within_bounds_failure() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Place Sixel at position (4, 9) with size 10x10
+        harness.state_mut().feed(b"\x1b[5;10H");
+        harness.state_mut().feed(b"\x1bPq\"1;1;10;10#0~\x1b\\");
+
+        // Area that doesn't contain the Sixel
+        let area = (0, 0, 5, 5);
+        let result = harness.assert_sixel_within_bounds(area);
+        assert!(result.is_err());
+
+        // Check that error is SixelValidation
+        if let Err(crate::error::TermTestError::SixelValidation(msg)) = result {
+            assert!(msg.contains("outside area"));
+        } else {
+            panic!("Expected SixelValidation error");
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "sixel")]
+    #[test]
+    fn test_has_sixel_in_area() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // No Sixel initially
+        let area = (0, 0, 80, 24);
+        assert!(!harness.has_sixel_in_area(area));
+
+        // Add Sixel at (4, 9)
+        harness.state_mut().feed(b"\x1b[5;10H");
+        harness.state_mut().feed(b"\x1bPq\"1;1;10;10#0~\x1b\\");
+
+        // Should detect Sixel in large area
+        assert!(harness.has_sixel_in_area((0, 0, 80, 24)));
+
+        // Should detect in area that contains it
+        assert!(harness.has_sixel_in_area((0, 0, 20, 20)));
+
+        // Should not detect in area that doesn't contain it
+        assert!(!harness.has_sixel_in_area((20, 20, 10, 10)));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "sixel")]
+    #[test]
+    fn test_verify_sixel_cleared() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Add Sixel
+        harness.state_mut().feed(b"\x1b[5;10H");
+        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
+        assert_eq!(harness.sixel_count(), 1);
+
+        // Recreate state (simulating screen clear)
+        let new_state = crate::screen::ScreenState::new(80, 24);
+        *harness.state_mut() = new_state;
+
+        // Manual update (simulating what verify_sixel_cleared does)
+        let before = harness.sixel_count();
+        assert_eq!(before, 0); // Already cleared due to new state
+
+        Ok(())
+    }
 
-/// Builder for configuring a `TuiTestHarness`.
-///
-/// # Example
-///
-/// ```rust,no_run
-/// use std::time::Duration;
-///
-/// use ratatui_testlib::TuiTestHarness;
-///
-/// let mut harness = TuiTestHarness::builder()
-///     .with_size(80, 24)
-///     .with_timeout(Duration::from_secs(10))
-///     .with_poll_interval(Duration::from_millis(50))
-///     .with_buffer_size(8192)
-///     .build()?;
-/// # Ok::<(), ratatui_testlib::TermTestError>(())
-/// ```
-#[derive(Debug, Clone)]
-pub struct TuiTestHarnessBuilder {
-    width: u16,
-    height: u16,
-    timeout: Duration,
-    poll_interval: Duration,
-    buffer_size: usize,
-    terminal_profile: TerminalProfile,
-}
+    #[cfg(feature = "sixel")]
+    #[test]
+    fn test_sixel_stream_range_in_recording() -> Result<()> {
+        use crate::sixel::SixelCapture;
 
-impl Default for TuiTestHarnessBuilder {
-    fn default() -> Self {
-        Self {
-            width: 80,
-            height: 24,
-            timeout: DEFAULT_TIMEOUT,
-            poll_interval: DEFAULT_POLL_INTERVAL,
-            buffer_size: DEFAULT_BUFFER_SIZE,
-            terminal_profile: TerminalProfile::default(),
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+        harness.start_recording();
+
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg(r#"printf 'before \033Pq"1;1;16;12#0~\033\\ after'"#);
+        harness.spawn(cmd)?;
+        match harness.wait_for_text("after") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
         }
-    }
-}
 
-impl TuiTestHarnessBuilder {
-    /// Sets the terminal size.
-    ///
-    /// # Arguments
-    ///
-    /// * `width` - Terminal width in columns
-    /// * `height` - Terminal height in rows
-    pub fn with_size(mut self, width: u16, height: u16) -> Self {
-        self.width = width;
-        self.height = height;
-        self
-    }
+        let recorded = harness.recorded_output();
+        let capture = SixelCapture::from_screen_state(harness.state());
+        assert_eq!(capture.sequences().len(), 1);
 
-    /// Sets the timeout for wait operations.
-    ///
-    /// # Arguments
-    ///
-    /// * `timeout` - Timeout duration
-    pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = timeout;
-        self
+        let range = capture.sequences()[0].stream_range.clone().unwrap();
+        assert_eq!(&recorded[range], b"\x1bPq\"1;1;16;12#0~\x1b\\");
+        Ok(())
     }
 
-    /// Sets the polling interval for wait operations.
-    ///
-    /// # Arguments
-    ///
-    /// * `interval` - Polling interval
-    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
-        self.poll_interval = interval;
-        self
-    }
+    #[cfg(feature = "sixel")]
+    #[test]
+    fn test_wait_for_sixel_in_area() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_timeout(Duration::from_secs(3))
+            .with_poll_interval(Duration::from_millis(20));
 
-    /// Sets the buffer size for reading PTY output.
-    ///
-    /// # Arguments
-    ///
-    /// * `size` - Buffer size in bytes
-    pub fn with_buffer_size(mut self, size: usize) -> Self {
-        self.buffer_size = size;
-        self
-    }
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg(r#"sleep 0.2; printf '\033[5;10H\033Pq"1;1;100;50#0~\033\\'; sleep 0.5"#);
+        harness.spawn(cmd)?;
 
-    /// Sets the terminal profile.
-    ///
-    /// # Arguments
-    ///
-    /// * `profile` - The terminal profile to use
-    pub fn with_terminal_profile(mut self, profile: TerminalProfile) -> Self {
-        self.terminal_profile = profile;
-        self
+        assert!(!harness.has_sixel_in_area((0, 0, 40, 20)));
+        harness.wait_for_sixel_in_area((0, 0, 40, 20))?;
+        assert_eq!(harness.sixel_count(), 1);
+
+        Ok(())
     }
 
-    /// Builds the test harness with the configured settings.
-    ///
-    /// # Errors
-    ///
-    /// Returns an error if terminal creation fails.
-    pub fn build(self) -> Result<TuiTestHarness> {
-        let terminal = TestTerminal::new(self.width, self.height)?;
-        let state = ScreenState::new(self.width, self.height);
+    #[cfg(feature = "sixel")]
+    #[test]
+    fn test_assert_sixel_cleared_after() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        let area = (0, 0, 40, 20);
+
+        // Image shown, then cleared by the action (simulating a screen clear)
+        harness
+            .state_mut()
+            .feed(b"\x1b[5;10H\x1bPq\"1;1;100;50#0~\x1b\\");
+        harness.assert_sixel_cleared_after(area, |h| {
+            h.state_mut().feed(b"\x1b[2J");
+            Ok(())
+        })?;
 
-        Ok(TuiTestHarness {
-            terminal,
-            event_delay: Duration::ZERO,
-            state,
-            timeout: self.timeout,
-            poll_interval: self.poll_interval,
-            buffer_size: self.buffer_size,
-            recording: false,
-            recorded_events: Vec::new(),
-            recording_start: None,
-            verbose: false,
-            terminal_profile: self.terminal_profile,
-            timing_recorder: TimingRecorder::new(),
-            latency_profile: LatencyProfile::new(),
-        })
-    }
-}
+        // Image shown, but the action leaves it in place
+        harness
+            .state_mut()
+            .feed(b"\x1b[5;10H\x1bPq\"1;1;100;50#0~\x1b\\");
+        let result = harness.assert_sixel_cleared_after(area, |_| Ok(()));
+        assert!(matches!(result, Err(TermTestError::SixelValidation(_))));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        // No image in the area to begin with
+        harness.state_mut().reset();
+        let result = harness.assert_sixel_cleared_after(area, |_| Ok(()));
+        assert!(matches!(result, Err(TermTestError::SixelValidation(_))));
 
-    #[test]
-    fn test_create_harness() {
-        let harness = TuiTestHarness::new(80, 24);
-        assert!(harness.is_ok());
-        let harness = harness.unwrap();
-        assert_eq!(harness.timeout, DEFAULT_TIMEOUT);
-        assert_eq!(harness.poll_interval, DEFAULT_POLL_INTERVAL);
-        assert_eq!(harness.buffer_size, DEFAULT_BUFFER_SIZE);
+        Ok(())
     }
 
+    #[cfg(feature = "sixel")]
     #[test]
-    fn test_with_timeout() {
-        let harness = TuiTestHarness::new(80, 24)
-            .unwrap()
-            .with_timeout(Duration::from_secs(10));
-        assert_eq!(harness.timeout, Duration::from_secs(10));
+    fn test_assert_preview_has_sixel_success() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Place Sixel in standard preview area (5, 40, 35, 15)
+        harness.state_mut().feed(b"\x1b[8;45H"); // Row 8, col 45 (within preview)
+        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
+
+        // Should succeed as Sixel is in preview area
+        assert!(harness.assert_preview_has_sixel().is_ok());
+
+        Ok(())
     }
 
+    #[cfg(feature = "sixel")]
     #[test]
-    fn test_with_poll_interval() {
-        let harness = TuiTestHarness::new(80, 24)
-            .unwrap()
-            .with_poll_interval(Duration::from_millis(50));
-        assert_eq!(harness.poll_interval, Duration::from_millis(50));
+    fn test_assert_preview_has_sixel_failure() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Place Sixel outside preview area
+        harness.state_mut().feed(b"\x1b[2;2H"); // Row 2, col 2 (outside preview)
+        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
+
+        // Should fail as Sixel is not in preview area
+        let result = harness.assert_preview_has_sixel();
+        assert!(result.is_err());
+
+        Ok(())
     }
 
+    #[cfg(feature = "sixel")]
     #[test]
-    fn test_builder_default() {
-        let harness = TuiTestHarness::builder().build();
-        assert!(harness.is_ok());
-        let harness = harness.unwrap();
-        assert_eq!(harness.timeout, DEFAULT_TIMEOUT);
-        assert_eq!(harness.poll_interval, DEFAULT_POLL_INTERVAL);
-        assert_eq!(harness.buffer_size, DEFAULT_BUFFER_SIZE);
+    fn test_assert_preview_has_sixel_in_custom() -> Result<()> {
+        let mut harness = TuiTestHarness::new(120, 40)?;
+
+        // Custom preview area for larger terminal
+        let custom_area = (10, 50, 60, 25);
+
+        // Place Sixel in custom area
+        // Position (15, 60) [1-based CSI] = (14, 59) [0-based]
+        // With dimensions 40x30 (small enough to fit within 60x25 area)
+        harness.state_mut().feed(b"\x1b[15;60H"); // Row 15, col 60
+        harness.state_mut().feed(b"\x1bPq\"1;1;40;30#0~\x1b\\");
+
+        // Should succeed - Sixel at (14, 59) with size 40x30 is within (10, 50, 60x25)
+        assert!(harness.assert_preview_has_sixel_in(custom_area).is_ok());
+
+        // Should fail for different area
+        let wrong_area = (0, 0, 20, 20);
+        assert!(harness.assert_preview_has_sixel_in(wrong_area).is_err());
+
+        Ok(())
     }
 
+    #[cfg(feature = "sixel")]
     #[test]
-    fn test_builder_with_size() {
-        let harness = TuiTestHarness::builder()
-            .with_size(120, 40)
-            .build()
-            .unwrap();
-        let (width, height) = harness.state.size();
-        assert_eq!(width, 120);
-        assert_eq!(height, 40);
+    fn test_multiple_sixels_in_area() -> Result<()> {
+        let mut harness = TuiTestHarness::new(100, 40)?;
+
+        // Add multiple Sixels in preview area
+        let preview_area = (5, 30, 60, 30);
+
+        harness.state_mut().feed(b"\x1b[10;40H");
+        harness.state_mut().feed(b"\x1bPq\"1;1;80;60#0~\x1b\\");
+
+        harness.state_mut().feed(b"\x1b[20;50H");
+        harness.state_mut().feed(b"\x1bPq\"1;1;100;80#0~\x1b\\");
+
+        // Both should be detected
+        assert_eq!(harness.sixel_count(), 2);
+        assert!(harness.has_sixel_in_area(preview_area));
+
+        Ok(())
     }
 
+    #[cfg(feature = "sixel")]
     #[test]
-    fn test_builder_with_timeout() {
-        let timeout = Duration::from_secs(15);
-        let harness = TuiTestHarness::builder()
-            .with_timeout(timeout)
-            .build()
-            .unwrap();
-        assert_eq!(harness.timeout, timeout);
+    fn test_sixel_at_screen_edge() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Place Sixel at screen edge
+        harness.state_mut().feed(b"\x1b[1;1H"); // Top-left corner
+        harness.state_mut().feed(b"\x1bPq\"1;1;50;30#0~\x1b\\");
+
+        assert_eq!(harness.sixel_count(), 1);
+        assert!(harness.sixel_at(0, 0).is_some());
+
+        // Verify position
+        let region = harness.sixel_at(0, 0).unwrap();
+        assert_eq!(region.start_row, 0);
+        assert_eq!(region.start_col, 0);
+
+        Ok(())
     }
 
+    #[cfg(feature = "sixel")]
     #[test]
-    fn test_builder_with_poll_interval() {
-        let interval = Duration::from_millis(25);
-        let harness = TuiTestHarness::builder()
-            .with_poll_interval(interval)
-            .build()
-            .unwrap();
-        assert_eq!(harness.poll_interval, interval);
+    fn test_empty_sixel_regions() -> Result<()> {
+        let harness = TuiTestHarness::new(80, 24)?;
+
+        // No Sixel graphics
+        assert_eq!(harness.sixel_count(), 0);
+        assert!(harness.sixel_regions().is_empty());
+        assert!(harness.sixel_at(0, 0).is_none());
+
+        // Empty screen should pass bounds validation
+        let area = (0, 0, 80, 24);
+        assert!(harness.assert_sixel_within_bounds(area).is_ok());
+        assert!(!harness.has_sixel_in_area(area));
+
+        Ok(())
     }
 
+    // ========================================================================
+    // Position and Layout Assertion Tests
+    // ========================================================================
+
     #[test]
-    fn test_builder_with_buffer_size() {
-        let buffer_size = 8192;
-        let harness = TuiTestHarness::builder()
-            .with_buffer_size(buffer_size)
-            .build()
-            .unwrap();
-        assert_eq!(harness.buffer_size, buffer_size);
+    fn test_rect_creation() {
+        use crate::screen::Rect;
+
+        let rect = Rect::new(10, 20, 30, 40);
+        assert_eq!(rect.x, 10);
+        assert_eq!(rect.y, 20);
+        assert_eq!(rect.width, 30);
+        assert_eq!(rect.height, 40);
     }
 
     #[test]
-    fn test_builder_chaining() {
-        let harness = TuiTestHarness::builder()
-            .with_size(100, 30)
-            .with_timeout(Duration::from_secs(20))
-            .with_poll_interval(Duration::from_millis(75))
-            .with_buffer_size(16384)
-            .build()
-            .unwrap();
+    fn test_rect_edges() {
+        use crate::screen::Rect;
 
-        assert_eq!(harness.state.size(), (100, 30));
-        assert_eq!(harness.timeout, Duration::from_secs(20));
-        assert_eq!(harness.poll_interval, Duration::from_millis(75));
-        assert_eq!(harness.buffer_size, 16384);
+        let rect = Rect::new(10, 20, 30, 40);
+        assert_eq!(rect.right(), 40); // 10 + 30
+        assert_eq!(rect.bottom(), 60); // 20 + 40
     }
 
     #[test]
-    fn test_cursor_position() {
-        let harness = TuiTestHarness::new(80, 24).unwrap();
-        let (row, col) = harness.cursor_position();
-        // Initial cursor position should be at (0, 0)
-        assert_eq!(row, 0);
-        assert_eq!(col, 0);
+    fn test_rect_contains_point() {
+        use crate::screen::Rect;
+
+        let rect = Rect::new(10, 20, 30, 40);
+
+        // Points inside
+        assert!(rect.contains(10, 20)); // Top-left corner
+        assert!(rect.contains(25, 35)); // Center
+        assert!(rect.contains(39, 59)); // Bottom-right corner (exclusive)
+
+        // Points outside
+        assert!(!rect.contains(9, 20)); // Left of rect
+        assert!(!rect.contains(10, 19)); // Above rect
+        assert!(!rect.contains(40, 30)); // Right of rect
+        assert!(!rect.contains(20, 60)); // Below rect
     }
 
     #[test]
-    fn test_get_cursor_position_alias() {
-        let harness = TuiTestHarness::new(80, 24).unwrap();
-        let pos1 = harness.cursor_position();
-        let pos2 = harness.get_cursor_position();
-        assert_eq!(pos1, pos2);
+    fn test_rect_contains_rect() {
+        use crate::screen::Rect;
+
+        let outer = Rect::new(0, 0, 80, 24);
+        let inner = Rect::new(10, 10, 20, 10);
+
+        assert!(outer.contains_rect(&inner));
+        assert!(!inner.contains_rect(&outer));
+
+        // Overlapping but not contained
+        let partial = Rect::new(70, 10, 20, 10);
+        assert!(!outer.contains_rect(&partial)); // Extends past right edge
     }
 
     #[test]
-    fn test_wait_for_text_helper_exists() {
-        // Test that all wait_for methods exist and compile (signature test)
-        let harness = TuiTestHarness::new(80, 24).unwrap();
+    fn test_rect_intersects() {
+        use crate::screen::Rect;
 
-        // Test method signatures exist (don't call them as they require a running process)
-        let _: fn(&mut TuiTestHarness, &str) -> Result<()> = TuiTestHarness::wait_for_text;
+        let rect1 = Rect::new(10, 10, 20, 20);
+        let rect2 = Rect::new(20, 20, 20, 20);
 
-        // Verify state access methods exist
-        assert_eq!(harness.cursor_position(), (0, 0));
-        assert_eq!(harness.get_cursor_position(), (0, 0));
+        assert!(rect1.intersects(&rect2));
+        assert!(rect2.intersects(&rect1));
+
+        // No intersection
+        let rect3 = Rect::new(50, 50, 10, 10);
+        assert!(!rect1.intersects(&rect3));
+        assert!(!rect3.intersects(&rect1));
     }
 
     #[test]
-    fn test_state_manipulation() {
-        // Test that we can manipulate state directly
-        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+    fn test_assert_text_at_position_success() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
 
-        // Feed text directly to state
-        harness.state_mut().feed(b"Test Data");
+        // Feed text at a specific position
+        harness.state_mut().feed(b"\x1b[5;10HHello World");
 
-        // Verify we can read it back
-        let contents = harness.screen_contents();
-        assert!(contents.contains("Test"));
+        // Should find the text at position (4, 9) [0-based]
+        harness.assert_text_at_position("Hello", 4, 9)?;
+        harness.assert_text_at_position("World", 4, 15)?;
+
+        Ok(())
     }
 
     #[test]
-    fn test_cursor_position_tracking() {
-        // Test cursor position tracking
-        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+    fn test_assert_text_at_position_failure() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
 
-        // Initial position
-        assert_eq!(harness.cursor_position(), (0, 0));
+        // Feed text
+        harness.state_mut().feed(b"\x1b[5;10HHello");
 
-        // Feed escape sequence to move cursor
-        harness.state_mut().feed(b"\x1b[2;5H"); // Move to row 2, col 5
+        // Should fail when text doesn't match
+        let result = harness.assert_text_at_position("World", 4, 9);
+        assert!(result.is_err());
 
-        // Check new position (note: escape sequences use 1-based indexing, we return 0-based)
-        let (row, col) = harness.cursor_position();
-        assert!(row >= 0); // Just verify we get valid coordinates
-        assert!(col >= 0);
+        if let Err(TermTestError::Parse(msg)) = result {
+            assert!(msg.contains("Text mismatch"));
+            assert!(msg.contains("Hello")); // Should show what was found
+            assert!(msg.contains("World")); // Should show what was expected
+        } else {
+            panic!("Expected Parse error");
+        }
+
+        Ok(())
     }
 
     #[test]
-    fn test_screen_state_access() {
+    fn test_assert_text_at_position_out_of_bounds() {
         let harness = TuiTestHarness::new(80, 24).unwrap();
-        let state = harness.state();
-        assert_eq!(state.size(), (80, 24));
 
-        let contents = harness.screen_contents();
-        assert!(contents.len() > 0 || contents.is_empty()); // Just verify it returns something
+        // Row out of bounds
+        let result = harness.assert_text_at_position("Test", 100, 0);
+        assert!(result.is_err());
+
+        // Column out of bounds
+        let result = harness.assert_text_at_position("Test", 0, 100);
+        assert!(result.is_err());
     }
 
     #[test]
-    fn test_resize() {
-        let mut harness = TuiTestHarness::new(80, 24).unwrap();
-        let result = harness.resize(100, 30);
-        assert!(result.is_ok());
-        assert_eq!(harness.state.size(), (100, 30));
+    fn test_assert_rows_equal() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        harness
+            .state_mut()
+            .feed(b"+-----+\r\n| abc |\r\n+-----+\r\n+----+");
+
+        harness.assert_rows_equal(0, 2)?;
+
+        match harness.assert_rows_equal(0, 3) {
+            Err(TermTestError::Parse(msg)) => {
+                assert!(msg.contains("Rows 0 and 3 differ at column 5"));
+                assert!(msg.contains("\"+-----+\""));
+                assert!(msg.contains("\"+----+\""));
+            }
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+
+        Ok(())
     }
 
     #[test]
-    fn test_is_running_no_process() {
-        let mut harness = TuiTestHarness::new(80, 24).unwrap();
-        assert!(!harness.is_running());
+    fn test_assert_no_wrap_and_wraps() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        harness.state_mut().feed(&[b'x'; 79]);
+        harness.state_mut().feed(b"\r\n");
+        harness.state_mut().feed(&[b'y'; 81]);
+
+        harness.assert_no_wrap(0)?;
+        harness.assert_wraps(1)?;
+
+        match harness.assert_no_wrap(1) {
+            Err(TermTestError::Parse(msg)) => {
+                assert!(msg.contains("Line at row 1 wrapped past column 80"));
+                assert!(msg.contains("Row   2: \"y\""));
+            }
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+        match harness.assert_wraps(0) {
+            Err(TermTestError::Parse(msg)) => assert!(msg.contains("Line at row 0 did not wrap")),
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+        Ok(())
     }
 
     #[test]
-    fn test_spawn_and_check_running() {
-        let mut harness = TuiTestHarness::new(80, 24).unwrap();
-        let mut cmd = CommandBuilder::new("sleep");
-        cmd.arg("0.1");
-
-        let spawn_result = harness.spawn(cmd);
-        if spawn_result.is_ok() {
-            // Should be running initially
-            assert!(harness.is_running());
+    fn test_assert_cursor_stays_in() -> Result<()> {
+        use crate::screen::Rect;
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        harness.state_mut().feed(b"\x1b[3;3H");
+        let pane = Rect::new(0, 0, 40, 10);
 
-            // Wait for it to exit
-            std::thread::sleep(Duration::from_millis(200));
+        harness.assert_cursor_stays_in(pane, |h| {
+            h.state_mut().feed(b"inside\x1b[5;10Hstill inside");
+            Ok(())
+        })?;
 
-            // Should have exited
-            assert!(!harness.is_running());
+        // Jumps to the status bar and back: the end position is inside the
+        // pane, but the excursion must still be reported.
+        let result = harness.assert_cursor_stays_in(pane, |h| {
+            h.state_mut().feed(b"\x1b[24;1HStatus\x1b[5;10H");
+            Ok(())
+        });
+        assert_eq!(harness.cursor_position(), (4, 9));
+        match result {
+            Err(TermTestError::Parse(msg)) => {
+                assert!(msg.contains("Cursor left region"));
+                assert!(msg.contains("(row 23, col 0)"));
+            }
+            other => panic!("Expected Parse error, got {:?}", other),
         }
+
+        Ok(())
     }
 
     #[test]
-    fn test_wait_for_text_success() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+    fn test_assert_cursor_stays_in_samples_later_output() -> Result<()> {
+        use crate::screen::Rect;
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_timeout(Duration::from_secs(3))
+            .with_poll_interval(Duration::from_millis(200));
 
-        let mut cmd = CommandBuilder::new("echo");
-        cmd.arg("hello world");
+        // Redraws the status bar a little after each line of input, then
+        // returns the cursor to the pane
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg(
+            "stty -echo; printf '\\033[3;3Hready'; \
+             while read line; do sleep 0.05; printf '\\033[24;1Hstatus\\033[3;3H'; \
+             sleep 0.05; printf 'text'; done",
+        );
         harness.spawn(cmd)?;
+        harness.wait_for_text("ready")?;
 
-        // wait_for_text may return ProcessExited since echo exits immediately
-        // but that's ok if the text is present
-        match harness.wait_for_text("hello") {
-            Ok(()) => {
-                assert!(harness.screen_contents().contains("hello"));
-            }
-            Err(TermTestError::ProcessExited) => {
-                // Process exited, but check if we still got the output
-                assert!(
-                    harness.screen_contents().contains("hello"),
-                    "Expected 'hello' in output even though process exited"
-                );
+        let result =
+            harness.assert_cursor_stays_in(Rect::new(0, 0, 40, 10), |h| h.send_text("x\n"));
+        match result {
+            Err(TermTestError::Parse(msg)) => assert!(msg.contains("(row 23, col 0)")),
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_redraws_at_most() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        harness.state_mut().feed(b"\x1b[2J\x1b[Hstartup");
+
+        // Startup clears don't count towards the action
+        harness.assert_redraws_at_most(1, |h| {
+            h.state_mut()
+                .feed(b"\x1b[2J\x1b[Hitem 1\x1b[2;1H\x1b[Kitem 2");
+            Ok(())
+        })?;
+
+        let result = harness.assert_redraws_at_most(1, |h| {
+            for i in 0..3 {
+                h.state_mut()
+                    .feed(format!("\x1b[2J\x1b[Hframe {}", i).as_bytes());
             }
-            Err(e) => return Err(e),
+            Ok(())
+        });
+        match result {
+            Err(TermTestError::Parse(msg)) => assert!(msg.contains("cleared 3 times")),
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+        assert_eq!(harness.state().full_redraw_count(), 5);
+
+        // ESC [ 3 J counts as well
+        let result = harness.assert_redraws_at_most(1, |h| {
+            h.state_mut().feed(b"\x1b[2J\x1b[3J\x1b[Hframe");
+            Ok(())
+        });
+        match result {
+            Err(TermTestError::Parse(msg)) => assert!(msg.contains("cleared 2 times")),
+            other => panic!("Expected Parse error, got {:?}", other),
         }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_text_within_bounds_success() -> Result<()> {
+        use crate::screen::Rect;
+        let mut harness = TuiTestHarness::new(80, 24)?;
+
+        // Feed text in a specific area
+        harness.state_mut().feed(b"\x1b[10;40HPreview");
+
+        // Should find text within the preview area
+        let preview_area = Rect::new(35, 5, 40, 20);
+        harness.assert_text_within_bounds("Preview", preview_area)?;
+
         Ok(())
     }
 
     #[test]
-    fn test_wait_for_text_timeout() {
-        let mut harness = TuiTestHarness::new(80, 24)
-            .unwrap()
-            .with_timeout(Duration::from_millis(300));
+    fn test_assert_text_within_bounds_failure() -> Result<()> {
+        use crate::screen::Rect;
+        let mut harness = TuiTestHarness::new(80, 24)?;
 
-        let mut cmd = CommandBuilder::new("sleep");
-        cmd.arg("1");
-        harness.spawn(cmd).unwrap();
+        // Feed text outside the area
+        harness.state_mut().feed(b"\x1b[1;1HOutside");
 
-        // Should timeout waiting for text that never appears
-        let result = harness.wait_for_text("never_appears");
+        // Should fail when text is not in the specified area
+        let preview_area = Rect::new(40, 10, 30, 10);
+        let result = harness.assert_text_within_bounds("Outside", preview_area);
         assert!(result.is_err());
 
-        match result {
-            Err(TermTestError::Timeout { timeout_ms }) => {
-                assert_eq!(timeout_ms, 300);
-            }
-            Err(TermTestError::ProcessExited) => {
-                // Also acceptable - process may exit before timeout
-            }
-            _ => panic!("Expected Timeout or ProcessExited error"),
+        if let Err(TermTestError::Parse(msg)) = result {
+            assert!(msg.contains("not found within bounds"));
+        } else {
+            panic!("Expected Parse error");
         }
+
+        Ok(())
     }
 
     #[test]
-    fn test_wait_for_text_with_custom_timeout() -> Result<()> {
+    fn test_assert_text_within_bounds_tab_bar() -> Result<()> {
+        use crate::screen::Rect;
         let mut harness = TuiTestHarness::new(80, 24)?;
 
-        let mut cmd = CommandBuilder::new("echo");
-        cmd.arg("quick test");
-        harness.spawn(cmd)?;
+        // Simulate tab bar at bottom
+        harness.state_mut().feed(b"\x1b[23;1HTab 1 | Tab 2 | Tab 3");
+
+        // Define tab bar area (last 2 rows)
+        let tab_bar_area = Rect::new(0, 22, 80, 2);
+
+        // Should find tabs in the area
+        harness.assert_text_within_bounds("Tab 1", tab_bar_area)?;
+        harness.assert_text_within_bounds("Tab 2", tab_bar_area)?;
+        harness.assert_text_within_bounds("Tab 3", tab_bar_area)?;
 
-        // Use custom timeout (shorter than default)
-        // May return ProcessExited since echo exits immediately
-        match harness.wait_for_text_timeout("quick", Duration::from_millis(500)) {
-            Ok(()) => {
-                assert!(harness.screen_contents().contains("quick"));
-            }
-            Err(TermTestError::ProcessExited) => {
-                // Process exited, but check if we still got the output
-                assert!(
-                    harness.screen_contents().contains("quick"),
-                    "Expected 'quick' in output even though process exited"
-                );
-            }
-            Err(e) => return Err(e),
-        }
         Ok(())
     }
 
     #[test]
-    fn test_wait_for_cursor_success() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_assert_no_overlap_success() -> Result<()> {
+        use crate::screen::Rect;
+        let harness = TuiTestHarness::new(80, 24)?;
 
-        // Feed escape sequence to move cursor
-        harness.state_mut().feed(b"\x1b[10;20H"); // Move to row 10, col 20
+        // Non-overlapping rectangles
+        let sidebar = Rect::new(0, 0, 20, 24);
+        let preview = Rect::new(20, 0, 60, 24);
 
-        // Wait for cursor to be at the position (1-based in CSI, 0-based in our API)
-        // Since no process is running, this will return ProcessExited immediately
-        // but that's fine - the cursor position should already be set
+        harness.assert_no_overlap(sidebar, preview)?;
 
-        match harness.wait_for_cursor((9, 19)) {
-            Ok(()) => {
-                let pos = harness.cursor_position();
-                assert_eq!(pos, (9, 19));
-            }
-            Err(TermTestError::ProcessExited) => {
-                // No process running, but cursor should still be in the right position
-                let pos = harness.cursor_position();
-                assert_eq!(
-                    pos,
-                    (9, 19),
-                    "Cursor should be at (9, 19) even though no process is running"
-                );
-            }
-            Err(e) => return Err(e),
-        }
         Ok(())
     }
 
     #[test]
-    fn test_wait_for_cursor_timeout() {
-        let mut harness = TuiTestHarness::new(80, 24)
-            .unwrap()
-            .with_timeout(Duration::from_millis(300));
+    fn test_assert_no_overlap_failure() -> Result<()> {
+        use crate::screen::Rect;
+        let harness = TuiTestHarness::new(80, 24)?;
 
-        // Cursor is at (0, 0) initially
-        let result = harness.wait_for_cursor((50, 50));
+        // Overlapping rectangles
+        let rect1 = Rect::new(10, 10, 20, 20);
+        let rect2 = Rect::new(20, 20, 20, 20);
+
+        let result = harness.assert_no_overlap(rect1, rect2);
         assert!(result.is_err());
 
-        match result {
-            Err(TermTestError::Timeout { .. }) => {
-                // Expected timeout
-            }
-            Err(TermTestError::ProcessExited) => {
-                // Also acceptable - no process is running
-            }
-            _ => panic!("Expected Timeout or ProcessExited error"),
+        if let Err(TermTestError::Parse(msg)) = result {
+            assert!(msg.contains("overlap"));
+        } else {
+            panic!("Expected Parse error");
         }
+
+        Ok(())
     }
 
     #[test]
-    fn test_wait_for_cursor_with_custom_timeout() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_assert_aligned_horizontal_success() -> Result<()> {
+        use crate::screen::Rect;
+        let harness = TuiTestHarness::new(80, 24)?;
 
-        // Feed escape sequence to move cursor
-        harness.state_mut().feed(b"\x1b[5;10H");
+        // Buttons on same row
+        let button1 = Rect::new(10, 20, 15, 3);
+        let button2 = Rect::new(30, 20, 15, 3);
+
+        harness.assert_aligned(button1, button2, Axis::Horizontal)?;
 
-        // Use custom timeout - may return ProcessExited since no process is running
-        match harness.wait_for_cursor_timeout((4, 9), Duration::from_millis(500)) {
-            Ok(()) => {
-                let pos = harness.cursor_position();
-                assert_eq!(pos, (4, 9));
-            }
-            Err(TermTestError::ProcessExited) => {
-                // No process running, but cursor should still be in the right position
-                let pos = harness.cursor_position();
-                assert_eq!(
-                    pos,
-                    (4, 9),
-                    "Cursor should be at (4, 9) even though no process is running"
-                );
-            }
-            Err(e) => return Err(e),
-        }
         Ok(())
     }
 
     #[test]
-    fn test_wait_for_custom_predicate() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+    fn test_assert_aligned_horizontal_failure() -> Result<()> {
+        use crate::screen::Rect;
+        let harness = TuiTestHarness::new(80, 24)?;
 
-        let mut cmd = CommandBuilder::new("echo");
-        cmd.arg("test123");
-        harness.spawn(cmd)?;
+        // Buttons on different rows
+        let button1 = Rect::new(10, 20, 15, 3);
+        let button2 = Rect::new(30, 21, 15, 3);
 
-        // Wait for custom condition: screen contains a digit
-        // May return ProcessExited since echo exits immediately
-        match harness.wait_for(|state| state.contents().chars().any(|c| c.is_numeric())) {
-            Ok(()) => {
-                assert!(harness.screen_contents().contains('1'));
-            }
-            Err(TermTestError::ProcessExited) => {
-                // Process exited, but check if we still got the output
-                assert!(
-                    harness.screen_contents().contains('1'),
-                    "Expected digit in output even though process exited"
-                );
-            }
-            Err(e) => return Err(e),
+        let result = harness.assert_aligned(button1, button2, Axis::Horizontal);
+        assert!(result.is_err());
+
+        if let Err(TermTestError::Parse(msg)) = result {
+            assert!(msg.contains("not horizontally aligned"));
+            assert!(msg.contains("different Y coordinates"));
+        } else {
+            panic!("Expected Parse error");
         }
+
         Ok(())
     }
 
     #[test]
-    fn test_wait_for_multiline_output() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+    fn test_assert_aligned_vertical_success() -> Result<()> {
+        use crate::screen::Rect;
+        let harness = TuiTestHarness::new(80, 24)?;
 
-        let mut cmd = CommandBuilder::new("sh");
-        cmd.arg("-c");
-        cmd.arg("echo 'line1'; echo 'line2'; echo 'line3'");
-        harness.spawn(cmd)?;
+        // Panels in same column
+        let panel1 = Rect::new(40, 0, 40, 10);
+        let panel2 = Rect::new(40, 10, 40, 14);
+
+        harness.assert_aligned(panel1, panel2, Axis::Vertical)?;
 
-        // Wait for all lines to appear - may return ProcessExited
-        match harness.wait_for(|state| {
-            let contents = state.contents();
-            contents.contains("line1") && contents.contains("line2") && contents.contains("line3")
-        }) {
-            Ok(()) => {
-                let contents = harness.screen_contents();
-                assert!(contents.contains("line1"));
-                assert!(contents.contains("line2"));
-                assert!(contents.contains("line3"));
-            }
-            Err(TermTestError::ProcessExited) => {
-                // Process exited, check if we still got all output
-                let contents = harness.screen_contents();
-                assert!(contents.contains("line1"), "Expected line1 in output");
-                assert!(contents.contains("line2"), "Expected line2 in output");
-                assert!(contents.contains("line3"), "Expected line3 in output");
-            }
-            Err(e) => return Err(e),
-        }
         Ok(())
     }
 
     #[test]
-    fn test_wait_for_complex_predicate() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
-
-        let mut cmd = CommandBuilder::new("echo");
-        cmd.arg("Ready: 100%");
-        harness.spawn(cmd)?;
+    fn test_assert_aligned_vertical_failure() -> Result<()> {
+        use crate::screen::Rect;
+        let harness = TuiTestHarness::new(80, 24)?;
 
-        // Complex predicate: check for pattern - may return ProcessExited
-        match harness.wait_for(|state| {
-            let contents = state.contents();
-            contents.contains("Ready") && contents.contains("%")
-        }) {
-            Ok(()) => {
-                assert!(harness.screen_contents().contains("Ready: 100%"));
-            }
-            Err(TermTestError::ProcessExited) => {
-                // Process exited, but check if we still got the output
-                assert!(
-                    harness.screen_contents().contains("Ready: 100%"),
-                    "Expected 'Ready: 100%' in output even though process exited"
-                );
-            }
-            Err(e) => return Err(e),
+        // Panels in different columns
+        let panel1 = Rect::new(40, 0, 40, 10);
+        let panel2 = Rect::new(41, 10, 39, 14);
+
+        let result = harness.assert_aligned(panel1, panel2, Axis::Vertical);
+        assert!(result.is_err());
+
+        if let Err(TermTestError::Parse(msg)) = result {
+            assert!(msg.contains("not vertically aligned"));
+            assert!(msg.contains("different X coordinates"));
+        } else {
+            panic!("Expected Parse error");
         }
+
         Ok(())
     }
 
     #[test]
-    fn test_update_state_multiple_times() -> Result<()> {
+    fn test_complex_layout_assertions() -> Result<()> {
+        use crate::screen::Rect;
         let mut harness = TuiTestHarness::new(80, 24)?;
 
-        let mut cmd = CommandBuilder::new("echo");
-        cmd.arg("data");
-        harness.spawn(cmd)?;
+        // Simulate complex layout
+        harness.state_mut().feed(b"\x1b[1;1HHeader");
+        harness.state_mut().feed(b"\x1b[3;1HSidebar");
+        harness.state_mut().feed(b"\x1b[3;21HContent");
+        harness.state_mut().feed(b"\x1b[23;1HStatus Bar");
 
-        // Multiple updates - first may succeed, subsequent ones may return ProcessExited
-        let _ = harness.update_state(); // First update may get data
+        // Define areas
+        let header = Rect::new(0, 0, 80, 2);
+        let sidebar = Rect::new(0, 2, 20, 20);
+        let content = Rect::new(20, 2, 60, 20);
+        let status_bar = Rect::new(0, 22, 80, 2);
 
-        // Give echo time to finish
-        std::thread::sleep(Duration::from_millis(100));
+        // Assert text in areas
+        harness.assert_text_within_bounds("Header", header)?;
+        harness.assert_text_within_bounds("Sidebar", sidebar)?;
+        harness.assert_text_within_bounds("Content", content)?;
+        harness.assert_text_within_bounds("Status Bar", status_bar)?;
 
-        // Subsequent updates will likely return ProcessExited
-        match harness.update_state() {
-            Ok(()) | Err(TermTestError::ProcessExited) => {
-                // Either is fine
-            }
-            Err(e) => return Err(e),
-        }
+        // Assert no overlap
+        harness.assert_no_overlap(sidebar, content)?;
+        harness.assert_no_overlap(header, status_bar)?;
+
+        // Assert alignment
+        harness.assert_aligned(sidebar, content, Axis::Horizontal)?;
 
-        // Check that we got the data at some point
-        assert!(harness.screen_contents().contains("data"), "Expected 'data' in output");
         Ok(())
     }
 
-    // ========================================================================
-    // Sixel Validation API Tests
-    // ========================================================================
-
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_sixel_count() -> Result<()> {
+    fn test_multiline_text_in_area() -> Result<()> {
+        use crate::screen::Rect;
         let mut harness = TuiTestHarness::new(80, 24)?;
 
-        // Initially no Sixel graphics
-        assert_eq!(harness.sixel_count(), 0);
-
-        // Feed a Sixel sequence directly to the screen state
-        harness.state_mut().feed(b"\x1b[5;10H"); // Move cursor
-        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\"); // Sixel sequence
-
-        // Should now have one Sixel
-        assert_eq!(harness.sixel_count(), 1);
+        // Feed multiline content
+        harness.state_mut().feed(b"\x1b[10;40HLine 1");
+        harness.state_mut().feed(b"\x1b[11;40HLine 2");
+        harness.state_mut().feed(b"\x1b[12;40HLine 3");
 
-        // Feed another Sixel
-        harness.state_mut().feed(b"\x1b[10;20H");
-        harness.state_mut().feed(b"\x1bPq\"1;1;80;60#0~\x1b\\");
+        let content_area = Rect::new(35, 5, 40, 20);
 
-        // Should now have two Sixels
-        assert_eq!(harness.sixel_count(), 2);
+        // Should find each line
+        harness.assert_text_within_bounds("Line 1", content_area)?;
+        harness.assert_text_within_bounds("Line 2", content_area)?;
+        harness.assert_text_within_bounds("Line 3", content_area)?;
 
         Ok(())
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_sixel_regions() -> Result<()> {
+    fn test_edge_case_text_at_screen_edge() -> Result<()> {
         let mut harness = TuiTestHarness::new(80, 24)?;
 
-        // Feed Sixel with known dimensions
-        harness.state_mut().feed(b"\x1b[5;10H"); // Position (4, 9) in 0-based
-        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
-
-        let regions = harness.sixel_regions();
-        assert_eq!(regions.len(), 1);
+        // Text at top-left corner
+        harness.state_mut().feed(b"\x1b[1;1HCorner");
+        harness.assert_text_at_position("Corner", 0, 0)?;
 
-        let region = &regions[0];
-        assert_eq!(region.start_row, 4); // 5-1 (CSI is 1-based, we use 0-based)
-        assert_eq!(region.start_col, 9); // 10-1
-        assert_eq!(region.width, 100);
-        assert_eq!(region.height, 50);
+        // Text near bottom-right corner
+        harness.state_mut().feed(b"\x1b[24;75HEnd");
+        harness.assert_text_at_position("End", 23, 74)?;
 
         Ok(())
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_sixel_at_position() -> Result<()> {
+    fn test_text_spanning_multiple_lines() -> Result<()> {
         let mut harness = TuiTestHarness::new(80, 24)?;
 
-        // Feed Sixel at position (5, 10) [1-based in CSI]
-        harness.state_mut().feed(b"\x1b[5;10H");
-        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
-
-        // Should find Sixel at (4, 9) [0-based]
-        let region = harness.sixel_at(4, 9);
-        assert!(region.is_some());
-
-        let region = region.unwrap();
-        assert_eq!(region.width, 100);
-        assert_eq!(region.height, 50);
+        // Text that wraps or is on multiple lines
+        harness.state_mut().feed(b"\x1b[5;10HFirst");
+        harness.state_mut().feed(b"\x1b[6;10HSecond");
 
-        // Should not find Sixel at other positions
-        assert!(harness.sixel_at(0, 0).is_none());
-        assert!(harness.sixel_at(10, 10).is_none());
+        // Each line should be found independently
+        harness.assert_text_at_position("First", 4, 9)?;
+        harness.assert_text_at_position("Second", 5, 9)?;
 
         Ok(())
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_assert_sixel_within_bounds_success() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_memory_usage() -> Result<()> {
+        let harness = TuiTestHarness::new(80, 24)?;
 
-        // Place Sixel well within screen bounds
-        harness.state_mut().feed(b"\x1b[5;10H");
-        harness.state_mut().feed(b"\x1bPq\"1;1;10;10#0~\x1b\\");
+        let memory = harness.memory_usage();
 
-        // Large area that encompasses the Sixel
-        let area = (0, 0, 80, 24);
-        assert!(harness.assert_sixel_within_bounds(area).is_ok());
+        // Should have non-zero memory for screen buffer
+        assert!(memory.current_bytes > 0);
+        assert_eq!(memory.current_bytes, memory.peak_bytes);
 
-        // Smaller area that still contains the Sixel
-        let area = (3, 8, 20, 15);
-        assert!(harness.assert_sixel_within_bounds(area).is_ok());
+        // Basic sanity check: 80x24 screen should be reasonable size
+        // Each Cell is at least char (4 bytes) + options + bools
+        let min_expected = 80 * 24 * 4; // Very conservative estimate
+        assert!(memory.current_bytes >= min_expected);
 
         Ok(())
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_assert_sixel_within_bounds_failure() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
-
-        // Place Sixel at position (4, 9) with size 10x10
-        harness.state_mut().feed(b"\x1b[5;10H");
-        harness.state_mut().feed(b"\x1bPq\"1;1;10;10#0~\x1b\\");
+    fn test_memory_usage_with_different_sizes() -> Result<()> {
+        let small = TuiTestHarness::new(20, 10)?;
+        let large = TuiTestHarness::new(200, 100)?;
 
-        // Area that doesn't contain the Sixel
-        let area = (0, 0, 5, 5);
-        let result = harness.assert_sixel_within_bounds(area);
-        assert!(result.is_err());
+        let small_memory = small.memory_usage();
+        let large_memory = large.memory_usage();
 
-        // Check that error is SixelValidation
-        if let Err(crate::error::TermTestError::SixelValidation(msg)) = result {
-            assert!(msg.contains("outside area"));
-        } else {
-            panic!("Expected SixelValidation error");
-        }
+        // Larger terminal should use more memory
+        assert!(large_memory.current_bytes > small_memory.current_bytes);
 
         Ok(())
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_has_sixel_in_area() -> Result<()> {
+    fn test_memory_usage_with_sixel() -> Result<()> {
+        use crate::screen::SixelRegion;
+
         let mut harness = TuiTestHarness::new(80, 24)?;
 
-        // No Sixel initially
-        let area = (0, 0, 80, 24);
-        assert!(!harness.has_sixel_in_area(area));
+        // Get initial memory without Sixel
+        let initial_memory = harness.memory_usage();
 
-        // Add Sixel at (4, 9)
-        harness.state_mut().feed(b"\x1b[5;10H");
-        harness.state_mut().feed(b"\x1bPq\"1;1;10;10#0~\x1b\\");
+        // Add a mock Sixel region (simulate Sixel data)
+        let sixel_data = vec![0u8; 1000]; // 1KB of Sixel data
+        let region = SixelRegion {
+            start_row: 5,
+            start_col: 10,
+            width: 100,
+            height: 50,
+            data: sixel_data,
+        };
 
-        // Should detect Sixel in large area
-        assert!(harness.has_sixel_in_area((0, 0, 80, 24)));
+        // Manually add the region to the state for testing
+        // Note: In real usage, this would come from parsing terminal output
+        harness.state_mut().sixel_regions_mut().push(region);
 
-        // Should detect in area that contains it
-        assert!(harness.has_sixel_in_area((0, 0, 20, 20)));
+        // Get memory with Sixel
+        let with_sixel_memory = harness.memory_usage();
 
-        // Should not detect in area that doesn't contain it
-        assert!(!harness.has_sixel_in_area((20, 20, 10, 10)));
+        // Memory should increase by approximately the size of the Sixel data
+        assert!(with_sixel_memory.current_bytes > initial_memory.current_bytes);
+        let increase = with_sixel_memory.current_bytes - initial_memory.current_bytes;
+        assert!(increase >= 1000); // Should be at least 1KB
 
         Ok(())
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_verify_sixel_cleared() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_assert_memory_under_success() -> Result<()> {
+        let harness = TuiTestHarness::new(80, 24)?;
 
-        // Add Sixel
-        harness.state_mut().feed(b"\x1b[5;10H");
-        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
-        assert_eq!(harness.sixel_count(), 1);
+        // Set a generous limit that should pass
+        harness.assert_memory_under(1_000_000)?; // 1 MB
 
-        // Recreate state (simulating screen clear)
-        let new_state = crate::screen::ScreenState::new(80, 24);
-        *harness.state_mut() = new_state;
+        Ok(())
+    }
 
-        // Manual update (simulating what verify_sixel_cleared does)
-        let before = harness.sixel_count();
-        assert_eq!(before, 0); // Already cleared due to new state
+    #[test]
+    fn test_assert_memory_under_failure() -> Result<()> {
+        let harness = TuiTestHarness::new(80, 24)?;
+
+        // Set a very small limit that should fail
+        let result = harness.assert_memory_under(100); // 100 bytes
+
+        assert!(result.is_err());
+        if let Err(e) = result {
+            let error_msg = format!("{}", e);
+            assert!(error_msg.contains("Memory usage exceeds limit"));
+            assert!(error_msg.contains("100 bytes (limit)"));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_memory_results_summary_format() -> Result<()> {
+        let harness = TuiTestHarness::new(80, 24)?;
+        let memory = harness.memory_usage();
+        let summary = memory.summary();
+
+        // Check that summary contains expected fields
+        assert!(summary.contains("Memory Usage"));
+        assert!(summary.contains("Current:"));
+        assert!(summary.contains("Peak:"));
+        assert!(summary.contains("bytes"));
+        assert!(summary.contains("KB"));
 
         Ok(())
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_assert_preview_has_sixel_success() -> Result<()> {
+    fn test_memory_tracking_across_operations() -> Result<()> {
         let mut harness = TuiTestHarness::new(80, 24)?;
 
-        // Place Sixel in standard preview area (5, 40, 35, 15)
-        harness.state_mut().feed(b"\x1b[8;45H"); // Row 8, col 45 (within preview)
-        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
+        let initial = harness.memory_usage();
 
-        // Should succeed as Sixel is in preview area
-        assert!(harness.assert_preview_has_sixel().is_ok());
+        // Feed some data to the screen
+        harness.state_mut().feed(b"Hello, World!");
+        harness.state_mut().feed(b"\x1b[2J"); // Clear screen
+        harness.state_mut().feed(b"More text");
+
+        let after_operations = harness.memory_usage();
+
+        // Memory should remain roughly the same (screen buffer size doesn't change)
+        // Allow for small variations due to internal state
+        let diff = if after_operations.current_bytes > initial.current_bytes {
+            after_operations.current_bytes - initial.current_bytes
+        } else {
+            initial.current_bytes - after_operations.current_bytes
+        };
+
+        // Should be within 10% of original
+        assert!(diff < initial.current_bytes / 10);
 
         Ok(())
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_assert_preview_has_sixel_failure() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_event_delay_default() {
+        let harness = TuiTestHarness::new(80, 24).unwrap();
+        // Default should be zero
+        assert_eq!(harness.event_delay(), Duration::ZERO);
+    }
 
-        // Place Sixel outside preview area
-        harness.state_mut().feed(b"\x1b[2;2H"); // Row 2, col 2 (outside preview)
-        harness.state_mut().feed(b"\x1bPq\"1;1;100;50#0~\x1b\\");
+    #[test]
+    fn test_set_event_delay() {
+        let mut harness = TuiTestHarness::new(80, 24).unwrap();
 
-        // Should fail as Sixel is not in preview area
-        let result = harness.assert_preview_has_sixel();
-        assert!(result.is_err());
+        // Set a custom delay
+        harness.set_event_delay(Duration::from_millis(100));
+        assert_eq!(harness.event_delay(), Duration::from_millis(100));
 
-        Ok(())
+        // Change it
+        harness.set_event_delay(Duration::from_millis(200));
+        assert_eq!(harness.event_delay(), Duration::from_millis(200));
+
+        // Reset to zero
+        harness.set_event_delay(Duration::ZERO);
+        assert_eq!(harness.event_delay(), Duration::ZERO);
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_assert_preview_has_sixel_in_custom() -> Result<()> {
-        let mut harness = TuiTestHarness::new(120, 40)?;
-
-        // Custom preview area for larger terminal
-        let custom_area = (10, 50, 60, 25);
-
-        // Place Sixel in custom area
-        // Position (15, 60) [1-based CSI] = (14, 59) [0-based]
-        // With dimensions 40x30 (small enough to fit within 60x25 area)
-        harness.state_mut().feed(b"\x1b[15;60H"); // Row 15, col 60
-        harness.state_mut().feed(b"\x1bPq\"1;1;40;30#0~\x1b\\");
+    fn test_advance_time() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
 
-        // Should succeed - Sixel at (14, 59) with size 40x30 is within (10, 50, 60x25)
-        assert!(harness.assert_preview_has_sixel_in(custom_area).is_ok());
+        // advance_time should succeed
+        let start = Instant::now();
+        harness.advance_time(Duration::from_millis(100))?;
+        let elapsed = start.elapsed();
 
-        // Should fail for different area
-        let wrong_area = (0, 0, 20, 20);
-        assert!(harness.assert_preview_has_sixel_in(wrong_area).is_err());
+        // Should have actually waited (with some tolerance for scheduling)
+        assert!(elapsed >= Duration::from_millis(95));
+        assert!(elapsed <= Duration::from_millis(200));
 
         Ok(())
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_multiple_sixels_in_area() -> Result<()> {
-        let mut harness = TuiTestHarness::new(100, 40)?;
-
-        // Add multiple Sixels in preview area
-        let preview_area = (5, 30, 60, 30);
-
-        harness.state_mut().feed(b"\x1b[10;40H");
-        harness.state_mut().feed(b"\x1bPq\"1;1;80;60#0~\x1b\\");
+    fn test_press_key_repeat() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
 
-        harness.state_mut().feed(b"\x1b[20;50H");
-        harness.state_mut().feed(b"\x1bPq\"1;1;100;80#0~\x1b\\");
+        // Test that press_key_repeat sends multiple keys
+        let start = Instant::now();
+        harness.press_key_repeat('a', 3, Duration::from_millis(50))?;
+        let elapsed = start.elapsed();
 
-        // Both should be detected
-        assert_eq!(harness.sixel_count(), 2);
-        assert!(harness.has_sixel_in_area(preview_area));
+        // Should take at least 3 * 50ms = 150ms
+        // (plus the default 50ms delay after each key from send_key_event)
+        // Total: ~300ms minimum
+        assert!(elapsed >= Duration::from_millis(250));
 
         Ok(())
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_sixel_at_screen_edge() -> Result<()> {
+    fn test_event_delay_affects_timing() -> Result<()> {
         let mut harness = TuiTestHarness::new(80, 24)?;
 
-        // Place Sixel at screen edge
-        harness.state_mut().feed(b"\x1b[1;1H"); // Top-left corner
-        harness.state_mut().feed(b"\x1bPq\"1;1;50;30#0~\x1b\\");
+        // With custom delay
+        harness.set_event_delay(Duration::from_millis(100));
 
-        assert_eq!(harness.sixel_count(), 1);
-        assert!(harness.sixel_at(0, 0).is_some());
+        let start = Instant::now();
+        // Send 3 characters
+        harness.send_keys("abc")?;
+        let elapsed = start.elapsed();
 
-        // Verify position
-        let region = harness.sixel_at(0, 0).unwrap();
-        assert_eq!(region.start_row, 0);
-        assert_eq!(region.start_col, 0);
+        // Each key should take 100ms, so 3 keys = ~300ms
+        assert!(elapsed >= Duration::from_millis(250));
 
         Ok(())
     }
 
-    #[cfg(feature = "sixel")]
     #[test]
-    fn test_empty_sixel_regions() -> Result<()> {
-        let harness = TuiTestHarness::new(80, 24)?;
+    fn test_press_key_repeat_with_zero_interval() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
 
-        // No Sixel graphics
-        assert_eq!(harness.sixel_count(), 0);
-        assert!(harness.sixel_regions().is_empty());
-        assert!(harness.sixel_at(0, 0).is_none());
+        // Should work with zero interval (only default key delay applies)
+        let start = Instant::now();
+        harness.press_key_repeat('x', 2, Duration::ZERO)?;
+        let elapsed = start.elapsed();
 
-        // Empty screen should pass bounds validation
-        let area = (0, 0, 80, 24);
-        assert!(harness.assert_sixel_within_bounds(area).is_ok());
-        assert!(!harness.has_sixel_in_area(area));
+        // Should still take time due to the default 50ms delay in send_key_event
+        // 2 keys * 50ms = ~100ms minimum
+        assert!(elapsed >= Duration::from_millis(80));
 
         Ok(())
     }
 
-    // ========================================================================
-    // Position and Layout Assertion Tests
-    // ========================================================================
-
     #[test]
-    fn test_rect_creation() {
-        use crate::screen::Rect;
+    fn test_timing_combination() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
 
-        let rect = Rect::new(10, 20, 30, 40);
-        assert_eq!(rect.x, 10);
-        assert_eq!(rect.y, 20);
-        assert_eq!(rect.width, 30);
-        assert_eq!(rect.height, 40);
-    }
+        // Test combining different timing methods
+        harness.set_event_delay(Duration::from_millis(50));
 
-    #[test]
-    fn test_rect_edges() {
-        use crate::screen::Rect;
+        let start = Instant::now();
 
-        let rect = Rect::new(10, 20, 30, 40);
-        assert_eq!(rect.right(), 40); // 10 + 30
-        assert_eq!(rect.bottom(), 60); // 20 + 40
-    }
+        // Send a key (50ms delay)
+        harness.send_key(KeyCode::Char('a'))?;
 
-    #[test]
-    fn test_rect_contains_point() {
-        use crate::screen::Rect;
+        // Advance time (100ms)
+        harness.advance_time(Duration::from_millis(100))?;
 
-        let rect = Rect::new(10, 20, 30, 40);
+        // Send repeated keys (2 * 50ms interval + 2 * 50ms event delay = 200ms)
+        harness.press_key_repeat('b', 2, Duration::from_millis(50))?;
 
-        // Points inside
-        assert!(rect.contains(10, 20)); // Top-left corner
-        assert!(rect.contains(25, 35)); // Center
-        assert!(rect.contains(39, 59)); // Bottom-right corner (exclusive)
+        let elapsed = start.elapsed();
 
-        // Points outside
-        assert!(!rect.contains(9, 20)); // Left of rect
-        assert!(!rect.contains(10, 19)); // Above rect
-        assert!(!rect.contains(40, 30)); // Right of rect
-        assert!(!rect.contains(20, 60)); // Below rect
+        // Total: 50 + 100 + 200 = 350ms minimum
+        assert!(elapsed >= Duration::from_millis(300));
+
+        Ok(())
     }
 
     #[test]
-    fn test_rect_contains_rect() {
-        use crate::screen::Rect;
-
-        let outer = Rect::new(0, 0, 80, 24);
-        let inner = Rect::new(10, 10, 20, 10);
-
-        assert!(outer.contains_rect(&inner));
-        assert!(!inner.contains_rect(&outer));
-
-        // Overlapping but not contained
-        let partial = Rect::new(70, 10, 20, 10);
-        assert!(!outer.contains_rect(&partial)); // Extends past right edge
+    fn test_run_script() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+        harness.spawn(CommandBuilder::new("cat"))?;
+
+        harness.run_script(&[
+            (Duration::ZERO, ScriptAction::SendText("first\n".into())),
+            (Duration::from_millis(50), ScriptAction::WaitForText("first".into())),
+            (Duration::from_millis(50), ScriptAction::SendText("second\n".into())),
+            (Duration::from_millis(100), ScriptAction::WaitForText("second".into())),
+            (Duration::ZERO, ScriptAction::Expect("first".into())),
+        ])?;
+
+        let result =
+            harness.run_script(&[(Duration::ZERO, ScriptAction::Expect("missing".into()))]);
+        match result {
+            Err(TermTestError::Parse(msg)) => assert!(msg.contains("step 0")),
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+        Ok(())
     }
 
     #[test]
-    fn test_rect_intersects() {
-        use crate::screen::Rect;
+    fn test_prompt_flow() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(3));
 
-        let rect1 = Rect::new(10, 10, 20, 20);
-        let rect2 = Rect::new(20, 20, 20, 20);
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg(
+            "printf 'Name? '; read name; printf 'Favorite color? '; read color; \
+             echo \"Hello $name, you like $color\"; sleep 1",
+        );
+        harness.spawn(cmd)?;
 
-        assert!(rect1.intersects(&rect2));
-        assert!(rect2.intersects(&rect1));
+        harness.prompt_flow(&[("Name?", "Ada"), ("Favorite color?", "teal")])?;
 
-        // No intersection
-        let rect3 = Rect::new(50, 50, 10, 10);
-        assert!(!rect1.intersects(&rect3));
-        assert!(!rect3.intersects(&rect1));
+        match harness.wait_for_text("Hello Ada, you like teal") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(harness
+            .screen_contents()
+            .contains("Hello Ada, you like teal"));
+        Ok(())
     }
 
     #[test]
-    fn test_assert_text_at_position_success() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
-
-        // Feed text at a specific position
-        harness.state_mut().feed(b"\x1b[5;10HHello World");
+    fn test_prompt_flow_missing_prompt() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_millis(500));
 
-        // Should find the text at position (4, 9) [0-based]
-        harness.assert_text_at_position("Hello", 4, 9)?;
-        harness.assert_text_at_position("World", 4, 15)?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("printf 'Name? '; read name; sleep 2");
+        harness.spawn(cmd)?;
 
+        match harness.prompt_flow(&[("Name?", "Ada"), ("Favorite color?", "teal")]) {
+            Err(TermTestError::Parse(msg)) => {
+                assert!(msg.contains("Prompt step 1 never showed prompt \"Favorite color?\""));
+            }
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
         Ok(())
     }
 
     #[test]
-    fn test_assert_text_at_position_failure() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
-
-        // Feed text
-        harness.state_mut().feed(b"\x1b[5;10HHello");
-
-        // Should fail when text doesn't match
-        let result = harness.assert_text_at_position("World", 4, 9);
-        assert!(result.is_err());
+    fn test_wait_until_idle() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(5));
 
-        if let Err(TermTestError::Parse(msg)) = result {
-            assert!(msg.contains("Text mismatch"));
-            assert!(msg.contains("Hello")); // Should show what was found
-            assert!(msg.contains("World")); // Should show what was expected
-        } else {
-            panic!("Expected Parse error");
-        }
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("while read line; do echo \"start $line\"; sleep 0.2; echo \"done $line\"; done");
+        harness.spawn(cmd)?;
 
+        harness.wait_until_idle(Duration::from_millis(200))?;
+        harness.send_text("one\n")?;
+        harness.wait_until_idle(Duration::from_millis(500))?;
+        assert!(harness.screen_contents().contains("done one"));
+
+        harness.send_text("two\n")?;
+        harness.wait_until_idle(Duration::from_millis(500))?;
+        assert!(harness.screen_contents().contains("done two"));
         Ok(())
     }
 
     #[test]
-    fn test_assert_text_at_position_out_of_bounds() {
-        let harness = TuiTestHarness::new(80, 24).unwrap();
+    fn test_assert_cleared_on_start() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(3));
 
-        // Row out of bounds
-        let result = harness.assert_text_at_position("Test", 100, 0);
-        assert!(result.is_err());
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("printf '\\033[2J\\033[Hhello'; sleep 1");
+        harness.spawn(cmd)?;
 
-        // Column out of bounds
-        let result = harness.assert_text_at_position("Test", 0, 100);
-        assert!(result.is_err());
+        harness.assert_cleared_on_start()
     }
 
     #[test]
-    fn test_assert_text_within_bounds_success() -> Result<()> {
-        use crate::screen::Rect;
-        let mut harness = TuiTestHarness::new(80, 24)?;
-
-        // Feed text in a specific area
-        harness.state_mut().feed(b"\x1b[10;40HPreview");
+    fn test_assert_cleared_on_start_fails_without_clear() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_millis(500));
 
-        // Should find text within the preview area
-        let preview_area = Rect::new(35, 5, 40, 20);
-        harness.assert_text_within_bounds("Preview", preview_area)?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo hello; sleep 1");
+        harness.spawn(cmd)?;
 
+        match harness.assert_cleared_on_start() {
+            Err(TermTestError::Parse(msg)) => assert!(msg.contains("did not clear the screen")),
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
         Ok(())
     }
 
     #[test]
-    fn test_assert_text_within_bounds_failure() -> Result<()> {
-        use crate::screen::Rect;
-        let mut harness = TuiTestHarness::new(80, 24)?;
-
-        // Feed text outside the area
-        harness.state_mut().feed(b"\x1b[1;1HOutside");
-
-        // Should fail when text is not in the specified area
-        let preview_area = Rect::new(40, 10, 30, 10);
-        let result = harness.assert_text_within_bounds("Outside", preview_area);
-        assert!(result.is_err());
+    fn test_did_scroll_up_covers_whole_update() -> Result<()> {
+        // A tiny read buffer splits the output into many chunks per update
+        let mut harness = TuiTestHarness::builder()
+            .with_size(20, 3)
+            .with_timeout(Duration::from_secs(3))
+            .with_buffer_size(4)
+            .build()?;
 
-        if let Err(TermTestError::Parse(msg)) = result {
-            assert!(msg.contains("not found within bounds"));
-        } else {
-            panic!("Expected Parse error");
-        }
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("printf 'one\\r\\ntwo\\r\\nthree\\r\\nfour\\r\\ndone'; sleep 1");
+        harness.spawn(cmd)?;
+        harness.wait_for_text("done")?;
+        assert!(harness.state().did_scroll_up());
 
+        // The next update without new output starts over
+        harness.update_state()?;
+        assert!(!harness.state().did_scroll_up());
         Ok(())
     }
 
     #[test]
-    fn test_assert_text_within_bounds_tab_bar() -> Result<()> {
-        use crate::screen::Rect;
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_assert_survives_input() -> Result<()> {
+        let inputs: [&[u8]; 6] = [b"\x1b[A", b"\x1bOP", b"\x03", b"\x04", b"q\r", b"\xff\xfe"];
 
-        // Simulate tab bar at bottom
-        harness.state_mut().feed(b"\x1b[23;1HTab 1 | Tab 2 | Tab 3");
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(3));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("stty raw -echo; printf ready; cat > /dev/null");
+        harness.spawn(cmd)?;
+        harness.wait_for_text("ready")?;
 
-        // Define tab bar area (last 2 rows)
-        let tab_bar_area = Rect::new(0, 22, 80, 2);
+        harness.assert_survives_input(&inputs, Duration::from_millis(300))?;
 
-        // Should find tabs in the area
-        harness.assert_text_within_bounds("Tab 1", tab_bar_area)?;
-        harness.assert_text_within_bounds("Tab 2", tab_bar_area)?;
-        harness.assert_text_within_bounds("Tab 3", tab_bar_area)?;
+        // An app that echoes input verbatim leaves caret-notation garbage
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(3));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("stty raw -echo; printf ready; cat -v");
+        harness.spawn(cmd)?;
+        harness.wait_for_text("ready")?;
 
+        match harness.assert_survives_input(&inputs, Duration::from_millis(300)) {
+            Err(TermTestError::Parse(msg)) => assert!(msg.contains("Literal escape")),
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
         Ok(())
     }
 
     #[test]
-    fn test_assert_no_overlap_success() -> Result<()> {
-        use crate::screen::Rect;
-        let harness = TuiTestHarness::new(80, 24)?;
-
-        // Non-overlapping rectangles
-        let sidebar = Rect::new(0, 0, 20, 24);
-        let preview = Rect::new(20, 0, 60, 24);
+    fn test_wait_for_lines_paced() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_timeout(Duration::from_secs(5))
+            .with_poll_interval(Duration::from_millis(20));
 
-        harness.assert_no_overlap(sidebar, preview)?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("for i in 1 2 3 4 5; do echo line $i; sleep 0.1; done; sleep 1");
+        harness.spawn(cmd)?;
 
+        harness.wait_for_lines_paced(5, Duration::from_secs(1))?;
+        assert!(harness.screen_contents().contains("line 5"));
         Ok(())
     }
 
     #[test]
-    fn test_assert_no_overlap_failure() -> Result<()> {
-        use crate::screen::Rect;
-        let harness = TuiTestHarness::new(80, 24)?;
-
-        // Overlapping rectangles
-        let rect1 = Rect::new(10, 10, 20, 20);
-        let rect2 = Rect::new(20, 20, 20, 20);
+    fn test_wait_for_lines_paced_detects_stall() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_timeout(Duration::from_secs(5))
+            .with_poll_interval(Duration::from_millis(20));
 
-        let result = harness.assert_no_overlap(rect1, rect2);
-        assert!(result.is_err());
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo first; sleep 2; echo second; sleep 1");
+        harness.spawn(cmd)?;
 
-        if let Err(TermTestError::Parse(msg)) = result {
-            assert!(msg.contains("overlap"));
-        } else {
-            panic!("Expected Parse error");
+        match harness.wait_for_lines_paced(2, Duration::from_millis(700)) {
+            Err(TermTestError::Parse(msg)) => assert!(msg.contains("Line 2")),
+            other => panic!("Expected Parse error, got {:?}", other),
         }
-
         Ok(())
     }
 
     #[test]
-    fn test_assert_aligned_horizontal_success() -> Result<()> {
-        use crate::screen::Rect;
-        let harness = TuiTestHarness::new(80, 24)?;
+    fn test_send_paste_bracketed_multiline() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(3));
 
-        // Buttons on same row
-        let button1 = Rect::new(10, 20, 15, 3);
-        let button2 = Rect::new(30, 20, 15, 3);
+        // Raw mode so the paste is read byte for byte; `cat -v` makes the
+        // bracket sequences visible. The paste is 6 + 12 + 6 = 24 bytes.
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("stty raw -echo; printf '\\033[?2004hready\\r\\n'; head -c 24 | cat -v; sleep 1");
+        harness.spawn(cmd)?;
+        harness.wait_for_text("ready")?;
 
-        harness.assert_aligned(button1, button2, Axis::Horizontal)?;
+        harness.send_paste("first\nsecond")?;
 
+        match harness.wait_for_text("second^[[201~") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        let contents = harness.screen_contents();
+        assert!(contents.contains("^[[200~first"), "{}", contents);
+        assert!(contents.contains("second^[[201~"), "{}", contents);
+        assert_eq!(contents.matches("^[[200~").count(), 1);
         Ok(())
     }
 
     #[test]
-    fn test_assert_aligned_horizontal_failure() -> Result<()> {
-        use crate::screen::Rect;
-        let harness = TuiTestHarness::new(80, 24)?;
-
-        // Buttons on different rows
-        let button1 = Rect::new(10, 20, 15, 3);
-        let button2 = Rect::new(30, 21, 15, 3);
+    fn test_output_without_trailing_newline() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
 
-        let result = harness.assert_aligned(button1, button2, Axis::Horizontal);
-        assert!(result.is_err());
+        let mut cmd = CommandBuilder::new("printf");
+        cmd.arg("a\\nb");
+        harness.spawn(cmd)?;
 
-        if let Err(TermTestError::Parse(msg)) = result {
-            assert!(msg.contains("not horizontally aligned"));
-            assert!(msg.contains("different Y coordinates"));
-        } else {
-            panic!("Expected Parse error");
+        match harness.wait_for_text("b") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
         }
-
+        assert_eq!(harness.state().lines(), vec!["a", "b"]);
+        assert_eq!(harness.state().content_height(), 2);
         Ok(())
     }
 
     #[test]
-    fn test_assert_aligned_vertical_success() -> Result<()> {
-        use crate::screen::Rect;
-        let harness = TuiTestHarness::new(80, 24)?;
+    fn test_assert_frame_sequence() -> Result<()> {
+        let mut harness =
+            TuiTestHarness::new(80, 24)?.with_poll_interval(Duration::from_millis(20));
 
-        // Panels in same column
-        let panel1 = Rect::new(40, 0, 40, 10);
-        let panel2 = Rect::new(40, 10, 40, 14);
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg(
+            "printf 'frame-1'; sleep 0.3; printf '\\rframe-2'; sleep 0.3; \
+             printf '\\rframe-3'; sleep 0.3",
+        );
+        harness.spawn(cmd)?;
 
-        harness.assert_aligned(panel1, panel2, Axis::Vertical)?;
+        harness
+            .assert_frame_sequence(&["frame-1", "frame-2", "frame-3"], Duration::from_secs(2))?;
 
+        // The animation is over, so an extra frame never appears
+        let result = harness.assert_frame_sequence(&["frame-4"], Duration::from_millis(200));
+        assert!(matches!(
+            result,
+            Err(TermTestError::Timeout { .. }) | Err(TermTestError::ProcessExited)
+        ));
         Ok(())
     }
 
     #[test]
-    fn test_assert_aligned_vertical_failure() -> Result<()> {
-        use crate::screen::Rect;
-        let harness = TuiTestHarness::new(80, 24)?;
-
-        // Panels in different columns
-        let panel1 = Rect::new(40, 0, 40, 10);
-        let panel2 = Rect::new(41, 10, 39, 14);
+    fn test_assert_size_reported_as() -> Result<()> {
+        let size_regex = r"(?P<rows>\d+) (?P<cols>\d+)";
 
-        let result = harness.assert_aligned(panel1, panel2, Axis::Vertical);
-        assert!(result.is_err());
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(2));
+        let mut cmd = CommandBuilder::new("stty");
+        cmd.arg("size");
+        harness.spawn(cmd)?;
+        harness.assert_size_reported_as((80, 24), size_regex)?;
 
-        if let Err(TermTestError::Parse(msg)) = result {
-            assert!(msg.contains("not vertically aligned"));
-            assert!(msg.contains("different X coordinates"));
-        } else {
-            panic!("Expected Parse error");
+        let mut harness = TuiTestHarness::new(100, 30)?.with_timeout(Duration::from_secs(2));
+        let mut cmd = CommandBuilder::new("stty");
+        cmd.arg("size");
+        harness.spawn(cmd)?;
+        let result = harness.assert_size_reported_as((80, 24), size_regex);
+        match result {
+            Err(TermTestError::Parse(msg)) => assert!(msg.contains("100x30")),
+            other => panic!("Expected Parse error, got {:?}", other),
         }
 
         Ok(())
     }
 
     #[test]
-    fn test_complex_layout_assertions() -> Result<()> {
-        use crate::screen::Rect;
+    fn test_last_command() -> Result<()> {
         let mut harness = TuiTestHarness::new(80, 24)?;
+        assert_eq!(harness.last_command(), None);
 
-        // Simulate complex layout
-        harness.state_mut().feed(b"\x1b[1;1HHeader");
-        harness.state_mut().feed(b"\x1b[3;1HSidebar");
-        harness.state_mut().feed(b"\x1b[3;21HContent");
-        harness.state_mut().feed(b"\x1b[23;1HStatus Bar");
+        let mut cmd = CommandBuilder::new("echo");
+        cmd.arg("-n");
+        cmd.arg("hello world");
+        harness.spawn(cmd)?;
 
-        // Define areas
-        let header = Rect::new(0, 0, 80, 2);
-        let sidebar = Rect::new(0, 2, 20, 20);
-        let content = Rect::new(20, 2, 60, 20);
-        let status_bar = Rect::new(0, 22, 80, 2);
+        assert_eq!(harness.last_command().as_deref(), Some(r#"echo -n "hello world""#));
+        Ok(())
+    }
 
-        // Assert text in areas
-        harness.assert_text_within_bounds("Header", header)?;
-        harness.assert_text_within_bounds("Sidebar", sidebar)?;
-        harness.assert_text_within_bounds("Content", content)?;
-        harness.assert_text_within_bounds("Status Bar", status_bar)?;
+    #[test]
+    fn test_from_config() -> Result<()> {
+        let config = HarnessConfig {
+            size: (100, 30),
+            timeout: Duration::from_secs(2),
+            poll_interval: Duration::from_millis(25),
+            env: vec![("RATATUI_TESTLIB_CONFIG_VAR".into(), "shared".into())],
+            ..HarnessConfig::default()
+        };
 
-        // Assert no overlap
-        harness.assert_no_overlap(sidebar, content)?;
-        harness.assert_no_overlap(header, status_bar)?;
+        let first = TuiTestHarness::from_config(config.clone())?;
+        let mut second = TuiTestHarness::from_config(config)?;
 
-        // Assert alignment
-        harness.assert_aligned(sidebar, content, Axis::Horizontal)?;
+        for harness in [&first, &second] {
+            assert_eq!(harness.state().size(), (100, 30));
+            assert_eq!(harness.timeout, Duration::from_secs(2));
+            assert_eq!(harness.poll_interval, Duration::from_millis(25));
+            assert_eq!(harness.buffer_size, DEFAULT_BUFFER_SIZE);
+        }
 
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo \"var=$RATATUI_TESTLIB_CONFIG_VAR\"");
+        second.spawn(cmd)?;
+        match second.wait_for_text("var=shared") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(second.screen_contents().contains("var=shared"));
         Ok(())
     }
 
     #[test]
-    fn test_multiline_text_in_area() -> Result<()> {
-        use crate::screen::Rect;
-        let mut harness = TuiTestHarness::new(80, 24)?;
-
-        // Feed multiline content
-        harness.state_mut().feed(b"\x1b[10;40HLine 1");
-        harness.state_mut().feed(b"\x1b[11;40HLine 2");
-        harness.state_mut().feed(b"\x1b[12;40HLine 3");
-
-        let content_area = Rect::new(35, 5, 40, 20);
+    fn test_separate_stderr() -> Result<()> {
+        let mut harness = TuiTestHarness::builder()
+            .with_separate_stderr()
+            .with_timeout(Duration::from_secs(3))
+            .build()?;
 
-        // Should find each line
-        harness.assert_text_within_bounds("Line 1", content_area)?;
-        harness.assert_text_within_bounds("Line 2", content_area)?;
-        harness.assert_text_within_bounds("Line 3", content_area)?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo to-stderr >&2; echo to-stdout; sleep 1");
+        harness.spawn(cmd)?;
 
+        match harness.wait_for_text("to-stdout") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(!harness.screen_contents().contains("to-stderr"));
+        assert_eq!(harness.stderr_contents(), "to-stderr\n");
+        assert_eq!(
+            harness.last_command().as_deref(),
+            Some("sh -c \"echo to-stderr >&2; echo to-stdout; sleep 1\"")
+        );
         Ok(())
     }
 
     #[test]
-    fn test_edge_case_text_at_screen_edge() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_with_temp_home() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_timeout(Duration::from_secs(3))
+            .with_temp_home();
+        assert!(harness.temp_home().is_none());
 
-        // Text at top-left corner
-        harness.state_mut().feed(b"\x1b[1;1HCorner");
-        harness.assert_text_at_position("Corner", 0, 0)?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo \"home=$(basename \"$HOME\")\"; sleep 1");
+        harness.spawn(cmd)?;
 
-        // Text near bottom-right corner
-        harness.state_mut().feed(b"\x1b[24;75HEnd");
-        harness.assert_text_at_position("End", 23, 74)?;
+        let home = harness
+            .temp_home()
+            .expect("temp home created")
+            .to_path_buf();
+        assert!(home.is_dir());
+        assert_ne!(Some(home.as_os_str()), std::env::var_os("HOME").as_deref());
+
+        // Only the directory name is echoed, so long temp paths don't wrap
+        let expected = format!("home={}", home.file_name().unwrap().to_string_lossy());
+        match harness.wait_for_text(&expected) {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(harness.screen_contents().contains(&expected));
 
+        drop(harness);
+        assert!(!home.exists());
         Ok(())
     }
 
     #[test]
-    fn test_text_spanning_multiple_lines() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_with_local_echo() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_timeout(Duration::from_secs(3))
+            .with_local_echo(true);
 
-        // Text that wraps or is on multiple lines
-        harness.state_mut().feed(b"\x1b[5;10HFirst");
-        harness.state_mut().feed(b"\x1b[6;10HSecond");
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("stty raw -echo; printf 'login: '; head -c 6 > /dev/null; sleep 1");
+        harness.spawn(cmd)?;
+        harness.wait_for_text("login:")?;
 
-        // Each line should be found independently
-        harness.assert_text_at_position("First", 4, 9)?;
-        harness.assert_text_at_position("Second", 5, 9)?;
+        harness.send_text("hunter")?;
+        assert!(harness.screen_contents().contains("login: hunter"));
+
+        // Without local echo the raw-mode app shows nothing
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(3));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("stty raw -echo; printf 'login: '; head -c 6 > /dev/null; sleep 1");
+        harness.spawn(cmd)?;
+        harness.wait_for_text("login:")?;
 
+        harness.send_text("hunter")?;
+        assert!(!harness.screen_contents().contains("hunter"));
         Ok(())
     }
 
     #[test]
-    fn test_memory_usage() -> Result<()> {
-        let harness = TuiTestHarness::new(80, 24)?;
+    fn test_cursor_report_hint_without_auto_responses() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_millis(500));
 
-        let memory = harness.memory_usage();
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("stty raw -echo; printf '\\033[6n'; head -c 1 > /dev/null; echo drawn; sleep 1");
+        harness.spawn(cmd)?;
 
-        // Should have non-zero memory for screen buffer
-        assert!(memory.current_bytes > 0);
-        assert_eq!(memory.current_bytes, memory.peak_bytes);
+        assert!(harness.wait_for_text("drawn").is_err());
+        assert_eq!(harness.hints().len(), 1);
+        assert!(harness.hints()[0].contains("with_auto_responses"));
+        Ok(())
+    }
 
-        // Basic sanity check: 80x24 screen should be reasonable size
-        // Each Cell is at least char (4 bytes) + options + bools
-        let min_expected = 80 * 24 * 4; // Very conservative estimate
-        assert!(memory.current_bytes >= min_expected);
+    #[test]
+    fn test_auto_responses_answer_cursor_report() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_timeout(Duration::from_secs(3))
+            .with_auto_responses(true);
+
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg(
+            "stty raw -echo; printf '\\033[5;10H\\033[6n'; \
+             reply=$(dd bs=1 count=7 2>/dev/null | tr -d '\\033'); \
+             printf '\\r\\ngot %s' \"$reply\"; sleep 1",
+        );
+        harness.spawn(cmd)?;
+
+        match harness.wait_for_text("got [5;10R") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(harness.screen_contents().contains("got [5;10R"));
+        assert!(harness.hints().is_empty());
+        Ok(())
+    }
 
+    #[test]
+    fn test_terminal_identity_enables_sixel() -> Result<()> {
+        // Enables Sixel only if attribute 4 is in the Primary DA reply
+        let script = "stty raw -echo; printf '\\033[c'; reply=''; \
+                      while :; do ch=$(dd bs=1 count=1 2>/dev/null); \
+                      reply=\"$reply$ch\"; [ \"$ch\" = c ] && break; done; \
+                      case \"$reply\" in *';4;'*|*';4c') echo 'Sixel: on';; \
+                      *) echo 'Sixel: off';; esac; sleep 1";
+
+        for (identity, expected) in [
+            (TerminalIdentity::vt340(), "Sixel: on"),
+            (TerminalIdentity::xterm(), "Sixel: off"),
+        ] {
+            let mut harness = TuiTestHarness::new(80, 24)?
+                .with_timeout(Duration::from_secs(3))
+                .with_terminal_identity(identity);
+
+            let mut cmd = CommandBuilder::new("sh");
+            cmd.arg("-c");
+            cmd.arg(script);
+            harness.spawn(cmd)?;
+
+            match harness.wait_for_text(expected) {
+                Ok(()) | Err(TermTestError::ProcessExited) => {}
+                Err(e) => return Err(e),
+            }
+            assert!(harness.screen_contents().contains(expected));
+            assert!(harness.hints().is_empty());
+        }
         Ok(())
     }
 
     #[test]
-    fn test_memory_usage_with_different_sizes() -> Result<()> {
-        let small = TuiTestHarness::new(20, 10)?;
-        let large = TuiTestHarness::new(200, 100)?;
-
-        let small_memory = small.memory_usage();
-        let large_memory = large.memory_usage();
+    fn test_send_key_sequence() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(3));
 
-        // Larger terminal should use more memory
-        assert!(large_memory.current_bytes > small_memory.current_bytes);
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg(
+            "stty raw -echo; echo ready; \
+             keys=$(dd bs=1 count=5 2>/dev/null | od -An -c | tr -d ' '); \
+             printf 'keys:%s\\r\\n' \"$keys\"; sleep 1",
+        );
+        harness.spawn(cmd)?;
+        harness.wait_for_text("ready")?;
 
+        harness.send_key_sequence(&[KeyCode::Up, KeyCode::Char('x'), KeyCode::Tab])?;
+        match harness.wait_for_text("keys:033[Ax\\t") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(harness.screen_contents().contains("keys:033[Ax\\t"));
         Ok(())
     }
 
     #[test]
-    fn test_memory_usage_with_sixel() -> Result<()> {
-        use crate::screen::SixelRegion;
-
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_send_bytes_split() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(3));
 
-        // Get initial memory without Sixel
-        let initial_memory = harness.memory_usage();
-
-        // Add a mock Sixel region (simulate Sixel data)
-        let sixel_data = vec![0u8; 1000]; // 1KB of Sixel data
-        let region = SixelRegion {
-            start_row: 5,
-            start_col: 10,
-            width: 100,
-            height: 50,
-            data: sixel_data,
-        };
+        // Reads one three-byte key and prints it in od's escaped notation
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg(
+            "stty raw -echo; echo ready; \
+             key=$(dd bs=1 count=3 2>/dev/null | od -An -c | tr -d ' '); \
+             printf 'key:%s\\r\\n' \"$key\"; sleep 1",
+        );
+        harness.spawn(cmd)?;
+        harness.wait_for_text("ready")?;
 
-        // Manually add the region to the state for testing
-        // Note: In real usage, this would come from parsing terminal output
-        harness.state_mut().sixel_regions_mut().push(region);
+        harness.send_bytes_split(&[b"\x1b", b"[A"], Duration::from_millis(100))?;
+        match harness.wait_for_text("key:033[A") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(harness.screen_contents().contains("key:033[A"));
+        Ok(())
+    }
 
-        // Get memory with Sixel
-        let with_sixel_memory = harness.memory_usage();
+    #[test]
+    fn test_global_deadline() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_timeout(Duration::from_millis(400))
+            .with_global_deadline(Duration::from_millis(600));
 
-        // Memory should increase by approximately the size of the Sixel data
-        assert!(with_sixel_memory.current_bytes > initial_memory.current_bytes);
-        let increase = with_sixel_memory.current_bytes - initial_memory.current_bytes;
-        assert!(increase >= 1000); // Should be at least 1KB
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo started; sleep 10");
+        harness.spawn(cmd)?;
+        harness.wait_for_text("started")?;
+
+        // The first wait times out on its own; the second runs into the deadline
+        assert!(matches!(harness.wait_for_text("never"), Err(TermTestError::Timeout { .. })));
+        match harness.wait_for_text("never") {
+            Err(TermTestError::GlobalDeadlineExceeded { deadline_ms, dump }) => {
+                assert_eq!(deadline_ms, 600);
+                assert!(dump.contains("Command: sh"));
+                assert!(dump.contains("Screen state:\nstarted"));
+            }
+            other => panic!("Expected GlobalDeadlineExceeded, got {:?}", other),
+        }
 
+        assert!(!harness.is_running());
+        assert!(matches!(
+            harness.send_text("q"),
+            Err(TermTestError::GlobalDeadlineExceeded { .. })
+        ));
         Ok(())
     }
 
     #[test]
-    fn test_assert_memory_under_success() -> Result<()> {
-        let harness = TuiTestHarness::new(80, 24)?;
+    fn test_global_deadline_bounds_wait_exit() -> Result<()> {
+        let mut harness =
+            TuiTestHarness::new(80, 24)?.with_global_deadline(Duration::from_millis(300));
 
-        // Set a generous limit that should pass
-        harness.assert_memory_under(1_000_000)?; // 1 MB
+        let mut cmd = CommandBuilder::new("sleep");
+        cmd.arg("10");
+        harness.spawn(cmd)?;
 
+        let start = Instant::now();
+        assert!(matches!(harness.wait_exit(), Err(TermTestError::GlobalDeadlineExceeded { .. })));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(!harness.is_running());
         Ok(())
     }
 
     #[test]
-    fn test_assert_memory_under_failure() -> Result<()> {
-        let harness = TuiTestHarness::new(80, 24)?;
+    fn test_last_wait_polls() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_timeout(Duration::from_secs(5))
+            .with_poll_interval(Duration::from_millis(10));
+        assert_eq!(harness.last_wait_polls(), 0);
 
-        // Set a very small limit that should fail
-        let result = harness.assert_memory_under(100); // 100 bytes
+        harness.state_mut().feed(b"Ready");
+        harness.wait_for_text("Ready")?;
+        assert_eq!(harness.last_wait_polls(), 0);
 
-        assert!(result.is_err());
-        if let Err(e) = result {
-            let error_msg = format!("{}", e);
-            assert!(error_msg.contains("Memory usage exceeds limit"));
-            assert!(error_msg.contains("100 bytes (limit)"));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("sleep 0.3; echo Later; sleep 1");
+        harness.spawn(cmd)?;
+        match harness.wait_for_text("Later") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
         }
-
+        let polls = harness.last_wait_polls();
+        assert!(polls > 0 && polls < 100, "unexpected poll count {}", polls);
         Ok(())
     }
 
     #[test]
-    fn test_memory_results_summary_format() -> Result<()> {
-        let harness = TuiTestHarness::new(80, 24)?;
-        let memory = harness.memory_usage();
-        let summary = memory.summary();
+    fn test_wait_for_stable() -> Result<()> {
+        let mut harness = TuiTestHarness::new(40, 5)?
+            .with_timeout(Duration::from_secs(3))
+            .with_poll_interval(Duration::from_millis(10));
 
-        // Check that summary contains expected fields
-        assert!(summary.contains("Memory Usage"));
-        assert!(summary.contains("Current:"));
-        assert!(summary.contains("Peak:"));
-        assert!(summary.contains("bytes"));
-        assert!(summary.contains("KB"));
+        // Counts up for a while, then only moves the cursor around
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg(
+            "for i in 1 2 3 4 5; do printf '\\rstep %s' $i; sleep 0.1; done; \
+             while :; do printf '\\033[2;1H\\033[1;1H'; sleep 0.05; done",
+        );
+        harness.spawn(cmd)?;
+
+        let start = Instant::now();
+        harness.wait_for_stable(Duration::from_millis(300))?;
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        assert!(harness.screen_contents().contains("step 5"));
+
+        // A screen that never settles times out
+        let mut harness = TuiTestHarness::new(40, 5)?.with_timeout(Duration::from_millis(500));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("i=0; while :; do i=$((i+1)); printf '\\r%s' $i; sleep 0.05; done");
+        harness.spawn(cmd)?;
 
+        let result = harness.wait_for_stable(Duration::from_millis(300));
+        assert!(matches!(result, Err(TermTestError::Timeout { .. })));
         Ok(())
     }
 
     #[test]
-    fn test_memory_tracking_across_operations() -> Result<()> {
+    fn test_wait_for_exit() -> Result<()> {
         let mut harness = TuiTestHarness::new(80, 24)?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo bye; exit 3");
+        harness.spawn(cmd)?;
 
-        let initial = harness.memory_usage();
-
-        // Feed some data to the screen
-        harness.state_mut().feed(b"Hello, World!");
-        harness.state_mut().feed(b"\x1b[2J"); // Clear screen
-        harness.state_mut().feed(b"More text");
-
-        let after_operations = harness.memory_usage();
-
-        // Memory should remain roughly the same (screen buffer size doesn't change)
-        // Allow for small variations due to internal state
-        let diff = if after_operations.current_bytes > initial.current_bytes {
-            after_operations.current_bytes - initial.current_bytes
-        } else {
-            initial.current_bytes - after_operations.current_bytes
-        };
+        let status = harness.wait_for_exit(Duration::from_secs(5))?;
+        assert_eq!(status.exit_code(), 3);
+        assert!(harness.screen_contents().contains("bye"));
 
-        // Should be within 10% of original
-        assert!(diff < initial.current_bytes / 10);
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        let mut cmd = CommandBuilder::new("sleep");
+        cmd.arg("5");
+        harness.spawn(cmd)?;
 
+        let start = Instant::now();
+        let result = harness.wait_for_exit(Duration::from_millis(200));
+        assert!(matches!(result, Err(TermTestError::Timeout { timeout_ms: 200 })));
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(harness.is_running());
         Ok(())
     }
 
     #[test]
-    fn test_event_delay_default() {
-        let harness = TuiTestHarness::new(80, 24).unwrap();
-        // Default should be zero
-        assert_eq!(harness.event_delay(), Duration::ZERO);
+    fn test_wait_for_exit_no_process() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?;
+        let result = harness.wait_for_exit(Duration::from_millis(100));
+        assert!(matches!(result, Err(TermTestError::NoProcessRunning)));
+        Ok(())
     }
 
     #[test]
-    fn test_set_event_delay() {
-        let mut harness = TuiTestHarness::new(80, 24).unwrap();
+    fn test_wait_for_text_absent() -> Result<()> {
+        let mut harness = TuiTestHarness::new(40, 5)?.with_timeout(Duration::from_secs(3));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("printf 'Loading...'; sleep 0.3; printf '\\r\\033[KDone'; sleep 5");
+        harness.spawn(cmd)?;
 
-        // Set a custom delay
-        harness.set_event_delay(Duration::from_millis(100));
-        assert_eq!(harness.event_delay(), Duration::from_millis(100));
+        harness.wait_for_text("Loading...")?;
+        harness.wait_for_text_absent("Loading...")?;
+        assert!(harness.screen_contents().contains("Done"));
 
-        // Change it
-        harness.set_event_delay(Duration::from_millis(200));
-        assert_eq!(harness.event_delay(), Duration::from_millis(200));
+        // Still on screen when the deadline hits
+        let mut harness = TuiTestHarness::new(40, 5)?.with_timeout(Duration::from_millis(300));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("printf 'Loading...'; sleep 5");
+        harness.spawn(cmd)?;
+        harness.wait_for_text("Loading...")?;
 
-        // Reset to zero
-        harness.set_event_delay(Duration::ZERO);
-        assert_eq!(harness.event_delay(), Duration::ZERO);
+        let result = harness.wait_for_text_absent("Loading...");
+        assert!(matches!(result, Err(TermTestError::Timeout { .. })));
+        Ok(())
     }
 
     #[test]
-    fn test_advance_time() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_wait_for_any() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(3));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("sleep 0.2; echo 'Error: disk full'; sleep 5");
+        harness.spawn(cmd)?;
 
-        // advance_time should succeed
-        let start = Instant::now();
-        harness.advance_time(Duration::from_millis(100))?;
-        let elapsed = start.elapsed();
+        assert_eq!(harness.wait_for_any(&["OK", "Error"])?, 1);
 
-        // Should have actually waited (with some tolerance for scheduling)
-        assert!(elapsed >= Duration::from_millis(95));
-        assert!(elapsed <= Duration::from_millis(200));
+        // The lowest index wins when several are present
+        harness.state_mut().feed(b"\r\nOK");
+        assert_eq!(harness.wait_for_any(&["Error", "OK"])?, 0);
+
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_millis(300));
+        let mut cmd = CommandBuilder::new("sleep");
+        cmd.arg("5");
+        harness.spawn(cmd)?;
 
+        let result = harness.wait_for_any(&["OK", "Error"]);
+        assert!(matches!(result, Err(TermTestError::Timeout { .. })));
         Ok(())
     }
 
     #[test]
-    fn test_press_key_repeat() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_wait_for_all() -> Result<()> {
+        let mut harness = TuiTestHarness::new(40, 5)?.with_timeout(Duration::from_secs(3));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo CPU; sleep 0.1; echo Memory; sleep 0.1; echo Network; sleep 5");
+        harness.spawn(cmd)?;
 
-        // Test that press_key_repeat sends multiple keys
-        let start = Instant::now();
-        harness.press_key_repeat('a', 3, Duration::from_millis(50))?;
-        let elapsed = start.elapsed();
+        harness.wait_for_all(&["CPU", "Memory", "Network"])?;
+        let contents = harness.screen_contents();
+        assert!(contents.contains("CPU") && contents.contains("Network"));
 
-        // Should take at least 3 * 50ms = 150ms
-        // (plus the default 50ms delay after each key from send_key_event)
-        // Total: ~300ms minimum
-        assert!(elapsed >= Duration::from_millis(250));
+        // "CPU" is cleared before "Network" shows, so they never coexist
+        let mut harness = TuiTestHarness::new(40, 5)?.with_timeout(Duration::from_millis(500));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo CPU; sleep 0.1; printf '\\033[2J\\033[H'; echo Network; sleep 5");
+        harness.spawn(cmd)?;
 
+        harness.wait_for_text("Network")?;
+        let result = harness.wait_for_all(&["CPU", "Network"]);
+        assert!(matches!(result, Err(TermTestError::Timeout { .. })));
         Ok(())
     }
 
     #[test]
-    fn test_event_delay_affects_timing() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_wait_for_text_in_row() -> Result<()> {
+        let mut harness = TuiTestHarness::new(40, 5)?.with_timeout(Duration::from_secs(1));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("printf 'Saved drafts\\r\\n'; sleep 0.3; printf '\\033[5;1HSaved'; sleep 5");
+        harness.spawn(cmd)?;
 
-        // With custom delay
-        harness.set_event_delay(Duration::from_millis(100));
+        harness.wait_for_text("drafts")?;
+        assert!(harness.state().row_contents(4).trim().is_empty());
 
-        let start = Instant::now();
-        // Send 3 characters
-        harness.send_keys("abc")?;
-        let elapsed = start.elapsed();
+        // "Saved" on row 0 does not satisfy a wait on the status line
+        harness.wait_for_text_in_row(4, "Saved")?;
+        assert!(harness.state().row_contents(4).starts_with("Saved"));
 
-        // Each key should take 100ms, so 3 keys = ~300ms
-        assert!(elapsed >= Duration::from_millis(250));
+        let result = harness.wait_for_text_in_row(2, "Saved");
+        assert!(matches!(result, Err(TermTestError::Timeout { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_text_ignore_case() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(3));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("sleep 0.2; echo 'Build FAILED'; sleep 1");
+        harness.spawn(cmd)?;
 
+        harness.wait_for_text_ignore_case("build failed")?;
+        assert!(!harness.screen_contents().contains("build failed"));
+
+        let result = harness.wait_for_text_timeout("build failed", Duration::from_millis(100));
+        assert!(matches!(result, Err(TermTestError::Timeout { .. })));
         Ok(())
     }
 
     #[test]
-    fn test_press_key_repeat_with_zero_interval() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_wait_for_text_expecting() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(10));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo 'ERROR: config missing'; sleep 5; echo Ready");
+        harness.spawn(cmd)?;
 
-        // Should work with zero interval (only default key delay applies)
         let start = Instant::now();
-        harness.press_key_repeat('x', 2, Duration::ZERO)?;
-        let elapsed = start.elapsed();
-
-        // Should still take time due to the default 50ms delay in send_key_event
-        // 2 keys * 50ms = ~100ms minimum
-        assert!(elapsed >= Duration::from_millis(80));
+        match harness.wait_for_text_expecting("Ready", "ERROR") {
+            Err(TermTestError::Parse(msg)) => {
+                assert!(msg.contains("Unexpected text 'ERROR'"));
+                assert!(msg.contains("config missing"));
+            }
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+        assert!(start.elapsed() < Duration::from_secs(4));
 
+        let mut harness = TuiTestHarness::new(80, 24)?.with_timeout(Duration::from_secs(5));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo Ready; sleep 1");
+        harness.spawn(cmd)?;
+        match harness.wait_for_text_expecting("Ready", "ERROR") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(harness.screen_contents().contains("Ready"));
         Ok(())
     }
 
     #[test]
-    fn test_timing_combination() -> Result<()> {
-        let mut harness = TuiTestHarness::new(80, 24)?;
+    fn test_eof_timeout_reports_closed_output() -> Result<()> {
+        let mut harness = TuiTestHarness::new(80, 24)?
+            .with_timeout(Duration::from_secs(10))
+            .with_eof_timeout(Duration::from_millis(300));
 
-        // Test combining different timing methods
-        harness.set_event_delay(Duration::from_millis(50));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo started; exec >&- 2>&- <&-; sleep 5");
+        harness.spawn(cmd)?;
 
         let start = Instant::now();
+        match harness.wait_for_text("never printed") {
+            Err(TermTestError::OutputClosed { timeout_ms }) => assert_eq!(timeout_ms, 300),
+            other => panic!("Expected OutputClosed, got {:?}", other),
+        }
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(harness.screen_contents().contains("started"));
+        Ok(())
+    }
 
-        // Send a key (50ms delay)
-        harness.send_key(KeyCode::Char('a'))?;
+    #[test]
+    fn test_builder_with_unset_env() -> Result<()> {
+        let mut harness = TuiTestHarness::builder()
+            .with_unset_env(&["RATATUI_TESTLIB_UNSET_ME"])
+            .with_timeout(Duration::from_secs(2))
+            .build()?;
 
-        // Advance time (100ms)
-        harness.advance_time(Duration::from_millis(100))?;
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo \"value=[${RATATUI_TESTLIB_UNSET_ME-absent}]\"");
+        cmd.env("RATATUI_TESTLIB_UNSET_ME", "present");
+        harness.spawn(cmd)?;
 
-        // Send repeated keys (2 * 50ms interval + 2 * 50ms event delay = 200ms)
-        harness.press_key_repeat('b', 2, Duration::from_millis(50))?;
+        match harness.wait_for_text("value=[") {
+            Ok(()) | Err(TermTestError::ProcessExited) => {}
+            Err(e) => return Err(e),
+        }
+        assert!(harness.screen_contents().contains("value=[absent]"));
+        Ok(())
+    }
 
-        let elapsed = start.elapsed();
+    #[test]
+    fn test_readable_poll_strategy_wakes_on_output() -> Result<()> {
+        // The poll interval is far longer than the output delay, so a
+        // sleeping harness would not notice the text until it elapsed.
+        let mut harness = TuiTestHarness::builder()
+            .with_poll_interval(Duration::from_secs(3))
+            .with_poll_strategy(PollStrategy::Readable)
+            .with_timeout(Duration::from_secs(10))
+            .build()?;
 
-        // Total: 50 + 100 + 200 = 350ms minimum
-        assert!(elapsed >= Duration::from_millis(300));
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("sleep 0.3; echo ready; sleep 2");
+        harness.spawn(cmd)?;
+
+        let start = Instant::now();
+        harness.wait_for_text("ready")?;
+        assert!(
+            start.elapsed() < Duration::from_secs(2),
+            "wait_for_text took {:?} with the readable strategy",
+            start.elapsed()
+        );
+        Ok(())
+    }
 
+    #[test]
+    fn test_max_output_bytes_stops_runaway_output() -> Result<()> {
+        let mut harness = TuiTestHarness::builder()
+            .with_max_output_bytes(16 * 1024)
+            .with_timeout(Duration::from_secs(5))
+            .build()?;
+        harness.spawn(CommandBuilder::new("yes"))?;
+
+        match harness.wait_for_text("never printed") {
+            Err(TermTestError::OutputLimitExceeded { limit_bytes, read_bytes }) => {
+                assert_eq!(limit_bytes, 16 * 1024);
+                assert!(read_bytes > limit_bytes);
+            }
+            other => panic!("Expected OutputLimitExceeded, got {:?}", other),
+        }
         Ok(())
     }
 }
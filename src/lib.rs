@@ -134,6 +134,7 @@
 //! - `ratatui-helpers`: Enable Ratatui-specific test helpers
 //! - `sixel`: Enable Sixel graphics position tracking and testing
 //! - `snapshot-insta`: Enable snapshot testing with `insta`
+//! - `snapshot-json`: Enable JSON serialization of [`GridSnapshot`] for stored baselines
 //! - `headless`: Enable headless mode for CI/CD (no display server required)
 //! - `shared-state`: Enable memory-mapped shared state access for testing
 //! - `mvp`: Enable all MVP features (recommended for dgx-pixels)
@@ -187,6 +188,7 @@ mod error;
 pub mod events;
 pub mod golden;
 mod harness;
+mod macros;
 pub mod navigation;
 pub mod parallel;
 mod pty;
@@ -215,7 +217,9 @@ pub use async_harness::{AsyncTuiTestHarness, WaitResult};
 pub use error::{Result, TermTestError};
 pub use events::{KeyCode, KeyEvent, Modifiers, MouseButton, MouseEvent, ScrollDirection};
 pub use golden::{GoldenFile, GoldenMetadata};
-pub use harness::{Axis, MemoryResults, RecordedEvent, TuiTestHarness};
+pub use harness::{
+    Axis, HarnessConfig, MemoryResults, PollStrategy, RecordedEvent, ScriptAction, TuiTestHarness,
+};
 pub use navigation::{
     FocusInfo, HintElementType, HintLabel, NavMode, NavigationTestExt, PromptMarker,
     PromptMarkerType,
@@ -224,9 +228,13 @@ pub use parallel::{
     IsolatedTerminal, PoolConfig, PoolStats, TerminalGuard, TerminalId, TerminalPool, TestContext,
 };
 pub use pty::TestTerminal;
-pub use screen::{Cell, GridSnapshot, ITerm2Region, KittyRegion, Rect, ScreenState, SixelRegion};
+pub use screen::{
+    Cell, CellAttributes, CellDiff, CellStyle, Color, CursorShape, GridSnapshot, Hyperlink,
+    ITerm2Region, ImageMarker, ImageProtocol, KittyImage, KittyRegion, MouseTrackingMode, Rect,
+    ScreenState, SixelRegion, StyledRun,
+};
 pub use terminal_profiles::{
-    ColorDepth, Feature, MouseProtocol, TerminalCapabilities, TerminalProfile,
+    ColorDepth, Feature, MouseProtocol, TerminalCapabilities, TerminalIdentity, TerminalProfile,
 };
 
 /// Re-export of [`ScreenState`] for clarity in stream-based parsing contexts.
@@ -256,4 +264,4 @@ pub use graphics::{GraphicsCapture, GraphicsProtocol, GraphicsRegion};
 // Re-export commonly used types for convenience
 pub use portable_pty::CommandBuilder;
 #[cfg(feature = "sixel")]
-pub use sixel::{SixelCapture, SixelSequence};
+pub use sixel::{SixelCapture, SixelDiff, SixelSequence, SixelSequenceBuilder};
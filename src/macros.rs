@@ -0,0 +1,218 @@
+//! Assertion macros for terminal screen state.
+//!
+//! These wrap the common checks on a [`TuiTestHarness`](crate::TuiTestHarness)
+//! and panic with the full screen contents on failure, so a failing `#[test]`
+//! shows what was actually on screen.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use ratatui_testlib::{
+//!     assert_cursor_at, assert_screen_contains, assert_screen_matches, TuiTestHarness,
+//! };
+//!
+//! # fn test() -> ratatui_testlib::Result<()> {
+//! let mut harness = TuiTestHarness::new(80, 24)?;
+//! // ... spawn app and wait ...
+//!
+//! assert_screen_contains!(harness, "Welcome");
+//! assert_screen_matches!(harness, r"\d+ items");
+//! assert_cursor_at!(harness, 2, 0);
+//! # Ok(())
+//! # }
+//! ```
+
+/// Asserts that the screen contains the given text.
+///
+/// Takes a [`TuiTestHarness`](crate::TuiTestHarness) and the text to look for,
+/// optionally followed by a custom message in `format!` syntax. On failure the
+/// panic message includes the full screen contents.
+///
+/// # Example
+///
+/// ```rust
+/// use ratatui_testlib::{assert_screen_contains, TuiTestHarness};
+///
+/// # fn test() -> ratatui_testlib::Result<()> {
+/// let mut harness = TuiTestHarness::new(80, 24)?;
+/// harness.state_mut().feed(b"Ready");
+///
+/// assert_screen_contains!(harness, "Ready");
+/// assert_screen_contains!(harness, "Ready", "app did not start");
+/// # Ok(())
+/// # }
+/// # test().unwrap();
+/// ```
+#[macro_export]
+macro_rules! assert_screen_contains {
+    ($harness:expr, $text:expr $(,)?) => {{
+        let state = $harness.state();
+        let text = &$text;
+        let text: &str = ::core::convert::AsRef::<str>::as_ref(text);
+        if !state.contains(text) {
+            ::core::panic!(
+                "assertion failed: screen does not contain {:?}\n\nScreen state:\n{}",
+                text,
+                state.debug_contents()
+            );
+        }
+    }};
+    ($harness:expr, $text:expr, $($arg:tt)+) => {{
+        let state = $harness.state();
+        let text = &$text;
+        let text: &str = ::core::convert::AsRef::<str>::as_ref(text);
+        if !state.contains(text) {
+            ::core::panic!(
+                "assertion failed: screen does not contain {:?}: {}\n\nScreen state:\n{}",
+                text,
+                ::core::format_args!($($arg)+),
+                state.debug_contents()
+            );
+        }
+    }};
+}
+
+/// Asserts that some row of the screen matches a regular expression.
+///
+/// Rows are matched separately (see
+/// [`ScreenState::contains_regex`](crate::ScreenState::contains_regex)). An
+/// invalid pattern panics as well. On failure the panic message includes the
+/// full screen contents.
+///
+/// # Example
+///
+/// ```rust
+/// use ratatui_testlib::{assert_screen_matches, TuiTestHarness};
+///
+/// # fn test() -> ratatui_testlib::Result<()> {
+/// let mut harness = TuiTestHarness::new(80, 24)?;
+/// harness.state_mut().feed(b"Loaded 42 items");
+///
+/// assert_screen_matches!(harness, r"Loaded \d+ items");
+/// # Ok(())
+/// # }
+/// # test().unwrap();
+/// ```
+#[macro_export]
+macro_rules! assert_screen_matches {
+    ($harness:expr, $pattern:expr $(,)?) => {{
+        let state = $harness.state();
+        let pattern = &$pattern;
+        let pattern: &str = ::core::convert::AsRef::<str>::as_ref(pattern);
+        match state.contains_regex(pattern) {
+            ::core::result::Result::Ok(true) => {}
+            ::core::result::Result::Ok(false) => ::core::panic!(
+                "assertion failed: no screen row matches /{}/\n\nScreen state:\n{}",
+                pattern,
+                state.debug_contents()
+            ),
+            ::core::result::Result::Err(e) => ::core::panic!("{}", e),
+        }
+    }};
+}
+
+/// Asserts that the cursor is at the given position.
+///
+/// Takes a [`TuiTestHarness`](crate::TuiTestHarness), a row and a column
+/// (0-based). On failure the panic message includes both positions and the
+/// full screen contents.
+///
+/// # Example
+///
+/// ```rust
+/// use ratatui_testlib::{assert_cursor_at, TuiTestHarness};
+///
+/// # fn test() -> ratatui_testlib::Result<()> {
+/// let mut harness = TuiTestHarness::new(80, 24)?;
+/// harness.state_mut().feed(b"\x1b[3;5H");
+///
+/// assert_cursor_at!(harness, 2, 4);
+/// # Ok(())
+/// # }
+/// # test().unwrap();
+/// ```
+#[macro_export]
+macro_rules! assert_cursor_at {
+    ($harness:expr, $row:expr, $col:expr $(,)?) => {{
+        let state = $harness.state();
+        let expected: (u16, u16) = ($row, $col);
+        let actual = state.cursor_position();
+        if actual != expected {
+            ::core::panic!(
+                "assertion failed: cursor is at {:?}, expected {:?}\n\nScreen state:\n{}",
+                actual,
+                expected,
+                state.debug_contents()
+            );
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Result, TuiTestHarness};
+
+    fn harness_with(output: &[u8]) -> Result<TuiTestHarness> {
+        let mut harness = TuiTestHarness::new(20, 4)?;
+        harness.state_mut().feed(output);
+        Ok(harness)
+    }
+
+    #[test]
+    fn test_assert_screen_contains() -> Result<()> {
+        let harness = harness_with(b"Hello\r\nWorld")?;
+        assert_screen_contains!(harness, "Hello");
+        assert_screen_contains!(harness, String::from("World"));
+        assert_screen_contains!(harness, "World", "row {} missing", 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_assert_screen_contains_failure_message() {
+        let harness = harness_with(b"Hello\r\nWorld").unwrap();
+        let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            assert_screen_contains!(harness, "Goodbye", "after {}", "login");
+        }))
+        .unwrap_err();
+
+        let msg = panic.downcast_ref::<String>().unwrap();
+        assert!(msg.contains("screen does not contain \"Goodbye\": after login"));
+        assert!(msg.contains("Screen state:\nHello"));
+        assert!(msg.contains("\nWorld"));
+    }
+
+    #[test]
+    fn test_assert_screen_matches() -> Result<()> {
+        let harness = harness_with(b"Loaded 42 items")?;
+        assert_screen_matches!(harness, r"Loaded \d+ items");
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "no screen row matches /^\\d+ errors/")]
+    fn test_assert_screen_matches_failure() {
+        let harness = harness_with(b"Loaded 42 items").unwrap();
+        assert_screen_matches!(harness, r"^\d+ errors");
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid regex")]
+    fn test_assert_screen_matches_invalid_pattern() {
+        let harness = harness_with(b"text").unwrap();
+        assert_screen_matches!(harness, "(unclosed");
+    }
+
+    #[test]
+    fn test_assert_cursor_at() -> Result<()> {
+        let harness = harness_with(b"ab\r\ncd")?;
+        assert_cursor_at!(harness, 1, 2);
+        Ok(())
+    }
+
+    #[test]
+    #[should_panic(expected = "cursor is at (1, 2), expected (0, 0)")]
+    fn test_assert_cursor_at_failure() {
+        let harness = harness_with(b"ab\r\ncd").unwrap();
+        assert_cursor_at!(harness, 0, 0);
+    }
+}
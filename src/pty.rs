@@ -9,7 +9,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use portable_pty::{Child, CommandBuilder, ExitStatus, PtyPair, PtySize};
+use portable_pty::{Child, CommandBuilder, ExitStatus, MasterPty, PtyPair, PtySize, SlavePty};
 
 use crate::error::{Result, TermTestError};
 
@@ -24,11 +24,18 @@ const DEFAULT_SPAWN_TIMEOUT: Duration = Duration::from_secs(5);
 /// This provides low-level access to PTY operations for spawning processes,
 /// reading output, and sending input.
 pub struct TestTerminal {
-    pty_pair: PtyPair,
+    master: Box<dyn MasterPty + Send>,
+    // Our own handle to the slave side; `None` once released
+    slave: Option<Box<dyn SlavePty + Send>>,
     child: Option<Box<dyn Child + Send + Sync>>,
     exit_status: Option<ExitStatus>,
     buffer_size: usize,
     writer: Option<Box<dyn Write + Send>>,
+    reader_rx: Option<mpsc::Receiver<std::io::Result<Vec<u8>>>>,
+    pending: Vec<u8>,
+    pending_error: Option<std::io::Error>,
+    // The reader thread saw end-of-file
+    eof: bool,
 }
 
 impl TestTerminal {
@@ -59,7 +66,7 @@ impl TestTerminal {
         }
 
         let pty_system = portable_pty::native_pty_system();
-        let pty_pair = pty_system.openpty(PtySize {
+        let PtyPair { master, slave } = pty_system.openpty(PtySize {
             rows: height,
             cols: width,
             pixel_width: 0,
@@ -67,11 +74,16 @@ impl TestTerminal {
         })?;
 
         Ok(Self {
-            pty_pair,
+            master,
+            slave: Some(slave),
             child: None,
             exit_status: None,
             buffer_size: DEFAULT_BUFFER_SIZE,
             writer: None,
+            reader_rx: None,
+            pending: Vec::new(),
+            pending_error: None,
+            eof: false,
         })
     }
 
@@ -162,7 +174,10 @@ impl TestTerminal {
         let start = Instant::now();
 
         // Spawn the command
-        let child = self.pty_pair.slave.spawn_command(cmd).map_err(|e| {
+        let slave = self.slave.as_ref().ok_or_else(|| {
+            TermTestError::SpawnFailed("PTY slave was released; create a new terminal".to_string())
+        })?;
+        let child = slave.spawn_command(cmd).map_err(|e| {
             TermTestError::SpawnFailed(format!("Failed to spawn process in PTY: {}", e))
         })?;
 
@@ -208,49 +223,110 @@ impl TestTerminal {
         // This ensures we return quickly when no data is available
         let read_timeout = Duration::from_millis(100);
 
-        let mut reader = self.pty_pair.master.try_clone_reader().map_err(|e| {
-            TermTestError::Io(std::io::Error::new(
-                ErrorKind::Other,
-                format!("Failed to clone PTY reader: {}", e),
-            ))
-        })?;
-
-        // Use a channel to implement timeout on blocking read
-        let (tx, rx) = mpsc::channel();
-        let buf_len = buf.len();
+        if self.pending.is_empty() && self.pending_error.is_none() {
+            self.receive(read_timeout)?;
+        }
 
-        std::thread::spawn(move || {
-            let mut local_buf = vec![0u8; buf_len];
-            let result = reader.read(&mut local_buf);
-            let _ = tx.send((result, local_buf));
-        });
+        if !self.pending.is_empty() {
+            let n = self.pending.len().min(buf.len());
+            buf[..n].copy_from_slice(&self.pending[..n]);
+            self.pending.drain(..n);
+            return Ok(n);
+        }
 
-        match rx.recv_timeout(read_timeout) {
-            Ok((Ok(n), local_buf)) => {
-                if n > 0 {
-                    buf[..n].copy_from_slice(&local_buf[..n]);
-                }
-                Ok(n)
+        match self.pending_error.take() {
+            Some(e) if e.kind() == ErrorKind::Interrupted => {
+                // Retry on interrupt - but return 0 to let caller retry
+                Ok(0)
             }
-            Ok((Err(e), _)) => {
-                if e.kind() == ErrorKind::Interrupted {
-                    // Retry on interrupt - but return 0 to let caller retry
-                    Ok(0)
-                } else if e.kind() == ErrorKind::WouldBlock {
-                    Ok(0)
-                } else {
-                    Err(TermTestError::Io(e))
+            Some(e) if e.kind() == ErrorKind::WouldBlock => Ok(0),
+            Some(e) => Err(TermTestError::Io(e)),
+            None => Ok(0),
+        }
+    }
+
+    /// Blocks until PTY output is available or the timeout expires.
+    ///
+    /// Unlike sleeping for a fixed interval, this wakes as soon as the reader
+    /// thread delivers data. Any data received is buffered and returned by the
+    /// next call to [`read`](Self::read).
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - Maximum time to wait for output
+    ///
+    /// # Returns
+    ///
+    /// `true` if output (or a read error) is ready to be consumed, `false` if the
+    /// timeout expired first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the PTY reader cannot be created.
+    pub fn wait_readable(&mut self, timeout: Duration) -> Result<bool> {
+        if self.pending.is_empty() && self.pending_error.is_none() {
+            self.receive(timeout)?;
+        }
+        Ok(!self.pending.is_empty() || self.pending_error.is_some())
+    }
+
+    /// Waits up to `timeout` for the next chunk from the reader thread and
+    /// stores it in the pending buffer.
+    ///
+    /// The reader thread is started on first use and lives as long as the PTY,
+    /// so output is never lost between reads.
+    fn receive(&mut self, timeout: Duration) -> Result<()> {
+        if self.reader_rx.is_none() {
+            let mut reader = self.master.try_clone_reader().map_err(|e| {
+                TermTestError::Io(std::io::Error::new(
+                    ErrorKind::Other,
+                    format!("Failed to clone PTY reader: {}", e),
+                ))
+            })?;
+
+            let (tx, rx) = mpsc::channel();
+            let buf_len = self.buffer_size;
+
+            std::thread::spawn(move || {
+                let mut local_buf = vec![0u8; buf_len];
+                loop {
+                    let result = match reader.read(&mut local_buf) {
+                        // EOF - the channel disconnects when the thread exits
+                        Ok(0) => break,
+                        Ok(n) => Ok(local_buf[..n].to_vec()),
+                        Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                        Err(e) => Err(e),
+                    };
+                    let is_err = result.is_err();
+                    if tx.send(result).is_err() || is_err {
+                        break;
+                    }
                 }
-            }
+            });
+
+            self.reader_rx = Some(rx);
+        }
+
+        let rx = self
+            .reader_rx
+            .as_ref()
+            .expect("reader channel initialized above");
+        match rx.recv_timeout(timeout) {
+            Ok(Ok(data)) => self.pending.extend_from_slice(&data),
+            Ok(Err(e)) => self.pending_error = Some(e),
             Err(mpsc::RecvTimeoutError::Timeout) => {
-                // No data available within timeout - return 0 (non-blocking behavior)
-                Ok(0)
+                // No data available within timeout
             }
             Err(mpsc::RecvTimeoutError::Disconnected) => {
-                // Thread panicked or channel closed
-                Ok(0)
+                // Reader reached EOF; avoid spinning when callers wait again
+                self.eof = true;
+                if !timeout.is_zero() {
+                    std::thread::sleep(timeout.min(Duration::from_millis(10)));
+                }
             }
         }
+
+        Ok(())
     }
 
     /// Reads output from the PTY with a timeout.
@@ -362,7 +438,7 @@ impl TestTerminal {
     pub fn write(&mut self, data: &[u8]) -> Result<usize> {
         // Get or create the writer (take_writer can only be called once)
         if self.writer.is_none() {
-            self.writer = Some(self.pty_pair.master.take_writer().map_err(|e| {
+            self.writer = Some(self.master.take_writer().map_err(|e| {
                 TermTestError::Io(std::io::Error::new(
                     ErrorKind::Other,
                     format!("Failed to take PTY writer: {}", e),
@@ -396,7 +472,7 @@ impl TestTerminal {
     pub fn write_all(&mut self, data: &[u8]) -> Result<()> {
         // Get or create the writer (take_writer can only be called once)
         if self.writer.is_none() {
-            self.writer = Some(self.pty_pair.master.take_writer().map_err(|e| {
+            self.writer = Some(self.master.take_writer().map_err(|e| {
                 TermTestError::Io(std::io::Error::new(
                     ErrorKind::Other,
                     format!("Failed to take PTY writer: {}", e),
@@ -435,7 +511,7 @@ impl TestTerminal {
             return Err(TermTestError::InvalidDimensions { width, height });
         }
 
-        self.pty_pair.master.resize(PtySize {
+        self.master.resize(PtySize {
             rows: height,
             cols: width,
             pixel_width: 0,
@@ -445,6 +521,25 @@ impl TestTerminal {
         Ok(())
     }
 
+    /// Closes this terminal's own handle to the PTY slave.
+    ///
+    /// A PTY only reports end-of-file once every handle to its slave side is
+    /// closed. Releasing ours after spawning means a child that closes its
+    /// stdout, stderr and stdin is observable through [`is_eof`](Self::is_eof)
+    /// even while it keeps running. No further processes can be spawned in
+    /// this terminal afterwards.
+    pub fn release_slave(&mut self) {
+        self.slave = None;
+    }
+
+    /// Returns `true` once reading the PTY has hit end-of-file.
+    ///
+    /// This only happens after [`release_slave`](Self::release_slave), when the
+    /// child has closed all of its handles to the terminal.
+    pub fn is_eof(&self) -> bool {
+        self.eof
+    }
+
     /// Returns the current PTY dimensions.
     pub fn size(&self) -> (u16, u16) {
         // Note: portable-pty doesn't provide a way to query current size,
@@ -854,6 +949,29 @@ mod tests {
         assert!(output_str.contains("test output"));
     }
 
+    #[test]
+    fn test_eof_after_release_slave() {
+        let mut terminal = TestTerminal::new(80, 24).unwrap();
+        let mut cmd = CommandBuilder::new("sh");
+        cmd.arg("-c");
+        cmd.arg("echo bye; exec >&- 2>&- <&-; sleep 2");
+        terminal.spawn(cmd).unwrap();
+        terminal.release_slave();
+
+        let mut buf = [0u8; 1024];
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while !terminal.is_eof() && Instant::now() < deadline {
+            let _ = terminal.read(&mut buf);
+        }
+        assert!(terminal.is_eof());
+        assert!(terminal.is_running());
+
+        // The slave is gone, so nothing else can be spawned
+        terminal.kill().unwrap();
+        let result = terminal.spawn(CommandBuilder::new("true"));
+        assert!(matches!(result, Err(TermTestError::SpawnFailed(_))));
+    }
+
     #[test]
     fn test_kill() {
         let mut terminal = TestTerminal::new(80, 24).unwrap();
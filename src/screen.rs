@@ -69,6 +69,11 @@
 //! // - Sixel regions: oracle.sixel_regions()
 //! ```
 
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap, VecDeque},
+    hash::{Hash, Hasher},
+};
+
 use vtparse::{CsiParam, VTActor, VTParser};
 
 /// Represents a single terminal cell with character and attributes.
@@ -78,7 +83,14 @@ use vtparse::{CsiParam, VTActor, VTParser};
 /// - Foreground color (ANSI color code, 0-255, or None for default)
 /// - Background color (ANSI color code, 0-255, or None for default)
 /// - Text attributes (bold, italic, underline, etc.)
+///
+/// Inverse, blink and 24-bit colors are not stored here; query them with
+/// [`ScreenState::attributes_at`] and [`ScreenState::fg_color_at`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "snapshot-json",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct Cell {
     /// The character displayed in this cell
     pub c: char,
@@ -107,6 +119,264 @@ impl Default for Cell {
     }
 }
 
+impl Cell {
+    /// Returns the text attributes of this cell without its character.
+    ///
+    /// A `Cell` doesn't carry inverse, blink or 24-bit colors, so those are
+    /// unset in the result; [`ScreenState::styled_runs`] reports them.
+    pub fn style(&self) -> CellStyle {
+        CellStyle {
+            fg: self.fg,
+            bg: self.bg,
+            bold: self.bold,
+            italic: self.italic,
+            underline: self.underline,
+            ..CellStyle::default()
+        }
+    }
+}
+
+/// Attributes of a cell that [`Cell`] has no fields for, kept in a grid
+/// alongside the cells.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct CellExtras {
+    inverse: bool,
+    blink: bool,
+    fg_rgb: Option<(u8, u8, u8)>,
+    bg_rgb: Option<(u8, u8, u8)>,
+}
+
+impl CellExtras {
+    /// Returns the full style of `cell`, which has these extra attributes.
+    fn style_of(&self, cell: &Cell) -> CellStyle {
+        CellStyle {
+            inverse: self.inverse,
+            blink: self.blink,
+            fg_rgb: self.fg_rgb,
+            bg_rgb: self.bg_rgb,
+            ..cell.style()
+        }
+    }
+}
+
+/// Text attributes set by SGR sequences, independent of any character.
+///
+/// Used for the terminal's current pen (see [`ScreenState::current_pen`]) and
+/// for comparing the styling of cells (see [`Cell::style`]). The default value
+/// is the reset state: default colors and no attributes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CellStyle {
+    /// Foreground color (None = default, Some(0-255) = ANSI color)
+    pub fg: Option<u8>,
+    /// Background color (None = default, Some(0-255) = ANSI color)
+    pub bg: Option<u8>,
+    /// Bold attribute
+    pub bold: bool,
+    /// Italic attribute
+    pub italic: bool,
+    /// Underline attribute
+    pub underline: bool,
+    /// Inverse (reverse video) attribute
+    pub inverse: bool,
+    /// Blink attribute
+    pub blink: bool,
+    /// 24-bit foreground color; `fg` is None when set
+    pub fg_rgb: Option<(u8, u8, u8)>,
+    /// 24-bit background color; `bg` is None when set
+    pub bg_rgb: Option<(u8, u8, u8)>,
+}
+
+impl CellStyle {
+    /// Returns the foreground color, whether indexed or 24-bit.
+    fn fg_color(&self) -> Color {
+        self.fg_rgb
+            .map_or(Color::from(self.fg), |(r, g, b)| Color::Rgb(r, g, b))
+    }
+
+    /// Returns the background color, whether indexed or 24-bit.
+    fn bg_color(&self) -> Color {
+        self.bg_rgb
+            .map_or(Color::from(self.bg), |(r, g, b)| Color::Rgb(r, g, b))
+    }
+}
+
+/// Boolean text attributes of a cell, as returned by
+/// [`ScreenState::attributes_at`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CellAttributes {
+    /// Bold attribute (SGR 1)
+    pub bold: bool,
+    /// Italic attribute (SGR 3)
+    pub italic: bool,
+    /// Underline attribute (SGR 4)
+    pub underline: bool,
+    /// Inverse (reverse video) attribute (SGR 7)
+    pub inverse: bool,
+    /// Blink attribute (SGR 5 or 6)
+    pub blink: bool,
+}
+
+/// A cell whose character differs between two screens.
+///
+/// Returned by [`ScreenState::diff`]. A side is `None` when the position lies
+/// outside that screen, which only happens when the screens differ in size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellDiff {
+    /// Row of the cell (0-indexed).
+    pub row: u16,
+    /// Column of the cell (0-indexed).
+    pub col: u16,
+    /// Character in the first screen, or None if out of its bounds.
+    pub before: Option<char>,
+    /// Character in the second screen, or None if out of its bounds.
+    pub after: Option<char>,
+}
+
+/// A cell color, as returned by [`ScreenState::fg_color_at`] and
+/// [`ScreenState::bg_color_at`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    /// The terminal's default color (never set, or reset with SGR 0/39/49).
+    Default,
+    /// A palette color: 0-7 standard, 8-15 bright, 16-255 extended.
+    Indexed(u8),
+    /// A 24-bit color, set with SGR `38;2;r;g;b` or `48;2;r;g;b`.
+    ///
+    /// Use [`ScreenState::rgb_at`] to resolve an indexed color to RGB as well.
+    Rgb(u8, u8, u8),
+}
+
+impl From<Option<u8>> for Color {
+    fn from(index: Option<u8>) -> Self {
+        index.map_or(Color::Default, Color::Indexed)
+    }
+}
+
+/// A run of adjacent cells in one row that share the same attributes.
+///
+/// Returned by [`ScreenState::styled_runs`]; useful for styled assertions and
+/// for exporting the screen to formats such as HTML.
+///
+/// # Fields
+///
+/// - `text`: The characters of the run
+/// - `fg`: Foreground color of the run
+/// - `bg`: Background color of the run
+/// - `style`: All text attributes of the run, including colors
+/// - `start_col`: Column of the first cell of the run (0-indexed)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyledRun {
+    /// The characters of the run.
+    pub text: String,
+    /// Foreground color of the run.
+    pub fg: Color,
+    /// Background color of the run.
+    pub bg: Color,
+    /// All text attributes of the run.
+    pub style: CellStyle,
+    /// Column of the first cell of the run (0-indexed).
+    pub start_col: u16,
+}
+
+impl StyledRun {
+    /// Returns the boolean text attributes of the run.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{CellAttributes, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[1;4mTitle");
+    ///
+    /// let run = &screen.styled_runs(0)[0];
+    /// assert_eq!(
+    ///     run.attributes(),
+    ///     CellAttributes { bold: true, underline: true, ..CellAttributes::default() }
+    /// );
+    /// ```
+    pub fn attributes(&self) -> CellAttributes {
+        CellAttributes {
+            bold: self.style.bold,
+            italic: self.style.italic,
+            underline: self.style.underline,
+            inverse: self.style.inverse,
+            blink: self.style.blink,
+        }
+    }
+}
+
+/// Cursor shape selected with DECSCUSR (`ESC [ Ps SP q`).
+///
+/// Editors commonly switch shapes to indicate their mode, e.g. a block cursor
+/// in normal mode and a bar in insert mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CursorShape {
+    /// The terminal's default shape (`Ps` = 0).
+    Default,
+    /// Blinking block (`Ps` = 1).
+    BlinkingBlock,
+    /// Steady block (`Ps` = 2).
+    SteadyBlock,
+    /// Blinking underline (`Ps` = 3).
+    BlinkingUnderline,
+    /// Steady underline (`Ps` = 4).
+    SteadyUnderline,
+    /// Blinking bar (`Ps` = 5).
+    BlinkingBar,
+    /// Steady bar (`Ps` = 6).
+    SteadyBar,
+}
+
+impl CursorShape {
+    /// Converts a DECSCUSR parameter to a cursor shape.
+    ///
+    /// Returns `None` for values outside 0-6.
+    pub fn from_param(param: i64) -> Option<Self> {
+        match param {
+            0 => Some(Self::Default),
+            1 => Some(Self::BlinkingBlock),
+            2 => Some(Self::SteadyBlock),
+            3 => Some(Self::BlinkingUnderline),
+            4 => Some(Self::SteadyUnderline),
+            5 => Some(Self::BlinkingBar),
+            6 => Some(Self::SteadyBar),
+            _ => None,
+        }
+    }
+}
+
+/// Which mouse events the application asked the terminal to report.
+///
+/// Set with DEC private modes 9, 1000, 1002 and 1003. The encoding of the
+/// reports (such as SGR, mode 1006) is chosen separately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MouseTrackingMode {
+    /// Button presses only (mode 9).
+    X10,
+    /// Button presses and releases (mode 1000).
+    Normal,
+    /// Presses, releases and motion while a button is held (mode 1002).
+    ButtonEvent,
+    /// Presses, releases and all motion (mode 1003).
+    AnyEvent,
+}
+
+impl MouseTrackingMode {
+    /// Converts a DEC private mode number to a tracking mode.
+    ///
+    /// Returns `None` for modes other than 9, 1000, 1002 and 1003.
+    pub fn from_mode(mode: i64) -> Option<Self> {
+        match mode {
+            9 => Some(Self::X10),
+            1000 => Some(Self::Normal),
+            1002 => Some(Self::ButtonEvent),
+            1003 => Some(Self::AnyEvent),
+            _ => None,
+        }
+    }
+}
+
 /// A rectangular area in terminal coordinate space.
 ///
 /// Represents a rectangular region with a position and size. This is compatible
@@ -274,6 +544,38 @@ pub struct KittyRegion {
     pub data: Vec<u8>,
 }
 
+/// A Kitty graphics image transmission command with its parsed control data.
+///
+/// Kitty graphics commands have the form `ESC _ G <key>=<value>,...;<payload> ESC \`.
+/// Only transmission commands (action `t`, `T`, or no action, which defaults to
+/// `t`) are reported; see [`ScreenState::kitty_images`].
+///
+/// # Fields
+///
+/// - `row`: Cursor row when the command was received (0-indexed)
+/// - `col`: Cursor column when the command was received (0-indexed)
+/// - `params`: The control data key/value pairs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KittyImage {
+    /// Row of the image (0-indexed).
+    pub row: u16,
+    /// Column of the image (0-indexed).
+    pub col: u16,
+    /// Control data key/value pairs, e.g. `a=T`, `f=100`, `i=1`.
+    pub params: BTreeMap<String, String>,
+}
+
+impl KittyImage {
+    /// Returns the value of a control data key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The control data key (e.g., `"f"` for the format)
+    pub fn param(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(String::as_str)
+    }
+}
+
 /// Represents an iTerm2 inline image region in the terminal.
 ///
 /// iTerm2 inline images use OSC 1337;File= sequences to embed
@@ -300,6 +602,56 @@ pub struct ITerm2Region {
     pub data: Vec<u8>,
 }
 
+/// Inline image protocols recognized by [`ScreenState`].
+///
+/// With the `sixel` feature, converts into `graphics::GraphicsProtocol`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageProtocol {
+    /// Sixel bitmap graphics (`ESC P q`).
+    Sixel,
+    /// Kitty graphics protocol (`ESC _ G`).
+    Kitty,
+    /// iTerm2 inline images (`ESC ] 1337;File=`).
+    ITerm2,
+}
+
+/// Marks where an inline image of any protocol was detected.
+///
+/// Protocol-specific details are available from [`ScreenState::sixel_regions`],
+/// [`ScreenState::kitty_regions`] and [`ScreenState::iterm2_regions`]; markers give
+/// a uniform view for tests that only care that an image was drawn and where.
+///
+/// # Fields
+///
+/// - `protocol`: The image protocol that was used
+/// - `row`: Cursor row when the image sequence completed (0-indexed)
+/// - `col`: Cursor column when the image sequence completed (0-indexed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageMarker {
+    /// The image protocol that was used.
+    pub protocol: ImageProtocol,
+    /// Row of the image (0-indexed).
+    pub row: u16,
+    /// Column of the image (0-indexed).
+    pub col: u16,
+}
+
+/// A hyperlink written with OSC 8 (`ESC ] 8 ; params ; URI ST`).
+///
+/// Returned by [`ScreenState::hyperlinks`]. The position is where the link
+/// text started when it was written; it is not adjusted if the screen scrolls.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hyperlink {
+    /// The link target.
+    pub uri: String,
+    /// Row where the link text starts (0-indexed).
+    pub row: u16,
+    /// Column where the link text starts (0-indexed).
+    pub col: u16,
+    /// The characters printed while the link was open.
+    pub text: String,
+}
+
 /// A complete snapshot of the terminal screen grid state.
 ///
 /// This structure provides a point-in-time capture of the entire screen state,
@@ -333,6 +685,10 @@ pub struct ITerm2Region {
 /// }
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "snapshot-json",
+    derive(serde::Serialize, serde::Deserialize)
+)]
 pub struct GridSnapshot {
     /// Screen width in columns.
     pub width: u16,
@@ -344,6 +700,66 @@ pub struct GridSnapshot {
     pub cursor: (u16, u16),
 }
 
+#[cfg(feature = "snapshot-json")]
+impl GridSnapshot {
+    /// Serializes the snapshot to a JSON string.
+    ///
+    /// The JSON captures every cell's character and style along with the grid
+    /// dimensions and cursor position, so it can be checked into a repository as
+    /// a baseline and compared against later runs with [`from_json`](Self::from_json).
+    ///
+    /// Requires the `snapshot-json` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Parse` error if serialization fails.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{GridSnapshot, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(20, 4);
+    /// screen.feed(b"\x1b[1;32mOK\x1b[0m");
+    ///
+    /// let json = screen.snapshot().to_json()?;
+    /// let restored = GridSnapshot::from_json(&json)?;
+    /// assert_eq!(restored, screen.snapshot());
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string(self).map_err(|e| {
+            crate::TermTestError::Parse(format!("Failed to serialize snapshot: {}", e))
+        })
+    }
+
+    /// Deserializes a snapshot from a JSON string produced by [`to_json`](Self::to_json).
+    ///
+    /// Requires the `snapshot-json` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `Parse` error if the JSON is malformed or does not describe a snapshot.
+    pub fn from_json(json: &str) -> crate::Result<Self> {
+        serde_json::from_str(json).map_err(|e| {
+            crate::TermTestError::Parse(format!("Failed to deserialize snapshot: {}", e))
+        })
+    }
+}
+
+/// A query from the application that a real terminal would answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TerminalQuery {
+    /// Cursor-position request (DSR 6), with the cursor position at the time.
+    CursorPosition(u16, u16),
+    /// Primary Device Attributes (`ESC [ c`).
+    PrimaryAttributes,
+    /// Secondary Device Attributes (`ESC [ > c`).
+    SecondaryAttributes,
+    /// DECRQSS (`ESC P $ q Pt ESC \`) for the setting `Pt`.
+    Setting(String),
+}
+
 /// Terminal state tracking for vtparse parser.
 ///
 /// Implements VTActor to handle escape sequences including DCS for Sixel,
@@ -351,9 +767,14 @@ pub struct GridSnapshot {
 struct TerminalState {
     cursor_pos: (u16, u16),
     sixel_regions: Vec<SixelRegion>,
+    /// Stream byte range of each entry in `sixel_regions`, `None` for regions
+    /// added through `sixel_regions_mut`.
+    sixel_stream_ranges: Vec<Option<std::ops::Range<usize>>>,
     current_sixel_data: Vec<u8>,
     current_sixel_params: Vec<i64>,
     in_sixel_mode: bool,
+    /// Stream offset of the escape that introduced the current Sixel.
+    current_sixel_start: usize,
 
     // Kitty graphics protocol state
     kitty_regions: Vec<KittyRegion>,
@@ -365,15 +786,102 @@ struct TerminalState {
     current_iterm2_data: Vec<u8>,
     in_iterm2_mode: bool,
 
+    /// Images of all protocols, in the order they were detected.
+    image_markers: Vec<ImageMarker>,
+
+    /// OSC 8 hyperlinks, in the order they were opened.
+    hyperlinks: Vec<Hyperlink>,
+    /// Index into `hyperlinks` of the link currently open, if any.
+    open_hyperlink: Option<usize>,
+    /// Most recent title set with OSC 0 or OSC 2.
+    window_title: Option<String>,
+
     width: u16,
     height: u16,
     cells: Vec<Vec<Cell>>,
+    /// The buffer that is not currently displayed: the main screen while the
+    /// alternate screen is active, and the alternate screen otherwise.
+    inactive_cells: Vec<Vec<Cell>>,
+    /// Whether each cell of `cells` has been printed to, as opposed to still
+    /// holding its initial blank.
+    written: Vec<Vec<bool>>,
+    /// Written flags for `inactive_cells`.
+    inactive_written: Vec<Vec<bool>>,
+    /// Inverse, blink and 24-bit colors of each cell of `cells`.
+    extras: Vec<Vec<CellExtras>>,
+    /// Extra attributes for `inactive_cells`.
+    inactive_extras: Vec<Vec<CellExtras>>,
+    /// Whether each row of `cells` is a continuation of the row above,
+    /// produced by autowrap rather than an explicit newline.
+    wrapped: Vec<bool>,
+    /// Wrap flags for `inactive_cells`.
+    inactive_wrapped: Vec<bool>,
+    /// Rows scrolled off the top of the main screen, oldest first.
+    scrollback: VecDeque<Vec<Cell>>,
+    /// Maximum number of rows kept in `scrollback`.
+    scrollback_limit: usize,
+    /// First and last row (0-based, inclusive) of the scrolling region (DECSTBM).
+    scroll_top: u16,
+    scroll_bottom: u16,
+    /// Whether autowrap (DECAWM, mode 7) is enabled.
+    autowrap: bool,
+    /// Set after printing in the last column; the next printed character
+    /// wraps to the following line first.
+    wrap_pending: bool,
+    alt_screen_active: bool,
+    /// Cursor position saved on entering the alternate screen via mode 1049.
+    saved_cursor: (u16, u16),
+    /// Whether the application enabled bracketed paste (mode 2004).
+    bracketed_paste: bool,
+    /// Whether the cursor is shown (DECTCEM, mode 25).
+    cursor_visible: bool,
+    /// Mouse events the application asked to receive, if any.
+    mouse_tracking: Option<MouseTrackingMode>,
+    /// Offset, within everything fed so far, of the first byte of the segment
+    /// being parsed.
+    stream_offset: usize,
+    /// Offset of the most recent escape (ESC or 8-bit DCS) introducer.
+    last_esc_offset: usize,
+    /// Number of absolute (CUP/HVP) cursor movement sequences processed.
+    absolute_moves: usize,
+    /// Number of relative (CUU/CUD/CUF/CUB) cursor movement sequences processed.
+    relative_moves: usize,
+    /// Number of line feeds processed.
+    line_feeds: usize,
+    /// Number of erase-display sequences that cleared the whole screen.
+    erase_display_count: usize,
+    /// Number of `ESC [ 2 J` and `ESC [ 3 J` sequences processed.
+    full_redraws: usize,
+    /// Number of times the alternate screen was entered.
+    alt_screen_entries: usize,
+    /// Number of prints to each visible cell since the last frame mark.
+    frame_writes: Vec<Vec<u16>>,
+    /// Write counts for `inactive_cells`.
+    inactive_frame_writes: Vec<Vec<u16>>,
+    /// Queries not yet answered, in the order they were received.
+    queries: Vec<TerminalQuery>,
+    /// Whether a DECRQSS request is being received.
+    in_decrqss: bool,
+    current_decrqss: Vec<u8>,
+    /// Cursor shapes selected via DECSCUSR, in order.
+    cursor_shapes: Vec<CursorShape>,
+    /// Number of lines scrolled off the top since the last `clear_scrolled` call.
+    scrolled_lines: usize,
+    /// Whether each visible row was written, erased or scrolled since the last
+    /// `clear_dirty_rows` call.
+    dirty: Vec<bool>,
+    /// Every distinct cursor position visited while tracing is enabled.
+    cursor_trace: Option<Vec<(u16, u16)>>,
     /// Current text attributes (for SGR sequences)
     current_fg: Option<u8>,
     current_bg: Option<u8>,
+    current_fg_rgb: Option<(u8, u8, u8)>,
+    current_bg_rgb: Option<(u8, u8, u8)>,
     current_bold: bool,
     current_italic: bool,
     current_underline: bool,
+    current_inverse: bool,
+    current_blink: bool,
 }
 
 impl TerminalState {
@@ -383,30 +891,95 @@ impl TerminalState {
         Self {
             cursor_pos: (0, 0),
             sixel_regions: Vec::new(),
+            sixel_stream_ranges: Vec::new(),
             current_sixel_data: Vec::new(),
             current_sixel_params: Vec::new(),
             in_sixel_mode: false,
+            current_sixel_start: 0,
             kitty_regions: Vec::new(),
             current_kitty_data: Vec::new(),
             in_kitty_mode: false,
             iterm2_regions: Vec::new(),
             current_iterm2_data: Vec::new(),
             in_iterm2_mode: false,
+            image_markers: Vec::new(),
+            hyperlinks: Vec::new(),
+            open_hyperlink: None,
+            window_title: None,
             width,
             height,
+            inactive_cells: cells.clone(),
             cells,
+            written: vec![vec![false; width as usize]; height as usize],
+            inactive_written: vec![vec![false; width as usize]; height as usize],
+            extras: vec![vec![CellExtras::default(); width as usize]; height as usize],
+            inactive_extras: vec![vec![CellExtras::default(); width as usize]; height as usize],
+            wrapped: vec![false; height as usize],
+            inactive_wrapped: vec![false; height as usize],
+            scrollback: VecDeque::new(),
+            scrollback_limit: 0,
+            scroll_top: 0,
+            scroll_bottom: height.saturating_sub(1),
+            autowrap: true,
+            wrap_pending: false,
+            alt_screen_active: false,
+            saved_cursor: (0, 0),
+            bracketed_paste: false,
+            cursor_visible: true,
+            mouse_tracking: None,
+            stream_offset: 0,
+            last_esc_offset: 0,
+            absolute_moves: 0,
+            relative_moves: 0,
+            line_feeds: 0,
+            erase_display_count: 0,
+            full_redraws: 0,
+            alt_screen_entries: 0,
+            frame_writes: vec![vec![0; width as usize]; height as usize],
+            inactive_frame_writes: vec![vec![0; width as usize]; height as usize],
+            queries: Vec::new(),
+            in_decrqss: false,
+            current_decrqss: Vec::new(),
+            cursor_shapes: Vec::new(),
+            scrolled_lines: 0,
+            dirty: vec![false; height as usize],
+            cursor_trace: None,
             current_fg: None,
             current_bg: None,
+            current_fg_rgb: None,
+            current_bg_rgb: None,
             current_bold: false,
             current_italic: false,
             current_underline: false,
+            current_inverse: false,
+            current_blink: false,
         }
     }
 
     fn put_char(&mut self, ch: char) {
+        let width = char_width(ch);
+        // Cells hold a single character, so there is nowhere to put combining
+        // marks and other zero-width characters
+        if width == 0 {
+            return;
+        }
+        // A wide glyph that doesn't fit in the last column moves to the next line
+        if width == 2 && self.autowrap && self.cursor_pos.1 + 1 == self.width {
+            self.wrap_pending = true;
+        }
+
+        if self.wrap_pending {
+            self.wrap_pending = false;
+            self.cursor_pos.1 = 0;
+            self.index();
+            if let Some(flag) = self.wrapped.get_mut(self.cursor_pos.0 as usize) {
+                *flag = true;
+            }
+        }
+
         let (row, col) = self.cursor_pos;
         if row < self.height && col < self.width {
-            self.cells[row as usize][col as usize] = Cell {
+            let cell = Cell {
                 c: ch,
                 fg: self.current_fg,
                 bg: self.current_bg,
@@ -414,17 +987,305 @@ impl TerminalState {
                 italic: self.current_italic,
                 underline: self.current_underline,
             };
-            // Move cursor forward, but don't wrap automatically
-            if col + 1 < self.width {
-                self.cursor_pos.1 = col + 1;
+            let extras = CellExtras {
+                inverse: self.current_inverse,
+                blink: self.current_blink,
+                fg_rgb: self.current_fg_rgb,
+                bg_rgb: self.current_bg_rgb,
+            };
+            self.cells[row as usize][col as usize] = cell;
+            self.extras[row as usize][col as usize] = extras;
+            self.written[row as usize][col as usize] = true;
+            self.dirty[row as usize] = true;
+            self.count_write(row, col);
+            // The second column of a wide glyph holds a blank spacer
+            if width == 2 && col + 1 < self.width {
+                self.cells[row as usize][col as usize + 1] = Cell { c: ' ', ..cell };
+                self.extras[row as usize][col as usize + 1] = extras;
+                self.written[row as usize][col as usize + 1] = true;
+                self.count_write(row, col + 1);
+            }
+            if let Some(index) = self.open_hyperlink {
+                self.hyperlinks[index].text.push(ch);
+            }
+            // Move cursor forward; at the last column, wrap on the next print
+            if col + width < self.width {
+                self.cursor_pos.1 = col + width;
+            } else {
+                self.cursor_pos.1 = self.width - 1;
+                if self.autowrap {
+                    self.wrap_pending = true;
+                }
+            }
+        }
+    }
+
+    /// Appends the cursor position to the cursor trace, if one is being recorded.
+    ///
+    /// Called before each action that may move the cursor, and after each
+    /// parsed segment, so every position the cursor rests at is seen.
+    fn trace_cursor(&mut self) {
+        let pos = self.cursor_pos;
+        if let Some(trace) = &mut self.cursor_trace {
+            if trace.last() != Some(&pos) {
+                trace.push(pos);
+            }
+        }
+    }
+
+    /// Sets the pen's foreground to a palette color, replacing any 24-bit color.
+    fn set_fg(&mut self, color: Option<u8>) {
+        self.current_fg = color;
+        self.current_fg_rgb = None;
+    }
+
+    /// Sets the pen's background to a palette color, replacing any 24-bit color.
+    fn set_bg(&mut self, color: Option<u8>) {
+        self.current_bg = color;
+        self.current_bg_rgb = None;
+    }
+
+    /// Returns the full style of the cell at `row`, `col`, or None if out of bounds.
+    fn style_at(&self, row: u16, col: u16) -> Option<CellStyle> {
+        let cell = self.cells.get(row as usize)?.get(col as usize)?;
+        Some(self.extras[row as usize][col as usize].style_of(cell))
+    }
+
+    /// Returns the full style of each cell of a row.
+    fn row_styles(&self, row: usize) -> Vec<CellStyle> {
+        self.cells[row]
+            .iter()
+            .zip(&self.extras[row])
+            .map(|(cell, extras)| extras.style_of(cell))
+            .collect()
+    }
+
+    /// Records a print to a cell for overdraw tracking.
+    fn count_write(&mut self, row: u16, col: u16) {
+        let count = &mut self.frame_writes[row as usize][col as usize];
+        *count = count.saturating_add(1);
+    }
+
+    /// Moves the cursor down one row, scrolling at the bottom of the scrolling region.
+    fn index(&mut self) {
+        if self.cursor_pos.0 == self.scroll_bottom {
+            self.scroll_up();
+        } else if self.cursor_pos.0 + 1 < self.height {
+            self.cursor_pos.0 += 1;
+        }
+    }
+
+    /// Scrolls the scrolling region up by one line, discarding its top row.
+    ///
+    /// Rows scrolled off the top of the screen go to scrollback.
+    fn scroll_up(&mut self) {
+        let (top, bottom) = (self.scroll_top as usize, self.scroll_bottom as usize);
+        if bottom >= self.cells.len() {
+            return;
+        }
+        let row = self.cells.remove(top);
+        if top == 0 && !self.alt_screen_active && self.scrollback_limit > 0 {
+            if self.scrollback.len() == self.scrollback_limit {
+                self.scrollback.pop_front();
+            }
+            self.scrollback.push_back(row);
+        }
+        self.cells
+            .insert(bottom, vec![Cell::default(); self.width as usize]);
+        self.written.remove(top);
+        self.written
+            .insert(bottom, vec![false; self.width as usize]);
+        self.extras.remove(top);
+        self.extras
+            .insert(bottom, vec![CellExtras::default(); self.width as usize]);
+        self.wrapped.remove(top);
+        self.wrapped.insert(bottom, false);
+        self.frame_writes.remove(top);
+        self.frame_writes
+            .insert(bottom, vec![0; self.width as usize]);
+        self.dirty[top..=bottom].fill(true);
+        self.scrolled_lines += 1;
+    }
+
+    /// Changes the screen size, keeping as much content as fits.
+    ///
+    /// Columns are cut or padded on the right. When the screen gets shorter,
+    /// rows are taken off the top (into scrollback) as far as needed to keep
+    /// the cursor row on screen, then off the bottom.
+    fn resize(&mut self, width: u16, height: u16) {
+        // Like real terminals, resizing resets the scrolling region
+        self.scroll_top = 0;
+        self.scroll_bottom = self.height - 1;
+        let excess = (self.cursor_pos.0 + 1).saturating_sub(height);
+        for _ in 0..excess {
+            self.scroll_up();
+        }
+        self.cursor_pos.0 -= excess;
+
+        let (w, h) = (width as usize, height as usize);
+        for cells in [&mut self.cells, &mut self.inactive_cells] {
+            cells.resize(h, vec![Cell::default(); w]);
+            for row in cells.iter_mut() {
+                row.resize(w, Cell::default());
+            }
+        }
+        for flags in [&mut self.written, &mut self.inactive_written] {
+            flags.resize(h, vec![false; w]);
+            for row in flags.iter_mut() {
+                row.resize(w, false);
+            }
+        }
+        for extras in [&mut self.extras, &mut self.inactive_extras] {
+            extras.resize(h, vec![CellExtras::default(); w]);
+            for row in extras.iter_mut() {
+                row.resize(w, CellExtras::default());
+            }
+        }
+        for counts in [&mut self.frame_writes, &mut self.inactive_frame_writes] {
+            counts.resize(h, vec![0; w]);
+            for row in counts.iter_mut() {
+                row.resize(w, 0);
             }
         }
+        self.wrapped.resize(h, false);
+        self.inactive_wrapped.resize(h, false);
+        self.dirty = vec![true; h];
+
+        self.width = width;
+        self.height = height;
+        self.scroll_bottom = height - 1;
+        self.move_cursor(self.cursor_pos.0, self.cursor_pos.1);
+        self.saved_cursor =
+            (self.saved_cursor.0.min(height - 1), self.saved_cursor.1.min(width - 1));
+    }
+
+    /// Records an image marker at the current cursor position.
+    fn mark_image(&mut self, protocol: ImageProtocol) {
+        self.image_markers.push(ImageMarker {
+            protocol,
+            row: self.cursor_pos.0,
+            col: self.cursor_pos.1,
+        });
+    }
+
+    /// Erases columns `cols` of `row`, leaving blanks with the current background.
+    fn erase_cells(&mut self, row: u16, cols: std::ops::Range<u16>) {
+        if row >= self.height {
+            return;
+        }
+        let blank = Cell { bg: self.current_bg, ..Cell::default() };
+        let extras = CellExtras {
+            bg_rgb: self.current_bg_rgb,
+            ..CellExtras::default()
+        };
+        let end = cols.end.min(self.width) as usize;
+        let start = (cols.start as usize).min(end);
+        self.cells[row as usize][start..end].fill(blank);
+        self.extras[row as usize][start..end].fill(extras);
+        self.written[row as usize][start..end].fill(false);
+        self.dirty[row as usize] = true;
+        if start == 0 && end == self.width as usize {
+            self.wrapped[row as usize] = false;
+        }
+        self.drop_sixels(row, start as u16..end as u16);
+    }
+
+    /// Removes Sixel images covering any of columns `cols` of `row`, as erasing
+    /// the cells under an image removes it.
+    fn drop_sixels(&mut self, row: u16, cols: std::ops::Range<u16>) {
+        if self.sixel_regions.is_empty() || cols.is_empty() {
+            return;
+        }
+        let keep: Vec<bool> = self
+            .sixel_regions
+            .iter()
+            .map(|region| {
+                let (width, height) = Self::pixels_to_cells(region.width, region.height);
+                let rows = region.start_row..region.start_row.saturating_add(height);
+                let covered = region.start_col..region.start_col.saturating_add(width);
+                !(rows.contains(&row) && covered.start < cols.end && cols.start < covered.end)
+            })
+            .collect();
+        if keep.iter().all(|&k| k) {
+            return;
+        }
+        self.sixel_stream_ranges
+            .resize(self.sixel_regions.len(), None);
+        let mut flags = keep.iter();
+        self.sixel_regions.retain(|_| *flags.next().unwrap());
+        let mut flags = keep.iter();
+        self.sixel_stream_ranges.retain(|_| *flags.next().unwrap());
     }
 
     fn move_cursor(&mut self, row: u16, col: u16) {
+        self.wrap_pending = false;
         self.cursor_pos = (row.min(self.height - 1), col.min(self.width - 1));
     }
 
+    /// Applies a DEC private mode set (`CSI ? Pm h`) or reset (`CSI ? Pm l`).
+    fn set_dec_mode(&mut self, mode: i64, enable: bool) {
+        match mode {
+            // Alternate screen buffer: 47 and 1047 switch buffers, 1049 also
+            // saves and restores the cursor. 1047/1049 start from a blank screen.
+            47 | 1047 | 1049 => {
+                if enable && !self.alt_screen_active {
+                    if mode == 1049 {
+                        self.saved_cursor = self.cursor_pos;
+                    }
+                    std::mem::swap(&mut self.cells, &mut self.inactive_cells);
+                    std::mem::swap(&mut self.written, &mut self.inactive_written);
+                    std::mem::swap(&mut self.extras, &mut self.inactive_extras);
+                    std::mem::swap(&mut self.wrapped, &mut self.inactive_wrapped);
+                    std::mem::swap(&mut self.frame_writes, &mut self.inactive_frame_writes);
+                    if mode != 47 {
+                        for row in &mut self.cells {
+                            row.fill(Cell::default());
+                        }
+                        for row in &mut self.written {
+                            row.fill(false);
+                        }
+                        for row in &mut self.extras {
+                            row.fill(CellExtras::default());
+                        }
+                        for row in &mut self.frame_writes {
+                            row.fill(0);
+                        }
+                        self.wrapped.fill(false);
+                    }
+                    self.dirty.fill(true);
+                    self.alt_screen_active = true;
+                    self.alt_screen_entries += 1;
+                } else if !enable && self.alt_screen_active {
+                    std::mem::swap(&mut self.cells, &mut self.inactive_cells);
+                    std::mem::swap(&mut self.written, &mut self.inactive_written);
+                    std::mem::swap(&mut self.extras, &mut self.inactive_extras);
+                    std::mem::swap(&mut self.wrapped, &mut self.inactive_wrapped);
+                    std::mem::swap(&mut self.frame_writes, &mut self.inactive_frame_writes);
+                    if mode == 1049 {
+                        self.cursor_pos = self.saved_cursor;
+                    }
+                    self.dirty.fill(true);
+                    self.alt_screen_active = false;
+                }
+            }
+            7 => {
+                self.autowrap = enable;
+                self.wrap_pending = false;
+            }
+            25 => self.cursor_visible = enable,
+            9 | 1000 | 1002 | 1003 => {
+                let mode = MouseTrackingMode::from_mode(mode);
+                if enable {
+                    self.mouse_tracking = mode;
+                } else if self.mouse_tracking == mode {
+                    self.mouse_tracking = None;
+                }
+            }
+            2004 => self.bracketed_paste = enable,
+            _ => {}
+        }
+    }
+
     /// Parse raster attributes from sixel data.
     ///
     /// Sixel raster attributes follow the format: "Pan;Pad;Ph;Pv
@@ -627,20 +1488,24 @@ impl TerminalState {
 
 impl VTActor for TerminalState {
     fn print(&mut self, ch: char) {
+        self.trace_cursor();
         self.put_char(ch);
     }
 
     fn execute_c0_or_c1(&mut self, control: u8) {
+        self.trace_cursor();
+        if matches!(control, b'\r' | b'\n' | b'\t' | 0x08) {
+            self.wrap_pending = false;
+        }
         match control {
             b'\r' => {
                 // Carriage return
                 self.cursor_pos.1 = 0;
             }
             b'\n' => {
-                // Line feed
-                if self.cursor_pos.0 + 1 < self.height {
-                    self.cursor_pos.0 += 1;
-                }
+                // Line feed, scrolling when at the bottom of the scrolling region
+                self.line_feeds += 1;
+                self.index();
             }
             b'\t' => {
                 // Tab - advance to next tab stop (every 8 columns)
@@ -655,12 +1520,20 @@ impl VTActor for TerminalState {
         &mut self,
         mode: u8,
         params: &[i64],
-        _intermediates: &[u8],
+        intermediates: &[u8],
         _ignored_excess_intermediates: bool,
     ) {
+        // DECRQSS shares the final byte with Sixel but has a '$' intermediate
+        if mode == b'q' && intermediates == b"$" {
+            self.in_decrqss = true;
+            self.current_decrqss.clear();
+            return;
+        }
+
         // Sixel sequences are identified by mode byte 'q' (0x71)
         if mode == b'q' {
             self.in_sixel_mode = true;
+            self.current_sixel_start = self.last_esc_offset;
             self.current_sixel_data.clear();
             self.current_sixel_params = params.to_vec();
         }
@@ -669,16 +1542,34 @@ impl VTActor for TerminalState {
     fn dcs_put(&mut self, byte: u8) {
         if self.in_sixel_mode {
             self.current_sixel_data.push(byte);
+        } else if self.in_decrqss {
+            self.current_decrqss.push(byte);
         }
     }
 
     fn dcs_unhook(&mut self) {
+        if self.in_decrqss {
+            let setting = String::from_utf8_lossy(&self.current_decrqss).into_owned();
+            self.queries.push(TerminalQuery::Setting(setting));
+            self.in_decrqss = false;
+            self.current_decrqss.clear();
+        }
+
         if self.in_sixel_mode {
             // Parse dimensions from raster attributes if present
             let (width, height) = self
                 .parse_raster_attributes(&self.current_sixel_data)
                 .unwrap_or((0, 0));
 
+            // The parser unhooks on the first byte of a segment (see `feed`). For
+            // the ESC of a 7-bit `ESC \` terminator the range extends over the
+            // following backslash too.
+            let end = if self.last_esc_offset == self.stream_offset {
+                self.stream_offset + 2
+            } else {
+                self.stream_offset + 1
+            };
+
             let region = SixelRegion {
                 start_row: self.cursor_pos.0,
                 start_col: self.cursor_pos.1,
@@ -686,7 +1577,12 @@ impl VTActor for TerminalState {
                 height,
                 data: self.current_sixel_data.clone(),
             };
+            self.sixel_stream_ranges
+                .resize(self.sixel_regions.len(), None);
             self.sixel_regions.push(region);
+            self.sixel_stream_ranges
+                .push(Some(self.current_sixel_start..end));
+            self.mark_image(ImageProtocol::Sixel);
 
             self.in_sixel_mode = false;
             self.current_sixel_data.clear();
@@ -695,6 +1591,22 @@ impl VTActor for TerminalState {
     }
 
     fn csi_dispatch(&mut self, params: &[CsiParam], _truncated: bool, byte: u8) {
+        self.trace_cursor();
+        // Anything but SGR cancels a pending wrap
+        if byte != b'm' {
+            self.wrap_pending = false;
+        }
+
+        // DEC private modes carry a '?' marker: ESC [ ? Pm h / ESC [ ? Pm l
+        if params.first() == Some(&CsiParam::P(b'?')) {
+            if byte == b'h' || byte == b'l' {
+                for mode in params.iter().filter_map(|p| p.as_integer()) {
+                    self.set_dec_mode(mode, byte == b'h');
+                }
+            }
+            return;
+        }
+
         match byte {
             b'H' | b'f' => {
                 // CUP - Cursor Position ESC [ row ; col H
@@ -706,26 +1618,119 @@ impl VTActor for TerminalState {
                 let col = integers.get(1).copied().unwrap_or(1).saturating_sub(1) as u16;
 
                 self.move_cursor(row, col);
+                self.absolute_moves += 1;
             }
             b'A' => {
                 // CUU - Cursor Up
                 let n = params.iter().find_map(|p| p.as_integer()).unwrap_or(1) as u16;
                 self.cursor_pos.0 = self.cursor_pos.0.saturating_sub(n);
+                self.relative_moves += 1;
             }
             b'B' => {
                 // CUD - Cursor Down
                 let n = params.iter().find_map(|p| p.as_integer()).unwrap_or(1) as u16;
                 self.cursor_pos.0 = (self.cursor_pos.0 + n).min(self.height - 1);
+                self.relative_moves += 1;
             }
             b'C' => {
                 // CUF - Cursor Forward
                 let n = params.iter().find_map(|p| p.as_integer()).unwrap_or(1) as u16;
                 self.cursor_pos.1 = (self.cursor_pos.1 + n).min(self.width - 1);
+                self.relative_moves += 1;
             }
             b'D' => {
                 // CUB - Cursor Back
                 let n = params.iter().find_map(|p| p.as_integer()).unwrap_or(1) as u16;
                 self.cursor_pos.1 = self.cursor_pos.1.saturating_sub(n);
+                self.relative_moves += 1;
+            }
+            b'J' => {
+                // ED - Erase in Display: 0 = to end, 1 = to cursor, 2 = all,
+                // 3 = scrollback only
+                let mode = params.iter().find_map(|p| p.as_integer()).unwrap_or(0);
+                let (row, col) = self.cursor_pos;
+                match mode {
+                    0 => {
+                        self.erase_cells(row, col..self.width);
+                        for r in row + 1..self.height {
+                            self.erase_cells(r, 0..self.width);
+                        }
+                        if (row, col) == (0, 0) {
+                            self.erase_display_count += 1;
+                        }
+                    }
+                    1 => {
+                        for r in 0..row {
+                            self.erase_cells(r, 0..self.width);
+                        }
+                        self.erase_cells(row, 0..col.saturating_add(1));
+                    }
+                    2 => {
+                        for r in 0..self.height {
+                            self.erase_cells(r, 0..self.width);
+                        }
+                        // Also drops images whose size is unknown
+                        self.sixel_regions.clear();
+                        self.sixel_stream_ranges.clear();
+                        self.erase_display_count += 1;
+                        self.full_redraws += 1;
+                    }
+                    3 => {
+                        self.scrollback.clear();
+                        self.full_redraws += 1;
+                    }
+                    _ => {}
+                }
+            }
+            b'K' => {
+                // EL - Erase in Line: 0 = to end, 1 = to cursor, 2 = whole line
+                let mode = params.iter().find_map(|p| p.as_integer()).unwrap_or(0);
+                let (row, col) = self.cursor_pos;
+                match mode {
+                    0 => self.erase_cells(row, col..self.width),
+                    1 => self.erase_cells(row, 0..col.saturating_add(1)),
+                    2 => self.erase_cells(row, 0..self.width),
+                    _ => {}
+                }
+            }
+            b'n' if params.iter().find_map(|p| p.as_integer()) == Some(6) => {
+                // DSR - Device Status Report; 6 requests the cursor position
+                let (row, col) = self.cursor_pos;
+                self.queries.push(TerminalQuery::CursorPosition(row, col));
+            }
+            b'c' => {
+                // DA - Device Attributes: ESC [ c (primary), ESC [ > c (secondary)
+                let secondary = params.first() == Some(&CsiParam::P(b'>'));
+                if params.iter().find_map(|p| p.as_integer()).unwrap_or(0) == 0 {
+                    self.queries.push(if secondary {
+                        TerminalQuery::SecondaryAttributes
+                    } else {
+                        TerminalQuery::PrimaryAttributes
+                    });
+                }
+            }
+            b'r' => {
+                // DECSTBM - Set Top and Bottom Margins ESC [ top ; bottom r
+                let integers: Vec<i64> = params.iter().filter_map(|p| p.as_integer()).collect();
+                let top = integers.first().copied().filter(|&t| t > 0).unwrap_or(1);
+                let bottom = integers
+                    .get(1)
+                    .copied()
+                    .filter(|&b| b > 0)
+                    .unwrap_or(self.height as i64)
+                    .min(self.height as i64);
+                if top < bottom {
+                    self.scroll_top = (top - 1) as u16;
+                    self.scroll_bottom = (bottom - 1) as u16;
+                    self.move_cursor(0, 0);
+                }
+            }
+            b'q' if params.last() == Some(&CsiParam::P(b' ')) => {
+                // DECSCUSR - Set Cursor Style ESC [ Ps SP q
+                let param = params.iter().find_map(|p| p.as_integer()).unwrap_or(0);
+                if let Some(shape) = CursorShape::from_param(param) {
+                    self.cursor_shapes.push(shape);
+                }
             }
             b'm' => {
                 // SGR - Select Graphic Rendition (colors and attributes)
@@ -735,9 +1740,13 @@ impl VTActor for TerminalState {
                 if integers.is_empty() {
                     self.current_fg = None;
                     self.current_bg = None;
+                    self.current_fg_rgb = None;
+                    self.current_bg_rgb = None;
                     self.current_bold = false;
                     self.current_italic = false;
                     self.current_underline = false;
+                    self.current_inverse = false;
+                    self.current_blink = false;
                     return;
                 }
 
@@ -748,36 +1757,57 @@ impl VTActor for TerminalState {
                             // Reset all attributes
                             self.current_fg = None;
                             self.current_bg = None;
+                            self.current_fg_rgb = None;
+                            self.current_bg_rgb = None;
                             self.current_bold = false;
                             self.current_italic = false;
                             self.current_underline = false;
+                            self.current_inverse = false;
+                            self.current_blink = false;
                         }
                         1 => self.current_bold = true,
                         3 => self.current_italic = true,
                         4 => self.current_underline = true,
+                        5 | 6 => self.current_blink = true,
+                        7 => self.current_inverse = true,
                         22 => self.current_bold = false,
                         23 => self.current_italic = false,
                         24 => self.current_underline = false,
+                        25 => self.current_blink = false,
+                        27 => self.current_inverse = false,
                         // Foreground colors (30-37: standard, 90-97: bright)
-                        30..=37 => self.current_fg = Some((integers[i] - 30) as u8),
-                        90..=97 => self.current_fg = Some((integers[i] - 90 + 8) as u8),
-                        39 => self.current_fg = None, // Default foreground
+                        30..=37 => self.set_fg(Some((integers[i] - 30) as u8)),
+                        90..=97 => self.set_fg(Some((integers[i] - 90 + 8) as u8)),
+                        39 => self.set_fg(None), // Default foreground
                         // Background colors (40-47: standard, 100-107: bright)
-                        40..=47 => self.current_bg = Some((integers[i] - 40) as u8),
-                        100..=107 => self.current_bg = Some((integers[i] - 100 + 8) as u8),
-                        49 => self.current_bg = None, // Default background
+                        40..=47 => self.set_bg(Some((integers[i] - 40) as u8)),
+                        100..=107 => self.set_bg(Some((integers[i] - 100 + 8) as u8)),
+                        49 => self.set_bg(None), // Default background
                         // 256-color mode: ESC[38;5;N or ESC[48;5;N
-                        38 | 48 => {
-                            if i + 2 < integers.len() && integers[i + 1] == 5 {
-                                let color = integers[i + 2] as u8;
+                        38 | 48 if integers.get(i + 1) == Some(&5) => {
+                            if let Some(&color) = integers.get(i + 2) {
                                 if integers[i] == 38 {
-                                    self.current_fg = Some(color);
+                                    self.set_fg(Some(color as u8));
                                 } else {
-                                    self.current_bg = Some(color);
+                                    self.set_bg(Some(color as u8));
                                 }
                                 i += 2; // Skip the '5' and color value
                             }
                         }
+                        // Truecolor mode: ESC[38;2;R;G;B or ESC[48;2;R;G;B
+                        38 | 48 if integers.get(i + 1) == Some(&2) => {
+                            if let Some(rgb) = integers.get(i + 2..i + 5) {
+                                let rgb = Some((rgb[0] as u8, rgb[1] as u8, rgb[2] as u8));
+                                if integers[i] == 38 {
+                                    self.current_fg = None;
+                                    self.current_fg_rgb = rgb;
+                                } else {
+                                    self.current_bg = None;
+                                    self.current_bg_rgb = rgb;
+                                }
+                                i += 4; // Skip the '2' and color values
+                            }
+                        }
                         _ => {} // Ignore unknown SGR codes
                     }
                     i += 1;
@@ -794,18 +1824,15 @@ impl VTActor for TerminalState {
         _ignored_excess_intermediates: bool,
         byte: u8,
     ) {
+        self.trace_cursor();
         match byte {
             b'D' => {
                 // IND - Index (move cursor down)
-                if self.cursor_pos.0 + 1 < self.height {
-                    self.cursor_pos.0 += 1;
-                }
+                self.index();
             }
             b'E' => {
                 // NEL - Next Line
-                if self.cursor_pos.0 + 1 < self.height {
-                    self.cursor_pos.0 += 1;
-                }
+                self.index();
                 self.cursor_pos.1 = 0;
             }
             _ => {}
@@ -819,6 +1846,40 @@ impl VTActor for TerminalState {
             return;
         }
 
+        // OSC 0 (icon name and title) and OSC 2 (title) set the window title
+        if params[0] == b"0" || params[0] == b"2" {
+            let title = params[1..]
+                .iter()
+                .map(|part| String::from_utf8_lossy(part))
+                .collect::<Vec<_>>()
+                .join(";");
+            self.window_title = Some(title);
+            return;
+        }
+
+        // OSC 8 ; params ; URI opens a hyperlink; an empty URI closes it
+        if params[0] == b"8" {
+            let uri = params
+                .get(2..)
+                .unwrap_or_default()
+                .iter()
+                .map(|part| String::from_utf8_lossy(part))
+                .collect::<Vec<_>>()
+                .join(";");
+            if uri.is_empty() {
+                self.open_hyperlink = None;
+            } else {
+                self.hyperlinks.push(Hyperlink {
+                    uri,
+                    row: self.cursor_pos.0,
+                    col: self.cursor_pos.1,
+                    text: String::new(),
+                });
+                self.open_hyperlink = Some(self.hyperlinks.len() - 1);
+            }
+            return;
+        }
+
         // Check if this is an iTerm2 inline image (OSC 1337;File=...)
         if let Ok(first_param) = std::str::from_utf8(params[0]) {
             if first_param.starts_with("1337;File=") || first_param == "1337" {
@@ -844,6 +1905,7 @@ impl VTActor for TerminalState {
                     data: self.current_iterm2_data.clone(),
                 };
                 self.iterm2_regions.push(region);
+                self.mark_image(ImageProtocol::ITerm2);
 
                 self.in_iterm2_mode = false;
                 self.current_iterm2_data.clear();
@@ -876,6 +1938,7 @@ impl VTActor for TerminalState {
                 data: self.current_kitty_data.clone(),
             };
             self.kitty_regions.push(region);
+            self.mark_image(ImageProtocol::Kitty);
 
             self.in_kitty_mode = false;
             self.current_kitty_data.clear();
@@ -922,6 +1985,100 @@ pub struct ScreenState {
     state: TerminalState,
     width: u16,
     height: u16,
+    /// RGB values used to resolve indexed colors in [`rgb_at()`](Self::rgb_at).
+    palette: [(u8, u8, u8); 256],
+}
+
+/// Returns the number of columns a character occupies in the terminal.
+///
+/// East Asian wide and fullwidth characters and most emoji take two columns;
+/// combining marks and other zero-width characters take none.
+fn char_width(ch: char) -> u16 {
+    unicode_width::UnicodeWidthChar::width(ch).unwrap_or(1) as u16
+}
+
+/// Iterates over the cells of a row with their columns, skipping the blank
+/// spacer that fills the second column of each wide glyph.
+fn glyph_cells(row: &[Cell]) -> impl Iterator<Item = (usize, &Cell)> {
+    let mut after_wide = false;
+    row.iter().enumerate().filter(move |(_, cell)| {
+        let spacer = after_wide && cell.c == ' ';
+        after_wide = !spacer && char_width(cell.c) == 2;
+        !spacer
+    })
+}
+
+/// Maps a ratatui color to the palette index stored in cells.
+#[cfg(feature = "ratatui-helpers")]
+fn ratatui_color_index(color: ratatui::style::Color) -> Option<u8> {
+    use ratatui::style::Color as C;
+
+    match color {
+        C::Reset | C::Rgb(..) => None,
+        C::Black => Some(0),
+        C::Red => Some(1),
+        C::Green => Some(2),
+        C::Yellow => Some(3),
+        C::Blue => Some(4),
+        C::Magenta => Some(5),
+        C::Cyan => Some(6),
+        C::Gray => Some(7),
+        C::DarkGray => Some(8),
+        C::LightRed => Some(9),
+        C::LightGreen => Some(10),
+        C::LightYellow => Some(11),
+        C::LightBlue => Some(12),
+        C::LightMagenta => Some(13),
+        C::LightCyan => Some(14),
+        C::White => Some(15),
+        C::Indexed(n) => Some(n),
+    }
+}
+
+/// Returns the 24-bit value of a ratatui RGB color.
+#[cfg(feature = "ratatui-helpers")]
+fn ratatui_rgb(color: ratatui::style::Color) -> Option<(u8, u8, u8)> {
+    match color {
+        ratatui::style::Color::Rgb(r, g, b) => Some((r, g, b)),
+        _ => None,
+    }
+}
+
+/// Builds the standard xterm 256-color palette.
+///
+/// Indices 0-15 are the ANSI colors, 16-231 a 6x6x6 color cube and 232-255 a
+/// grayscale ramp.
+fn default_palette() -> [(u8, u8, u8); 256] {
+    const ANSI: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    let mut palette = [(0, 0, 0); 256];
+    palette[..16].copy_from_slice(&ANSI);
+    for (i, entry) in palette[16..232].iter_mut().enumerate() {
+        *entry = (CUBE_LEVELS[i / 36], CUBE_LEVELS[(i / 6) % 6], CUBE_LEVELS[i % 6]);
+    }
+    for (i, entry) in palette[232..].iter_mut().enumerate() {
+        let level = 8 + 10 * i as u8;
+        *entry = (level, level, level);
+    }
+    palette
 }
 
 impl ScreenState {
@@ -947,115 +2104,106 @@ impl ScreenState {
         let parser = VTParser::new();
         let state = TerminalState::new(width, height);
 
-        Self { parser, state, width, height }
+        Self {
+            parser,
+            state,
+            width,
+            height,
+            palette: default_palette(),
+        }
     }
 
-    /// Feeds data from the PTY to the parser.
-    ///
-    /// This processes VT100/ANSI escape sequences and updates the screen state,
-    /// including:
-    /// - Text output
-    /// - Cursor movements
-    /// - Sixel graphics (tracked via DCS callbacks)
+    /// Creates a new screen state that keeps lines scrolled off the top.
     ///
-    /// This method can be called multiple times to incrementally feed data.
-    /// The parser maintains state across calls, so partial escape sequences
-    /// are handled correctly.
+    /// [`new()`](Self::new) discards lines once they scroll off the screen. With a
+    /// scrollback buffer, up to `scrollback_lines` of the most recent ones are kept
+    /// and available from [`scrollback_contents()`](Self::scrollback_contents),
+    /// which is useful for testing pagers and log viewers. Lines scrolled off the
+    /// alternate screen are not kept, matching real terminals.
     ///
     /// # Arguments
     ///
-    /// * `data` - Raw bytes from PTY output
+    /// * `width` - Screen width in columns
+    /// * `height` - Screen height in rows
+    /// * `scrollback_lines` - Maximum number of off-screen lines to keep
     ///
     /// # Example
     ///
     /// ```rust
     /// use ratatui_testlib::ScreenState;
     ///
-    /// let mut screen = ScreenState::new(80, 24);
-    ///
-    /// // Feed data incrementally
-    /// screen.feed(b"Hello, ");
-    /// screen.feed(b"World!");
+    /// let mut screen = ScreenState::with_scrollback(20, 2, 100);
+    /// screen.feed(b"line 1\r\nline 2\r\nline 3");
     ///
-    /// assert!(screen.contains("Hello, World!"));
+    /// assert!(!screen.contains("line 1"));
+    /// assert!(screen.scrollback_contents().contains("line 1"));
     /// ```
-    pub fn feed(&mut self, data: &[u8]) {
-        self.parser.parse(data, &mut self.state);
+    pub fn with_scrollback(width: u16, height: u16, scrollback_lines: usize) -> Self {
+        let mut screen = Self::new(width, height);
+        screen.state.scrollback_limit = scrollback_lines;
+        screen
     }
 
-    /// Returns the screen contents as a string.
+    /// Creates a new screen state that resolves indexed colors through a custom palette.
     ///
-    /// This includes all visible characters, preserving layout with newlines
-    /// between rows. Empty cells are represented as spaces.
+    /// By default [`rgb_at()`](Self::rgb_at) uses the standard xterm palette. Pass
+    /// the palette of a specific terminal theme to make color assertions (such as
+    /// contrast checks) match what users of that theme see.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A string containing the entire screen contents, with rows separated by newlines.
+    /// * `width` - Screen width in columns
+    /// * `height` - Screen height in rows
+    /// * `palette` - RGB values for color indices 0-255
     ///
     /// # Example
     ///
     /// ```rust
     /// use ratatui_testlib::ScreenState;
     ///
-    /// let mut screen = ScreenState::new(10, 3);
-    /// screen.feed(b"Hello");
+    /// let mut palette = [(0, 0, 0); 256];
+    /// palette[1] = (220, 50, 47); // Solarized red
     ///
-    /// let contents = screen.contents();
-    /// // First line contains "Hello     " (padded to 10 chars)
-    /// // Second and third lines are all spaces
-    /// assert!(contents.contains("Hello"));
+    /// let mut screen = ScreenState::with_palette(80, 24, palette);
+    /// screen.feed(b"\x1b[31mError");
+    ///
+    /// assert_eq!(screen.rgb_at(0, 0), Some((220, 50, 47)));
     /// ```
-    pub fn contents(&self) -> String {
-        self.state
-            .cells
-            .iter()
-            .map(|row| row.iter().map(|cell| cell.c).collect::<String>())
-            .collect::<Vec<_>>()
-            .join("\n")
+    pub fn with_palette(width: u16, height: u16, palette: [(u8, u8, u8); 256]) -> Self {
+        Self { palette, ..Self::new(width, height) }
     }
 
-    /// Returns the contents of a specific row.
-    ///
-    /// # Arguments
+    /// Creates a new screen state and feeds it a string.
     ///
-    /// * `row` - Row index (0-based)
+    /// Shorthand for [`new()`](Self::new) followed by
+    /// [`feed_str()`](Self::feed_str).
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// The row contents as a string, or empty string if row is out of bounds.
-    pub fn row_contents(&self, row: u16) -> String {
-        if row < self.height {
-            self.state.cells[row as usize]
-                .iter()
-                .map(|cell| cell.c)
-                .collect()
-        } else {
-            String::new()
-        }
-    }
-
-    /// Returns the character at a specific position.
+    /// * `width` - Screen width in columns
+    /// * `height` - Screen height in rows
+    /// * `s` - Output to feed, including any escape sequences
     ///
-    /// # Arguments
+    /// # Example
     ///
-    /// * `row` - Row index (0-based)
-    /// * `col` - Column index (0-based)
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
     ///
-    /// # Returns
+    /// let screen = ScreenState::from_str(20, 3, "Größe: 42\r\n\x1b[1mOK");
     ///
-    /// The character at the position, or None if out of bounds.
-    pub fn text_at(&self, row: u16, col: u16) -> Option<char> {
-        if row < self.height && col < self.width {
-            Some(self.state.cells[row as usize][col as usize].c)
-        } else {
-            None
-        }
+    /// assert_eq!(screen.lines(), vec!["Größe: 42", "OK"]);
+    /// ```
+    pub fn from_str(width: u16, height: u16, s: &str) -> Self {
+        let mut screen = Self::new(width, height);
+        screen.feed_str(s);
+        screen
     }
 
-    /// Returns the complete cell (character + attributes) at a specific position.
+    /// Returns the foreground color at a position as RGB.
     ///
-    /// This method provides access to the full cell state including colors and
-    /// text attributes, enabling verification of ANSI escape sequence handling.
+    /// Indexed colors (SGR 30-37, 90-97 and `38;5;N`) are resolved through the
+    /// screen's palette; see [`with_palette()`](Self::with_palette). 24-bit
+    /// colors (`38;2;R;G;B`) are returned as is.
     ///
     /// # Arguments
     ///
@@ -1064,7 +2212,8 @@ impl ScreenState {
     ///
     /// # Returns
     ///
-    /// A reference to the cell, or None if out of bounds.
+    /// The RGB color, or `None` if the cell uses the default foreground or the
+    /// position is out of bounds.
     ///
     /// # Example
     ///
@@ -1072,74 +2221,114 @@ impl ScreenState {
     /// use ratatui_testlib::ScreenState;
     ///
     /// let mut screen = ScreenState::new(80, 24);
-    /// screen.feed(b"\x1b[31mRed\x1b[0m");
+    /// screen.feed(b"\x1b[38;5;196mAlert\x1b[0m plain");
     ///
-    /// if let Some(cell) = screen.get_cell(0, 0) {
-    ///     assert_eq!(cell.c, 'R');
-    ///     assert_eq!(cell.fg, Some(1)); // Red = color 1
-    /// }
+    /// assert_eq!(screen.rgb_at(0, 0), Some((255, 0, 0)));
+    /// assert_eq!(screen.rgb_at(0, 6), None);
     /// ```
-    pub fn get_cell(&self, row: u16, col: u16) -> Option<&Cell> {
-        if row < self.height && col < self.width {
-            Some(&self.state.cells[row as usize][col as usize])
-        } else {
-            None
-        }
+    pub fn rgb_at(&self, row: u16, col: u16) -> Option<(u8, u8, u8)> {
+        let style = self.state.style_at(row, col)?;
+        style
+            .fg_rgb
+            .or_else(|| style.fg.map(|index| self.palette[index as usize]))
     }
 
-    /// Returns the current cursor position.
+    /// Splits a row into runs of adjacent cells with identical attributes.
     ///
-    /// # Returns
+    /// Trailing blank cells with default attributes are omitted, so a row of
+    /// plain text followed by empty space yields a single run. A wide glyph
+    /// contributes one character to the text, like in
+    /// [`row_contents()`](Self::row_contents), so `start_col` can be more than
+    /// the character count of earlier runs.
     ///
-    /// A tuple of (row, col) with 0-based indexing.
-    pub fn cursor_position(&self) -> (u16, u16) {
-        self.state.cursor_pos
-    }
-
-    /// Returns the screen dimensions.
+    /// # Arguments
     ///
-    /// # Returns
+    /// * `row` - Row index (0-based)
     ///
-    /// A tuple of (width, height) in columns and rows.
-    pub fn size(&self) -> (u16, u16) {
-        (self.width, self.height)
-    }
-
-    /// Returns the screen width in columns.
+    /// # Returns
     ///
-    /// This is a convenience method equivalent to `self.size().0`.
+    /// The runs from left to right, or an empty vector if the row is blank or
+    /// out of bounds.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use ratatui_testlib::ScreenState;
+    /// use ratatui_testlib::{Color, ScreenState};
     ///
-    /// let screen = ScreenState::new(80, 24);
-    /// assert_eq!(screen.cols(), 80);
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[32mOK\x1b[0m done");
+    ///
+    /// let runs = screen.styled_runs(0);
+    /// assert_eq!(runs.len(), 2);
+    /// assert_eq!(runs[0].text, "OK");
+    /// assert_eq!(runs[0].fg, Color::Indexed(2));
+    /// assert_eq!(runs[1].text, " done");
+    /// assert_eq!(runs[1].start_col, 2);
     /// ```
-    pub fn cols(&self) -> u16 {
-        self.width
+    pub fn styled_runs(&self, row: u16) -> Vec<StyledRun> {
+        let Some(cells) = self.state.cells.get(row as usize) else {
+            return Vec::new();
+        };
+        let styles = self.state.row_styles(row as usize);
+        let len = cells
+            .iter()
+            .zip(&styles)
+            .rposition(|(cell, style)| cell.c != ' ' || *style != CellStyle::default())
+            .map_or(0, |last| last + 1);
+
+        let mut runs: Vec<StyledRun> = Vec::new();
+        for (col, cell) in glyph_cells(&cells[..len]) {
+            let style = styles[col];
+            match runs.last_mut() {
+                Some(run) if run.style == style => run.text.push(cell.c),
+                _ => runs.push(StyledRun {
+                    text: cell.c.to_string(),
+                    fg: style.fg_color(),
+                    bg: style.bg_color(),
+                    style,
+                    start_col: col as u16,
+                }),
+            }
+        }
+        runs
     }
 
-    /// Returns the screen height in rows.
+    /// Returns the text attributes at a specific position.
     ///
-    /// This is a convenience method equivalent to `self.size().1`.
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// The attributes, or None if out of bounds (like [`text_at`](Self::text_at)).
     ///
     /// # Example
     ///
     /// ```rust
     /// use ratatui_testlib::ScreenState;
     ///
-    /// let screen = ScreenState::new(80, 24);
-    /// assert_eq!(screen.rows(), 24);
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[1;7m Tab 1 \x1b[0m Tab 2 ");
+    ///
+    /// let active = screen.attributes_at(0, 1).unwrap();
+    /// assert!(active.bold && active.inverse);
+    /// assert!(!screen.attributes_at(0, 8).unwrap().bold);
     /// ```
-    pub fn rows(&self) -> u16 {
-        self.height
+    pub fn attributes_at(&self, row: u16, col: u16) -> Option<CellAttributes> {
+        self.state.style_at(row, col).map(|style| CellAttributes {
+            bold: style.bold,
+            italic: style.italic,
+            underline: style.underline,
+            inverse: style.inverse,
+            blink: style.blink,
+        })
     }
 
-    /// Returns an iterator over all rows in the screen.
+    /// Returns whether the cell at a specific position is bold.
     ///
-    /// Each item in the iterator is a reference to a row (a slice of cells).
+    /// Out-of-bounds positions are not bold.
     ///
     /// # Example
     ///
@@ -1147,103 +2336,82 @@ impl ScreenState {
     /// use ratatui_testlib::ScreenState;
     ///
     /// let mut screen = ScreenState::new(80, 24);
-    /// screen.feed(b"Line 1\r\nLine 2");
+    /// screen.feed(b"\x1b[1mActive\x1b[0m Inactive");
     ///
-    /// for (row_idx, row) in screen.iter_rows().enumerate() {
-    ///     println!("Row {}: {} cells", row_idx, row.len());
-    ///     for (col_idx, cell) in row.iter().enumerate() {
-    ///         if cell.c != ' ' {
-    ///             println!("  Cell ({}, {}): '{}'", row_idx, col_idx, cell.c);
-    ///         }
-    ///     }
-    /// }
+    /// assert!(screen.is_bold_at(0, 0));
+    /// assert!(!screen.is_bold_at(0, 7));
     /// ```
-    pub fn iter_rows(&self) -> impl Iterator<Item = &[Cell]> {
-        self.state.cells.iter().map(|row| row.as_slice())
+    pub fn is_bold_at(&self, row: u16, col: u16) -> bool {
+        self.get_cell(row, col).is_some_and(|cell| cell.bold)
     }
 
-    /// Returns an iterator over all cells in a specific row.
-    ///
-    /// Returns `None` if the row index is out of bounds.
+    /// Returns the foreground color at a specific position.
     ///
     /// # Arguments
     ///
     /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// The color, or None if out of bounds (like [`text_at`](Self::text_at)).
     ///
     /// # Example
     ///
     /// ```rust
-    /// use ratatui_testlib::ScreenState;
+    /// use ratatui_testlib::{Color, ScreenState};
     ///
     /// let mut screen = ScreenState::new(80, 24);
-    /// screen.feed(b"\x1b[31mRed\x1b[32mGreen\x1b[34mBlue");
-    ///
-    /// // Use the iterator immediately to avoid lifetime issues
-    /// let colored_cells: Vec<_> = screen
-    ///     .iter_row(0)
-    ///     .unwrap()
-    ///     .enumerate()
-    ///     .filter(|(_, cell)| cell.fg.is_some())
-    ///     .collect();
+    /// screen.feed(b"\x1b[31mError\x1b[0m ok");
     ///
-    /// assert!(colored_cells.len() >= 3, "Should have colored cells");
+    /// assert_eq!(screen.fg_color_at(0, 0), Some(Color::Indexed(1)));
+    /// assert_eq!(screen.fg_color_at(0, 6), Some(Color::Default));
+    /// assert_eq!(screen.fg_color_at(24, 0), None);
     /// ```
-    pub fn iter_row(&self, row: u16) -> Option<impl Iterator<Item = &Cell>> {
-        if row < self.height {
-            Some(self.state.cells[row as usize].iter())
-        } else {
-            None
-        }
+    pub fn fg_color_at(&self, row: u16, col: u16) -> Option<Color> {
+        self.state.style_at(row, col).map(|style| style.fg_color())
     }
 
-    /// Returns a structured snapshot of the entire screen grid.
+    /// Returns the background color at a specific position.
     ///
-    /// This method provides a complete copy of the screen state suitable for
-    /// deep comparison with other terminal emulators or for serialization.
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
     ///
     /// # Returns
     ///
-    /// A `GridSnapshot` containing:
-    /// - Grid dimensions (width, height)
-    /// - Complete cell data (2D vector of cells)
-    /// - Current cursor position
+    /// The color, or None if out of bounds (like [`text_at`](Self::text_at)).
     ///
     /// # Example
     ///
     /// ```rust
-    /// use ratatui_testlib::ScreenState;
+    /// use ratatui_testlib::{Color, ScreenState};
     ///
     /// let mut screen = ScreenState::new(80, 24);
-    /// screen.feed(b"\x1b[31mHello");
+    /// screen.feed(b"\x1b[44m> selected\x1b[0m");
     ///
-    /// let snapshot = screen.snapshot();
-    /// assert_eq!(snapshot.width, 80);
-    /// assert_eq!(snapshot.height, 24);
-    /// assert_eq!(snapshot.cells[0][0].c, 'H');
-    /// assert_eq!(snapshot.cells[0][0].fg, Some(1)); // Red
-    /// assert_eq!(snapshot.cursor, (0, 5));
+    /// assert_eq!(screen.bg_color_at(0, 0), Some(Color::Indexed(4)));
     /// ```
-    pub fn snapshot(&self) -> GridSnapshot {
-        GridSnapshot {
-            width: self.width,
-            height: self.height,
-            cells: self.state.cells.clone(),
-            cursor: self.state.cursor_pos,
-        }
+    pub fn bg_color_at(&self, row: u16, col: u16) -> Option<Color> {
+        self.state.style_at(row, col).map(|style| style.bg_color())
     }
 
-    /// Returns all Sixel graphics regions currently on screen.
+    /// Feeds data from the PTY to the parser.
     ///
-    /// This method provides access to all Sixel graphics that have been rendered
-    /// via DCS (Device Control String) sequences. Each region includes position
-    /// and dimension information.
+    /// This processes VT100/ANSI escape sequences and updates the screen state,
+    /// including:
+    /// - Text output
+    /// - Cursor movements
+    /// - Sixel graphics (tracked via DCS callbacks)
     ///
-    /// This is essential for verifying Sixel positioning in tests, particularly
-    /// for ensuring that graphics appear within designated preview areas.
+    /// This method can be called multiple times to incrementally feed data.
+    /// The parser maintains state across calls, so partial escape sequences
+    /// are handled correctly.
     ///
-    /// # Returns
+    /// # Arguments
     ///
-    /// A slice of [`SixelRegion`] containing all detected Sixel graphics.
+    /// * `data` - Raw bytes from PTY output
     ///
     /// # Example
     ///
@@ -1251,257 +2419,3980 @@ impl ScreenState {
     /// use ratatui_testlib::ScreenState;
     ///
     /// let mut screen = ScreenState::new(80, 24);
-    /// // ... render some Sixel graphics ...
     ///
-    /// let regions = screen.sixel_regions();
-    /// for (i, region) in regions.iter().enumerate() {
-    ///     println!(
-    ///         "Region {}: position ({}, {}), size {}x{}",
-    ///         i, region.start_row, region.start_col, region.width, region.height
-    ///     );
-    /// }
+    /// // Feed data incrementally
+    /// screen.feed(b"Hello, ");
+    /// screen.feed(b"World!");
+    ///
+    /// assert!(screen.contains("Hello, World!"));
     /// ```
-    pub fn sixel_regions(&self) -> &[SixelRegion] {
-        &self.state.sixel_regions
+    pub fn feed(&mut self, data: &[u8]) {
+        // Parse in segments that start at each byte able to begin or end a DCS
+        // string (ESC, 8-bit DCS/ST, CAN and SUB). The actor then knows the
+        // stream offset of those bytes, which locates Sixel sequences in the
+        // raw stream.
+        let is_boundary = |b: &u8| matches!(b, 0x1b | 0x90 | 0x9c | 0x18 | 0x1a);
+        let mut start = 0;
+        while start < data.len() {
+            let end = data[start + 1..]
+                .iter()
+                .position(is_boundary)
+                .map_or(data.len(), |i| start + 1 + i);
+            if data[start] == 0x1b || data[start] == 0x90 {
+                self.state.last_esc_offset = self.state.stream_offset;
+            }
+            self.parser.parse(&data[start..end], &mut self.state);
+            self.state.stream_offset += end - start;
+            start = end;
+            self.state.trace_cursor();
+        }
+    }
+
+    /// Feeds a string to the terminal emulator.
+    ///
+    /// Same as [`feed`](Self::feed) with the string's UTF-8 bytes, which keeps
+    /// tests with non-ASCII text readable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed_str("→ naïve café");
+    ///
+    /// assert!(screen.contains("naïve café"));
+    /// ```
+    pub fn feed_str(&mut self, s: &str) {
+        self.feed(s.as_bytes());
+    }
+
+    /// Returns the rows drawn to since the last update, ascending.
+    ///
+    /// A row is marked when a character is printed to it or any of its cells
+    /// are erased, even if the content ends up unchanged. Scrolling marks every
+    /// row of the scrolling region, and switching between the main and
+    /// alternate screens or resizing marks the whole screen. Marks accumulate
+    /// until [`clear_dirty_rows`](Self::clear_dirty_rows) is called; a
+    /// [`TuiTestHarness`](crate::TuiTestHarness) clears them at the start of
+    /// each [`update_state`](crate::TuiTestHarness::update_state).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(20, 5);
+    /// screen.feed(b"Title\x1b[5;1HNORMAL");
+    /// assert_eq!(screen.dirty_rows(), vec![0, 4]);
+    ///
+    /// // Only the status line redraws after a keypress
+    /// screen.clear_dirty_rows();
+    /// screen.feed(b"\x1b[5;1HINSERT");
+    /// assert_eq!(screen.dirty_rows(), vec![4]);
+    /// ```
+    pub fn dirty_rows(&self) -> Vec<u16> {
+        (0..self.height)
+            .filter(|&row| self.state.dirty[row as usize])
+            .collect()
+    }
+
+    /// Starts a new update for [`dirty_rows()`](Self::dirty_rows).
+    pub fn clear_dirty_rows(&mut self) {
+        self.state.dirty.fill(false);
+    }
+
+    /// Resets the screen to its initial state, keeping its size.
+    ///
+    /// Clears all cells, images, hyperlinks, modes and counters, moves the
+    /// cursor to the origin and discards any partially parsed escape sequence.
+    /// The palette and scrollback capacity are kept, but scrollback contents
+    /// are cleared. Useful for simulating a screen clear without knowing the
+    /// dimensions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[5;10H\x1b[1mHello");
+    ///
+    /// screen.reset();
+    /// assert!(!screen.contains("Hello"));
+    /// assert_eq!(screen.cursor_position(), (0, 0));
+    /// assert_eq!(screen.size(), (80, 24));
+    /// ```
+    pub fn reset(&mut self) {
+        let scrollback_limit = self.state.scrollback_limit;
+        self.parser = VTParser::new();
+        self.state = TerminalState::new(self.width, self.height);
+        self.state.scrollback_limit = scrollback_limit;
+    }
+
+    /// Changes the screen size, keeping the content already on screen.
+    ///
+    /// Text is not reflowed: columns are cut or padded on the right. When the
+    /// screen gets shorter, rows are removed from the top as far as needed to
+    /// keep the cursor's row visible (they go to scrollback, if enabled), then
+    /// from the bottom. The cursor is clamped to the new size. Sizes with a
+    /// zero dimension are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - New width in columns
+    /// * `height` - New height in rows
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(20, 5);
+    /// screen.feed(b"Header\r\nBody");
+    ///
+    /// screen.resize(40, 10);
+    /// assert_eq!(screen.size(), (40, 10));
+    /// assert_eq!(screen.row_contents(0).trim_end(), "Header");
+    ///
+    /// screen.resize(3, 10);
+    /// assert_eq!(screen.row_contents(0), "Hea");
+    /// ```
+    pub fn resize(&mut self, width: u16, height: u16) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        self.state.resize(width, height);
+        self.width = width;
+        self.height = height;
+    }
+
+    /// Starts recording every cursor position reached while feeding data.
+    ///
+    /// The trace begins with the current position. Positions are sampled
+    /// before each printed character, control character and escape sequence
+    /// the parser dispatches, and at the end of each segment `feed` parses, so
+    /// a move that the next sequence undoes is still captured.
+    pub(crate) fn start_cursor_trace(&mut self) {
+        self.state.cursor_trace = Some(vec![self.state.cursor_pos]);
+    }
+
+    /// Stops cursor tracing and returns the recorded positions as (row, col).
+    pub(crate) fn take_cursor_trace(&mut self) -> Vec<(u16, u16)> {
+        self.state.cursor_trace.take().unwrap_or_default()
+    }
+
+    /// Returns the screen contents as a string.
+    ///
+    /// This includes all visible characters, preserving layout with newlines
+    /// between rows. Empty cells are represented as spaces.
+    ///
+    /// # Returns
+    ///
+    /// A string containing the entire screen contents, with rows separated by newlines.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(10, 3);
+    /// screen.feed(b"Hello");
+    ///
+    /// let contents = screen.contents();
+    /// // First line contains "Hello     " (padded to 10 chars)
+    /// // Second and third lines are all spaces
+    /// assert!(contents.contains("Hello"));
+    /// ```
+    pub fn contents(&self) -> String {
+        Self::render_cells(&self.state.cells)
+    }
+
+    /// Returns the screen contents with trailing whitespace removed.
+    ///
+    /// Each row is trimmed on the right and trailing blank rows are dropped,
+    /// so the result only depends on the visible text (see
+    /// [`lines()`](Self::lines)). Colors and attributes are never included.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(10, 3);
+    /// screen.feed(b"\x1b[31mHello\x1b[0m");
+    ///
+    /// assert_eq!(screen.contents_trimmed(), "Hello");
+    /// ```
+    pub fn contents_trimmed(&self) -> String {
+        self.lines().join("\n")
+    }
+
+    /// Returns the screen contents without trailing spaces or empty lines.
+    ///
+    /// Same as [`contents_trimmed()`](Self::contents_trimmed). Unlike
+    /// [`contents()`](Self::contents), which keeps the full padded grid, this
+    /// gives stable, minimal snapshots.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(10, 4);
+    /// screen.feed(b"a  \r\n\r\nb");
+    ///
+    /// assert_eq!(screen.trimmed_contents(), "a\n\nb");
+    /// assert_eq!(screen.contents().lines().count(), 4);
+    /// ```
+    pub fn trimmed_contents(&self) -> String {
+        self.contents_trimmed()
+    }
+
+    /// Returns a hash of the visible characters.
+    ///
+    /// Screens showing the same characters in the same cells hash equally,
+    /// regardless of colors, attributes or cursor position. Comparing hashes is
+    /// a cheap way for polling loops to check whether anything changed, without
+    /// building and comparing [`contents()`](Self::contents) strings.
+    ///
+    /// Hashes are only comparable within one process.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut a = ScreenState::new(20, 3);
+    /// let mut b = ScreenState::new(20, 3);
+    /// a.feed(b"Hello");
+    /// b.feed(b"\x1b[1;31mHel\x1b[0mlo\r\n");
+    ///
+    /// assert_eq!(a.content_hash(), b.content_hash());
+    ///
+    /// b.feed(b"!");
+    /// assert_ne!(a.content_hash(), b.content_hash());
+    /// ```
+    pub fn content_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.width.hash(&mut hasher);
+        self.height.hash(&mut hasher);
+        for cell in self.state.cells.iter().flatten() {
+            cell.c.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Returns the screen as ANSI text that reproduces it, colors included.
+    ///
+    /// Rows are separated by `\r\n`, and SGR sequences are emitted wherever
+    /// the colors or attributes change from one cell to the next. Feeding the
+    /// result into a fresh `ScreenState` of the same size produces the same
+    /// cells, which makes it suitable for golden files that should capture
+    /// styling as well as text. The cursor position is not included.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(10, 2);
+    /// screen.feed(b"\x1b[1;32mOK\x1b[0m done");
+    ///
+    /// let ansi = screen.to_ansi();
+    /// assert!(ansi.starts_with("\x1b[0;1;32mOK\x1b[0m done"));
+    ///
+    /// let mut replay = ScreenState::new(10, 2);
+    /// replay.feed(ansi.as_bytes());
+    /// assert_eq!(replay.get_cell(0, 0), screen.get_cell(0, 0));
+    /// assert_eq!(replay.contents(), screen.contents());
+    /// ```
+    pub fn to_ansi(&self) -> String {
+        let mut out = String::new();
+        let mut pen = CellStyle::default();
+        for (index, row) in self.state.cells.iter().enumerate() {
+            if index > 0 {
+                out.push_str("\r\n");
+            }
+            let styles = self.state.row_styles(index);
+            // The spacer of a wide glyph is filled in by the glyph itself
+            for (col, cell) in glyph_cells(row) {
+                let style = styles[col];
+                if style != pen {
+                    out.push_str(&Self::sgr(&style));
+                    pen = style;
+                }
+                out.push(cell.c);
+            }
+        }
+        if pen != CellStyle::default() {
+            out.push_str("\x1b[0m");
+        }
+        out
+    }
+
+    /// Resizes the screen and renders a ratatui frame into it.
+    ///
+    /// The screen is replaced by a blank one of `size` (keeping the palette
+    /// and scrollback limit),
+    /// `draw` renders into a ratatui `TestBackend` of the same size, and the
+    /// resulting buffer is fed in as if an application had drawn it. Calling
+    /// this at two sizes lets a test check how a widget reflows.
+    ///
+    /// Named and indexed colors map to palette indices; RGB colors are kept
+    /// as 24-bit colors.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Screen size as (width, height)
+    /// * `draw` - Renders the frame, as passed to `ratatui::Terminal::draw`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the ratatui terminal cannot be created or drawn.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui::widgets::{Block, Paragraph};
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.render_ratatui_at((30, 5), |frame| {
+    ///     let widget = Paragraph::new("Hello").block(Block::bordered());
+    ///     frame.render_widget(widget, frame.area());
+    /// })?;
+    ///
+    /// assert_eq!(screen.size(), (30, 5));
+    /// assert!(screen.row_contents(1).starts_with("│Hello"));
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    #[cfg(feature = "ratatui-helpers")]
+    pub fn render_ratatui_at<F>(&mut self, size: (u16, u16), draw: F) -> crate::Result<()>
+    where
+        F: FnOnce(&mut ratatui::Frame<'_>),
+    {
+        use ratatui::{backend::TestBackend, style::Modifier, Terminal};
+
+        let (width, height) = size;
+        let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+        terminal.draw(draw)?;
+        let buffer = terminal.backend().buffer();
+
+        let mut bytes = String::new();
+        let mut pen = CellStyle::default();
+        for y in 0..height {
+            bytes.push_str(&format!("\x1b[{};1H", y + 1));
+            let mut skip = 0;
+            for x in 0..width {
+                let Some(cell) = buffer.cell((x, y)) else {
+                    continue;
+                };
+                // Columns covered by the previous wide symbol
+                if skip > 0 {
+                    skip -= 1;
+                    continue;
+                }
+                let symbol = cell.symbol();
+                skip = symbol
+                    .chars()
+                    .next()
+                    .map_or(0, |ch| char_width(ch).saturating_sub(1));
+
+                let style = CellStyle {
+                    fg: ratatui_color_index(cell.fg),
+                    bg: ratatui_color_index(cell.bg),
+                    fg_rgb: ratatui_rgb(cell.fg),
+                    bg_rgb: ratatui_rgb(cell.bg),
+                    bold: cell.modifier.contains(Modifier::BOLD),
+                    italic: cell.modifier.contains(Modifier::ITALIC),
+                    underline: cell.modifier.contains(Modifier::UNDERLINED),
+                    inverse: cell.modifier.contains(Modifier::REVERSED),
+                    blink: cell
+                        .modifier
+                        .intersects(Modifier::SLOW_BLINK | Modifier::RAPID_BLINK),
+                };
+                if style != pen {
+                    bytes.push_str(&Self::sgr(&style));
+                    pen = style;
+                }
+                bytes.push_str(if symbol.is_empty() { " " } else { symbol });
+            }
+        }
+
+        let scrollback_limit = self.state.scrollback_limit;
+        *self = Self::with_palette(width, height, self.palette);
+        self.state.scrollback_limit = scrollback_limit;
+        self.feed(bytes.as_bytes());
+        Ok(())
+    }
+
+    /// Returns the screen as an HTML `<pre>` block with colors and attributes.
+    ///
+    /// Runs of cells sharing the same style are wrapped in a
+    /// `<span style="...">`; unstyled text is left bare. Indexed colors are
+    /// resolved through the screen's palette (the xterm palette unless created
+    /// with [`with_palette()`](Self::with_palette)) and written as hex. Inverse
+    /// cells swap their colors, using palette colors 0 and 7 for a default
+    /// background and foreground. Trailing blanks are dropped from each row and
+    /// text is HTML-escaped, so the result can be embedded in a report as is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(20, 2);
+    /// screen.feed(b"\x1b[1;31mFAIL\x1b[0m a < b");
+    ///
+    /// assert_eq!(
+    ///     screen.to_html(),
+    ///     "<pre><span style=\"color:#cd0000;font-weight:bold\">FAIL</span> a &lt; b\n</pre>"
+    /// );
+    /// ```
+    pub fn to_html(&self) -> String {
+        let mut out = String::from("<pre>");
+        for (index, row) in self.state.cells.iter().enumerate() {
+            if index > 0 {
+                out.push('\n');
+            }
+            let styles = self.state.row_styles(index);
+            let len = row
+                .iter()
+                .zip(&styles)
+                .rposition(|(cell, style)| cell.c != ' ' || *style != CellStyle::default())
+                .map_or(0, |last| last + 1);
+
+            let mut open: Option<CellStyle> = None;
+            for (col, cell) in glyph_cells(&row[..len]) {
+                let style = styles[col];
+                if open != Some(style) {
+                    if open.is_some_and(|open| open != CellStyle::default()) {
+                        out.push_str("</span>");
+                    }
+                    if style != CellStyle::default() {
+                        out.push_str(&format!("<span style=\"{}\">", self.css(&style)));
+                    }
+                    open = Some(style);
+                }
+                match cell.c {
+                    '&' => out.push_str("&amp;"),
+                    '<' => out.push_str("&lt;"),
+                    '>' => out.push_str("&gt;"),
+                    c => out.push(c),
+                }
+            }
+            if open.is_some_and(|open| open != CellStyle::default()) {
+                out.push_str("</span>");
+            }
+        }
+        out.push_str("</pre>");
+        out
+    }
+
+    /// Builds the inline CSS for a cell style.
+    fn css(&self, style: &CellStyle) -> String {
+        let hex = |color: Color| match color {
+            Color::Default => None,
+            Color::Indexed(n) => {
+                let (r, g, b) = self.palette[n as usize];
+                Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+            }
+            Color::Rgb(r, g, b) => Some(format!("#{:02x}{:02x}{:02x}", r, g, b)),
+        };
+
+        let (mut fg, mut bg) = (style.fg_color(), style.bg_color());
+        if style.inverse {
+            let fallback_fg = match fg {
+                Color::Default => Color::Indexed(7),
+                color => color,
+            };
+            let fallback_bg = match bg {
+                Color::Default => Color::Indexed(0),
+                color => color,
+            };
+            (fg, bg) = (fallback_bg, fallback_fg);
+        }
+
+        let mut props = Vec::new();
+        if let Some(color) = hex(fg) {
+            props.push(format!("color:{}", color));
+        }
+        if let Some(color) = hex(bg) {
+            props.push(format!("background:{}", color));
+        }
+        if style.bold {
+            props.push("font-weight:bold".to_string());
+        }
+        if style.italic {
+            props.push("font-style:italic".to_string());
+        }
+        let decorations: Vec<&str> = [(style.underline, "underline"), (style.blink, "blink")]
+            .into_iter()
+            .filter_map(|(enabled, name)| enabled.then_some(name))
+            .collect();
+        if !decorations.is_empty() {
+            props.push(format!("text-decoration:{}", decorations.join(" ")));
+        }
+        props.join(";")
+    }
+
+    /// Builds the SGR sequence that switches from the reset state to `style`.
+    fn sgr(style: &CellStyle) -> String {
+        let mut codes = vec!["0".to_string()];
+        for (enabled, code) in [
+            (style.bold, "1"),
+            (style.italic, "3"),
+            (style.underline, "4"),
+            (style.blink, "5"),
+            (style.inverse, "7"),
+        ] {
+            if enabled {
+                codes.push(code.to_string());
+            }
+        }
+        match style.fg_color() {
+            Color::Indexed(n @ 0..=7) => codes.push((30 + n).to_string()),
+            Color::Indexed(n @ 8..=15) => codes.push((90 + n - 8).to_string()),
+            Color::Indexed(n) => codes.push(format!("38;5;{}", n)),
+            Color::Rgb(r, g, b) => codes.push(format!("38;2;{};{};{}", r, g, b)),
+            Color::Default => {}
+        }
+        match style.bg_color() {
+            Color::Indexed(n @ 0..=7) => codes.push((40 + n).to_string()),
+            Color::Indexed(n @ 8..=15) => codes.push((100 + n - 8).to_string()),
+            Color::Indexed(n) => codes.push(format!("48;5;{}", n)),
+            Color::Rgb(r, g, b) => codes.push(format!("48;2;{};{};{}", r, g, b)),
+            Color::Default => {}
+        }
+        format!("\x1b[{}m", codes.join(";"))
+    }
+
+    /// Joins a cell grid into a string with rows separated by newlines.
+    fn render_cells(cells: &[Vec<Cell>]) -> String {
+        cells
+            .iter()
+            .map(|row| Self::row_text(row))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Returns the characters of a row, skipping the spacer cells of wide glyphs.
+    fn row_text(row: &[Cell]) -> String {
+        glyph_cells(row).map(|(_, cell)| cell.c).collect()
+    }
+
+    /// Returns the text of a row with the column of each byte of it.
+    ///
+    /// The text is the same as [`row_contents()`](Self::row_contents). The
+    /// column list has an entry per byte of the text, holding the column of
+    /// the character the byte belongs to, plus a final entry for the end of
+    /// the row. It turns byte offsets of matches into columns even when wide
+    /// glyphs put characters and columns out of step.
+    fn row_text_with_columns(&self, row: u16) -> (String, Vec<u16>) {
+        let mut text = String::new();
+        let mut columns = Vec::new();
+        if let Some(cells) = self.state.cells.get(row as usize) {
+            for (col, cell) in glyph_cells(cells) {
+                text.push(cell.c);
+                columns.resize(text.len(), col as u16);
+            }
+        }
+        columns.push(self.width);
+        (text, columns)
+    }
+
+    /// Returns the lines that scrolled off the top of the screen.
+    ///
+    /// Lines are ordered oldest first and separated by newlines, formatted like
+    /// [`contents()`](Self::contents). Always empty unless the screen was created
+    /// with [`with_scrollback()`](Self::with_scrollback).
+    pub fn scrollback_contents(&self) -> String {
+        self.state
+            .scrollback
+            .iter()
+            .map(|row| Self::row_text(row))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Compares the characters of this screen with another.
+    ///
+    /// Only characters are compared; attribute changes are not reported. If the
+    /// screens differ in size, the union of both areas is compared and cells
+    /// that exist on only one side are reported with `None` on the other (and
+    /// skipped if they are blank), so a resize alone doesn't produce diffs.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The screen to compare against, typically a later capture
+    ///
+    /// # Returns
+    ///
+    /// The differing cells in row-major order; empty if the text is identical.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{CellDiff, ScreenState};
+    ///
+    /// let mut before = ScreenState::new(20, 3);
+    /// before.feed(b"[ ] task");
+    /// let mut after = ScreenState::new(20, 3);
+    /// after.feed(b"[x] task");
+    ///
+    /// assert_eq!(
+    ///     before.diff(&after),
+    ///     vec![CellDiff { row: 0, col: 1, before: Some(' '), after: Some('x') }]
+    /// );
+    /// ```
+    pub fn diff(&self, other: &ScreenState) -> Vec<CellDiff> {
+        let is_blank = |c: Option<char>| c.unwrap_or(' ') == ' ';
+        let mut diffs = Vec::new();
+        for row in 0..self.height.max(other.height) {
+            for col in 0..self.width.max(other.width) {
+                let before = self.text_at(row, col);
+                let after = other.text_at(row, col);
+                if before != after && !(is_blank(before) && is_blank(after)) {
+                    diffs.push(CellDiff { row, col, before, after });
+                }
+            }
+        }
+        diffs
+    }
+
+    /// Returns the contents of a specific row.
+    ///
+    /// A double-width character (such as CJK text) occupies two columns, but
+    /// appears only once in the result: the blank spacer in its second column
+    /// is skipped, so the string matches what a user sees.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// The row contents as a string, or empty string if row is out of bounds.
+    pub fn row_contents(&self, row: u16) -> String {
+        if row < self.height {
+            Self::row_text(&self.state.cells[row as usize])
+        } else {
+            String::new()
+        }
+    }
+
+    /// Returns the contents of a rectangular region, one string per row.
+    ///
+    /// Useful for asserting on a single pane of a multi-panel layout. The
+    /// rectangle is clamped to the screen, so parts outside it are dropped
+    /// rather than causing a panic.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Top row of the region (0-based)
+    /// * `col` - Left column of the region (0-based)
+    /// * `width` - Width of the region in columns
+    /// * `height` - Height of the region in rows
+    ///
+    /// # Returns
+    ///
+    /// The cells of each row within the column range, untrimmed. A wide glyph
+    /// appears once, as in [`row_contents()`](Self::row_contents). Empty if the
+    /// region lies entirely outside the screen.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(20, 5);
+    /// screen.feed(b"files    | preview\r\nmain.rs  | fn main");
+    ///
+    /// assert_eq!(screen.region_contents(0, 11, 7, 2), vec!["preview", "fn main"]);
+    /// ```
+    pub fn region_contents(&self, row: u16, col: u16, width: u16, height: u16) -> Vec<String> {
+        let row_end = row.saturating_add(height).min(self.height);
+        let col_end = col.saturating_add(width).min(self.width);
+        if col >= col_end {
+            return Vec::new();
+        }
+        (row..row_end)
+            .map(|r| {
+                glyph_cells(&self.state.cells[r as usize])
+                    .filter(|&(c, _)| (col as usize..col_end as usize).contains(&c))
+                    .map(|(_, cell)| cell.c)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the character at a specific position.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// The character at the position, or None if out of bounds.
+    pub fn text_at(&self, row: u16, col: u16) -> Option<char> {
+        if row < self.height && col < self.width {
+            Some(self.state.cells[row as usize][col as usize].c)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the complete cell (character + attributes) at a specific position.
+    ///
+    /// This method provides access to the full cell state including colors and
+    /// text attributes, enabling verification of ANSI escape sequence handling.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// A reference to the cell, or None if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[31mRed\x1b[0m");
+    ///
+    /// if let Some(cell) = screen.get_cell(0, 0) {
+    ///     assert_eq!(cell.c, 'R');
+    ///     assert_eq!(cell.fg, Some(1)); // Red = color 1
+    /// }
+    /// ```
+    pub fn get_cell(&self, row: u16, col: u16) -> Option<&Cell> {
+        if row < self.height && col < self.width {
+            Some(&self.state.cells[row as usize][col as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Returns a copy of the cell at the given position.
+    ///
+    /// Like [`get_cell`](Self::get_cell), but returns the [`Cell`] by value, so
+    /// a test can check the character, colors and every attribute of one cell
+    /// in a single `assert_eq!`.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// The cell, or `None` if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{Cell, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[1;4;33;44mW\x1b[0m");
+    ///
+    /// assert_eq!(
+    ///     screen.cell(0, 0),
+    ///     Some(Cell {
+    ///         c: 'W',
+    ///         fg: Some(3),
+    ///         bg: Some(4),
+    ///         bold: true,
+    ///         underline: true,
+    ///         ..Cell::default()
+    ///     })
+    /// );
+    /// assert_eq!(screen.cell(24, 0), None);
+    /// ```
+    pub fn cell(&self, row: u16, col: u16) -> Option<Cell> {
+        self.get_cell(row, col).copied()
+    }
+
+    /// Returns the screen as a grid of characters, one row per inner vector.
+    ///
+    /// The grid is always `height` rows of `width` characters. Empty cells are
+    /// spaces, and a wide glyph appears in its first column followed by a space.
+    /// Indexing as `grid[row][col]` makes checks on columns, diagonals or
+    /// box-drawing alignment simpler than slicing strings.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(4, 3);
+    /// screen.feed("┌──┐\r\n│ab│\r\n└──┘".as_bytes());
+    ///
+    /// let grid = screen.char_grid();
+    /// assert_eq!(grid.len(), 3);
+    /// assert!(grid.iter().all(|row| row.len() == 4));
+    /// assert!(grid.iter().all(|row| matches!(row[0], '┌' | '│' | '└')));
+    /// assert_eq!(grid[1][2], 'b');
+    /// ```
+    pub fn char_grid(&self) -> Vec<Vec<char>> {
+        self.state
+            .cells
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.c).collect())
+            .collect()
+    }
+
+    /// Returns whether a row continues the row above it because of autowrap.
+    ///
+    /// When output runs past the right edge of the screen it continues on the
+    /// next row; that row is flagged as wrapped. Rows started by an explicit
+    /// newline (or cursor movement) are not.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// `true` if the row is a wrap continuation, `false` otherwise or if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(5, 4);
+    /// screen.feed(b"abcdefg\r\nxy");
+    ///
+    /// assert!(!screen.is_wrapped_line(0));
+    /// assert!(screen.is_wrapped_line(1)); // "fg"
+    /// assert!(!screen.is_wrapped_line(2)); // "xy"
+    /// ```
+    pub fn is_wrapped_line(&self, row: u16) -> bool {
+        self.state
+            .wrapped
+            .get(row as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Returns whether the line starting at a row wrapped onto the next row.
+    ///
+    /// Where [`is_wrapped_line()`](Self::is_wrapped_line) asks whether a row
+    /// continues the one above, this asks whether the line starting at
+    /// `logical_line_start_row` ran past the right edge. Text that exactly
+    /// fills the width does not wrap until another character is printed.
+    ///
+    /// # Arguments
+    ///
+    /// * `logical_line_start_row` - Row where the line starts (0-based)
+    ///
+    /// # Returns
+    ///
+    /// `true` if the line continues on the next row, `false` otherwise or if
+    /// out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(10, 4);
+    /// screen.feed(b"0123456789\r\n0123456789X");
+    ///
+    /// assert!(!screen.line_wrapped_at(0)); // exactly fits
+    /// assert!(screen.line_wrapped_at(1)); // one column too long
+    /// ```
+    pub fn line_wrapped_at(&self, logical_line_start_row: u16) -> bool {
+        self.is_wrapped_line(logical_line_start_row.saturating_add(1))
+    }
+
+    /// Returns whether a row's text continues onto the next row.
+    ///
+    /// This is the per-row wrap flag of terminals such as vt100: set when
+    /// output hit the right edge and autowrap carried it to the next row, clear
+    /// when the row ended with a hard newline or was truncated with autowrap
+    /// disabled. Same as [`line_wrapped_at()`](Self::line_wrapped_at).
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(10, 4);
+    /// screen.feed(b"a long paragraph\r\nshort");
+    ///
+    /// assert!(screen.is_wrapped(0)); // soft wrap at column 10
+    /// assert!(!screen.is_wrapped(1)); // hard newline
+    /// assert!(!screen.is_wrapped(2));
+    /// ```
+    pub fn is_wrapped(&self, row: u16) -> bool {
+        self.line_wrapped_at(row)
+    }
+
+    /// Returns every point where autowrap broke a line.
+    ///
+    /// Each entry is `(row, col)`: the row whose text ran past the right edge
+    /// and the column it broke at, which is the screen width. Its continuation
+    /// begins at column 0 of `row + 1` (see
+    /// [`is_wrapped_line()`](Self::is_wrapped_line)). Wraps whose first row has
+    /// scrolled off the top are not reported.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(10, 5);
+    /// screen.feed(b"short\r\n0123456789abc");
+    ///
+    /// assert_eq!(screen.wrap_points(), vec![(1, 10)]);
+    /// ```
+    pub fn wrap_points(&self) -> Vec<(u16, u16)> {
+        self.state
+            .wrapped
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|&(_, &wrapped)| wrapped)
+            .map(|(row, _)| (row as u16 - 1, self.width))
+            .collect()
+    }
+
+    /// Returns the UTF-8 bytes of the character in a cell.
+    ///
+    /// Useful for checking exactly what was stored for multibyte or unusual
+    /// characters, where comparing rendered text can hide differences.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// The encoded bytes, or None if out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed("é".as_bytes());
+    ///
+    /// assert_eq!(screen.cell_bytes(0, 0), Some(vec![0xC3, 0xA9]));
+    /// ```
+    pub fn cell_bytes(&self, row: u16, col: u16) -> Option<Vec<u8>> {
+        let c = self.get_cell(row, col)?.c;
+        let mut buf = [0u8; 4];
+        Some(c.encode_utf8(&mut buf).as_bytes().to_vec())
+    }
+
+    /// Returns the current cursor position.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of (row, col) with 0-based indexing.
+    pub fn cursor_position(&self) -> (u16, u16) {
+        self.state.cursor_pos
+    }
+
+    /// Returns whether the cursor is visible.
+    ///
+    /// Applications hide the cursor with `ESC [ ? 25 l` (DECTCEM), typically
+    /// while a menu or modal dialog is open, and show it again with
+    /// `ESC [ ? 25 h`. The cursor starts out visible.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// assert!(screen.cursor_visible());
+    ///
+    /// screen.feed(b"\x1b[?25lMenu");
+    /// assert!(!screen.cursor_visible());
+    ///
+    /// screen.feed(b"\x1b[?25h");
+    /// assert!(screen.cursor_visible());
+    /// ```
+    pub fn cursor_visible(&self) -> bool {
+        self.state.cursor_visible
+    }
+
+    /// Returns whether the application has enabled mouse reporting.
+    ///
+    /// True while any of the tracking modes of
+    /// [`mouse_tracking_mode()`](Self::mouse_tracking_mode) is active. False by
+    /// default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// assert!(!screen.mouse_tracking_enabled());
+    ///
+    /// screen.feed(b"\x1b[?1000h\x1b[?1006h");
+    /// assert!(screen.mouse_tracking_enabled());
+    ///
+    /// screen.feed(b"\x1b[?1000l");
+    /// assert!(!screen.mouse_tracking_enabled());
+    /// ```
+    pub fn mouse_tracking_enabled(&self) -> bool {
+        self.state.mouse_tracking.is_some()
+    }
+
+    /// Returns which mouse events the application asked to receive.
+    ///
+    /// Enabling one of modes 9, 1000, 1002 or 1003 replaces any other tracking
+    /// mode, and disabling the active one turns tracking off.
+    ///
+    /// # Returns
+    ///
+    /// The active tracking mode, or None if mouse reporting is off.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{MouseTrackingMode, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[?1002h\x1b[?1006h");
+    ///
+    /// assert_eq!(screen.mouse_tracking_mode(), Some(MouseTrackingMode::ButtonEvent));
+    /// ```
+    pub fn mouse_tracking_mode(&self) -> Option<MouseTrackingMode> {
+        self.state.mouse_tracking
+    }
+
+    /// Returns whether the application has enabled bracketed paste mode.
+    ///
+    /// Editors turn it on with `ESC [ ? 2004 h` so pasted text arrives wrapped
+    /// in `ESC [ 200 ~` / `ESC [ 201 ~` and can be told apart from typing
+    /// (see [`TuiTestHarness::send_paste`](crate::TuiTestHarness::send_paste)).
+    /// `ESC [ ? 2004 l` turns it off again. False by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// assert!(!screen.bracketed_paste_enabled());
+    ///
+    /// screen.feed(b"\x1b[?2004h");
+    /// assert!(screen.bracketed_paste_enabled());
+    ///
+    /// screen.feed(b"\x1b[?2004l");
+    /// assert!(!screen.bracketed_paste_enabled());
+    /// ```
+    pub fn bracketed_paste_enabled(&self) -> bool {
+        self.state.bracketed_paste
+    }
+
+    /// Returns the current scrolling region.
+    ///
+    /// Pagers and log views set a region with `ESC [ top ; bottom r` (DECSTBM)
+    /// so a header or status line stays put while the rows in between scroll.
+    /// Line feeds at the bottom of the region scroll only the region. Invalid
+    /// regions are ignored, and `ESC [ r` resets to the whole screen.
+    ///
+    /// # Returns
+    ///
+    /// `(top, bottom)` as 0-based, inclusive rows; `(0, height - 1)` by default.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// assert_eq!(screen.scroll_region(), (0, 23));
+    ///
+    /// // Keep the header on row 0 fixed
+    /// screen.feed(b"\x1b[2;24r");
+    /// assert_eq!(screen.scroll_region(), (1, 23));
+    /// ```
+    pub fn scroll_region(&self) -> (u16, u16) {
+        (self.state.scroll_top, self.state.scroll_bottom)
+    }
+
+    /// Returns the screen dimensions.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of (width, height) in columns and rows.
+    pub fn size(&self) -> (u16, u16) {
+        (self.width, self.height)
+    }
+
+    /// Returns the screen width in columns.
+    ///
+    /// This is a convenience method equivalent to `self.size().0`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let screen = ScreenState::new(80, 24);
+    /// assert_eq!(screen.cols(), 80);
+    /// ```
+    pub fn cols(&self) -> u16 {
+        self.width
+    }
+
+    /// Returns the screen height in rows.
+    ///
+    /// This is a convenience method equivalent to `self.size().1`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let screen = ScreenState::new(80, 24);
+    /// assert_eq!(screen.rows(), 24);
+    /// ```
+    pub fn rows(&self) -> u16 {
+        self.height
+    }
+
+    /// Returns an iterator over all rows in the screen.
+    ///
+    /// Each item in the iterator is a reference to a row (a slice of cells).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"Line 1\r\nLine 2");
+    ///
+    /// for (row_idx, row) in screen.iter_rows().enumerate() {
+    ///     println!("Row {}: {} cells", row_idx, row.len());
+    ///     for (col_idx, cell) in row.iter().enumerate() {
+    ///         if cell.c != ' ' {
+    ///             println!("  Cell ({}, {}): '{}'", row_idx, col_idx, cell.c);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[Cell]> {
+        self.state.cells.iter().map(|row| row.as_slice())
+    }
+
+    /// Returns an iterator over all cells in a specific row.
+    ///
+    /// Returns `None` if the row index is out of bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[31mRed\x1b[32mGreen\x1b[34mBlue");
+    ///
+    /// // Use the iterator immediately to avoid lifetime issues
+    /// let colored_cells: Vec<_> = screen
+    ///     .iter_row(0)
+    ///     .unwrap()
+    ///     .enumerate()
+    ///     .filter(|(_, cell)| cell.fg.is_some())
+    ///     .collect();
+    ///
+    /// assert!(colored_cells.len() >= 3, "Should have colored cells");
+    /// ```
+    pub fn iter_row(&self, row: u16) -> Option<impl Iterator<Item = &Cell>> {
+        if row < self.height {
+            Some(self.state.cells[row as usize].iter())
+        } else {
+            None
+        }
+    }
+
+    /// Returns a structured snapshot of the entire screen grid.
+    ///
+    /// This method provides a complete copy of the screen state suitable for
+    /// deep comparison with other terminal emulators or for serialization.
+    ///
+    /// # Returns
+    ///
+    /// A `GridSnapshot` containing:
+    /// - Grid dimensions (width, height)
+    /// - Complete cell data (2D vector of cells)
+    /// - Current cursor position
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[31mHello");
+    ///
+    /// let snapshot = screen.snapshot();
+    /// assert_eq!(snapshot.width, 80);
+    /// assert_eq!(snapshot.height, 24);
+    /// assert_eq!(snapshot.cells[0][0].c, 'H');
+    /// assert_eq!(snapshot.cells[0][0].fg, Some(1)); // Red
+    /// assert_eq!(snapshot.cursor, (0, 5));
+    /// ```
+    pub fn snapshot(&self) -> GridSnapshot {
+        GridSnapshot {
+            width: self.width,
+            height: self.height,
+            cells: self.state.cells.clone(),
+            cursor: self.state.cursor_pos,
+        }
+    }
+
+    /// Returns all Sixel graphics regions currently on screen.
+    ///
+    /// This method provides access to all Sixel graphics that have been rendered
+    /// via DCS (Device Control String) sequences. Each region includes position
+    /// and dimension information.
+    ///
+    /// This is essential for verifying Sixel positioning in tests, particularly
+    /// for ensuring that graphics appear within designated preview areas.
+    ///
+    /// # Returns
+    ///
+    /// A slice of [`SixelRegion`] containing all detected Sixel graphics.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// // ... render some Sixel graphics ...
+    ///
+    /// let regions = screen.sixel_regions();
+    /// for (i, region) in regions.iter().enumerate() {
+    ///     println!(
+    ///         "Region {}: position ({}, {}), size {}x{}",
+    ///         i, region.start_row, region.start_col, region.width, region.height
+    ///     );
+    /// }
+    /// ```
+    pub fn sixel_regions(&self) -> &[SixelRegion] {
+        &self.state.sixel_regions
+    }
+
+    /// Returns a mutable reference to the Sixel regions.
+    ///
+    /// This is primarily intended for testing purposes, allowing tests to
+    /// inject mock Sixel data for memory profiling and other tests.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the vector of Sixel regions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{screen::SixelRegion, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    ///
+    /// // Add a mock Sixel region for testing
+    /// screen.sixel_regions_mut().push(SixelRegion {
+    ///     start_row: 5,
+    ///     start_col: 10,
+    ///     width: 100,
+    ///     height: 50,
+    ///     data: vec![0u8; 1000],
+    /// });
+    ///
+    /// assert_eq!(screen.sixel_regions().len(), 1);
+    /// ```
+    pub fn sixel_regions_mut(&mut self) -> &mut Vec<SixelRegion> {
+        &mut self.state.sixel_regions
+    }
+
+    /// Returns where a Sixel region's escape sequence sits in the fed stream.
+    ///
+    /// The range covers the complete sequence, from `ESC P` through the string
+    /// terminator, counted over every byte passed to [`feed`](Self::feed) since
+    /// the screen was created or [`reset`](Self::reset). Removing these ranges
+    /// reconstructs the stream without images.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - Index of the region in [`sixel_regions`](Self::sixel_regions)
+    ///
+    /// # Returns
+    ///
+    /// The byte range, or `None` if there is no such region or it was added
+    /// through [`sixel_regions_mut`](Self::sixel_regions_mut). Ranges follow
+    /// the regions parsed from the stream, so they are only meaningful while
+    /// regions are not removed or reordered by hand.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"text\x1bPq\"1;1;16;12#0~\x1b\\");
+    ///
+    /// assert_eq!(screen.sixel_stream_range(0), Some(4..22));
+    /// assert_eq!(screen.sixel_stream_range(1), None);
+    /// ```
+    pub fn sixel_stream_range(&self, index: usize) -> Option<std::ops::Range<usize>> {
+        self.state.sixel_stream_ranges.get(index).cloned().flatten()
+    }
+
+    /// Checks if a Sixel region exists at the given position.
+    ///
+    /// This method checks if any Sixel region has its starting position
+    /// at the exact (row, col) coordinates provided.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row to check (0-indexed)
+    /// * `col` - Column to check (0-indexed)
+    ///
+    /// # Returns
+    ///
+    /// `true` if a Sixel region starts at the given position, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    ///
+    /// // Render a Sixel at position (5, 10) - ESC[5;10H moves to row 5, col 10 (1-based)
+    /// screen.feed(b"\x1b[5;10H"); // Move cursor
+    /// screen.feed(b"\x1bPq"); // Start Sixel
+    /// screen.feed(b"\"1;1;100;50#0~"); // Raster + data
+    /// screen.feed(b"\x1b\\"); // End Sixel
+    ///
+    /// // Check for Sixel at 0-based coordinates (4, 9)
+    /// assert!(screen.has_sixel_at(4, 9));
+    /// assert!(!screen.has_sixel_at(0, 0));
+    /// ```
+    pub fn has_sixel_at(&self, row: u16, col: u16) -> bool {
+        self.state
+            .sixel_regions
+            .iter()
+            .any(|region| region.start_row == row && region.start_col == col)
+    }
+
+    /// Returns all Kitty graphics regions currently on screen.
+    ///
+    /// This method provides access to all Kitty graphics that have been rendered
+    /// via APC (Application Program Command) sequences. Each region includes position
+    /// and dimension information.
+    ///
+    /// # Returns
+    ///
+    /// A slice of [`KittyRegion`] containing all detected Kitty graphics.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// // ... render some Kitty graphics ...
+    ///
+    /// let regions = screen.kitty_regions();
+    /// for (i, region) in regions.iter().enumerate() {
+    ///     println!(
+    ///         "Kitty region {}: position ({}, {}), size {}x{}",
+    ///         i, region.start_row, region.start_col, region.width, region.height
+    ///     );
+    /// }
+    /// ```
+    pub fn kitty_regions(&self) -> &[KittyRegion] {
+        &self.state.kitty_regions
+    }
+
+    /// Returns a mutable reference to the Kitty regions.
+    ///
+    /// This is primarily intended for testing purposes.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the vector of Kitty regions.
+    pub fn kitty_regions_mut(&mut self) -> &mut Vec<KittyRegion> {
+        &mut self.state.kitty_regions
+    }
+
+    /// Returns all Kitty image transmission commands with their parsed parameters.
+    ///
+    /// Each captured Kitty graphics APC whose action is a transmission (`a=t`,
+    /// `a=T`, or no `a` key) is returned with its position and the key/value pairs
+    /// from its control data. Other commands, such as queries (`a=q`) and deletes
+    /// (`a=d`), are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[2;3H\x1b_Ga=T,f=100,i=7;iVBORw0KGgo=\x1b\\");
+    ///
+    /// let images = screen.kitty_images();
+    /// assert_eq!(images.len(), 1);
+    /// assert_eq!((images[0].row, images[0].col), (1, 2));
+    /// assert_eq!(images[0].param("f"), Some("100"));
+    /// assert_eq!(images[0].param("i"), Some("7"));
+    /// ```
+    pub fn kitty_images(&self) -> Vec<KittyImage> {
+        self.state
+            .kitty_regions
+            .iter()
+            .filter_map(|region| {
+                let data = region.data.strip_prefix(b"G").unwrap_or(&region.data);
+                let control = match data.iter().position(|&b| b == b';') {
+                    Some(end) => &data[..end],
+                    None => data,
+                };
+                let params: BTreeMap<String, String> = String::from_utf8_lossy(control)
+                    .split(',')
+                    .filter_map(|pair| pair.split_once('='))
+                    .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                    .collect();
+
+                match params.get("a").map(String::as_str) {
+                    None | Some("t") | Some("T") => Some(KittyImage {
+                        row: region.start_row,
+                        col: region.start_col,
+                        params,
+                    }),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns all iTerm2 inline image regions currently on screen.
+    ///
+    /// This method provides access to all iTerm2 inline images that have been rendered
+    /// via OSC 1337 sequences. Each region includes position and dimension information.
+    ///
+    /// # Returns
+    ///
+    /// A slice of [`ITerm2Region`] containing all detected iTerm2 inline images.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// // ... render some iTerm2 inline images ...
+    ///
+    /// let regions = screen.iterm2_regions();
+    /// for (i, region) in regions.iter().enumerate() {
+    ///     println!(
+    ///         "iTerm2 region {}: position ({}, {}), size {}x{}",
+    ///         i, region.start_row, region.start_col, region.width, region.height
+    ///     );
+    /// }
+    /// ```
+    pub fn iterm2_regions(&self) -> &[ITerm2Region] {
+        &self.state.iterm2_regions
+    }
+
+    /// Returns a mutable reference to the iTerm2 regions.
+    ///
+    /// This is primarily intended for testing purposes.
+    ///
+    /// # Returns
+    ///
+    /// A mutable reference to the vector of iTerm2 regions.
+    pub fn iterm2_regions_mut(&mut self) -> &mut Vec<ITerm2Region> {
+        &mut self.state.iterm2_regions
+    }
+
+    /// Returns every OSC 8 hyperlink written, in the order they were opened.
+    ///
+    /// Each link records its target, where its text started, and the text
+    /// printed until the link was closed (`ESC ] 8 ; ; ST`). Closing sequences
+    /// don't produce entries.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"See \x1b]8;;https://example.com/docs\x1b\\the docs\x1b]8;;\x1b\\.");
+    ///
+    /// let links = screen.hyperlinks();
+    /// assert_eq!(links.len(), 1);
+    /// assert_eq!(links[0].uri, "https://example.com/docs");
+    /// assert_eq!(links[0].text, "the docs");
+    /// assert_eq!((links[0].row, links[0].col), (0, 4));
+    /// ```
+    pub fn hyperlinks(&self) -> Vec<Hyperlink> {
+        self.state.hyperlinks.clone()
+    }
+
+    /// Returns the most recent window title set with OSC 0 or OSC 2.
+    ///
+    /// # Returns
+    ///
+    /// The title from the last `ESC ] 0 ; title BEL` or `ESC ] 2 ; title BEL`,
+    /// or `None` if no title has been set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// assert_eq!(screen.window_title(), None);
+    ///
+    /// screen.feed(b"\x1b]0;editor - main.rs\x07");
+    /// assert_eq!(screen.window_title().as_deref(), Some("editor - main.rs"));
+    /// ```
+    pub fn window_title(&self) -> Option<String> {
+        self.state.window_title.clone()
+    }
+
+    /// Returns a marker for every inline image detected, in stream order.
+    ///
+    /// Covers Sixel (`ESC P q`), Kitty (`ESC _ G`) and iTerm2 (`ESC ] 1337;File=`)
+    /// images, so tests for apps using non-Sixel protocols can verify image
+    /// placement the same way.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{ImageMarker, ImageProtocol, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[3;5H\x1b]1337;File=inline=1:SGVsbG8=\x07");
+    ///
+    /// assert_eq!(
+    ///     screen.image_markers(),
+    ///     &[ImageMarker { protocol: ImageProtocol::ITerm2, row: 2, col: 4 }]
+    /// );
+    /// ```
+    pub fn image_markers(&self) -> &[ImageMarker] {
+        &self.state.image_markers
+    }
+
+    /// Returns the attributes that will be applied to the next printed character.
+    ///
+    /// Reflects all SGR sequences processed so far, even if no text was printed
+    /// with them. Use this to check that an application reset the pen before
+    /// exiting, or that it established an expected default pen.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{CellStyle, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[1;31mError\x1b[0m");
+    ///
+    /// assert_eq!(screen.current_pen(), CellStyle::default());
+    /// ```
+    pub fn current_pen(&self) -> CellStyle {
+        CellStyle {
+            fg: self.state.current_fg,
+            bg: self.state.current_bg,
+            bold: self.state.current_bold,
+            italic: self.state.current_italic,
+            underline: self.state.current_underline,
+            inverse: self.state.current_inverse,
+            blink: self.state.current_blink,
+            fg_rgb: self.state.current_fg_rgb,
+            bg_rgb: self.state.current_bg_rgb,
+        }
+    }
+
+    /// Returns the screen contents for debugging purposes.
+    ///
+    /// This is currently an alias for [`contents()`](Self::contents), but may
+    /// include additional debug information in the future.
+    ///
+    /// # Returns
+    ///
+    /// A string containing the screen contents.
+    pub fn debug_contents(&self) -> String {
+        self.contents()
+    }
+
+    /// Checks if the screen contains the specified text.
+    ///
+    /// This is a convenience method that searches the entire screen contents
+    /// for the given substring. It's useful for simple text-based assertions
+    /// in tests.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to search for
+    ///
+    /// # Returns
+    ///
+    /// `true` if the text appears anywhere on the screen, `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"Welcome to the application");
+    ///
+    /// assert!(screen.contains("Welcome"));
+    /// assert!(screen.contains("application"));
+    /// assert!(!screen.contains("goodbye"));
+    /// ```
+    pub fn contains(&self, text: &str) -> bool {
+        self.contents().contains(text)
+    }
+
+    /// Checks if the screen contains the specified text, ignoring case.
+    ///
+    /// Like [`contains()`](Self::contains), but both the screen contents and
+    /// `text` are lowercased first, so "Error", "ERROR" and "error" all match.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - Text to search for
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"[ERROR] disk full");
+    ///
+    /// assert!(screen.contains_ignore_case("error"));
+    /// assert!(!screen.contains("error"));
+    /// ```
+    pub fn contains_ignore_case(&self, text: &str) -> bool {
+        self.contents()
+            .to_lowercase()
+            .contains(&text.to_lowercase())
+    }
+
+    /// Finds the first occurrence of text on screen.
+    ///
+    /// Rows are scanned top to bottom using [`row_contents`](Self::row_contents);
+    /// matches never span row boundaries.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to search for
+    ///
+    /// # Returns
+    ///
+    /// The `(row, col)` of the first character of the first match (0-based), or
+    /// None if the text is not on screen.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"Files\x1b[2;41HERROR: disk full");
+    ///
+    /// assert_eq!(screen.find("ERROR"), Some((1, 40)));
+    /// assert_eq!(screen.find("WARN"), None);
+    /// ```
+    pub fn find(&self, text: &str) -> Option<(u16, u16)> {
+        if text.is_empty() {
+            return None;
+        }
+        (0..self.height).find_map(|row| {
+            let (contents, columns) = self.row_text_with_columns(row);
+            let byte_idx = contents.find(text)?;
+            Some((row, columns[byte_idx]))
+        })
+    }
+
+    /// Finds every occurrence of text on screen.
+    ///
+    /// Like [`find`](Self::find), but returns all non-overlapping matches,
+    /// scanned top to bottom and left to right. After a match, scanning resumes
+    /// past its end, so `"aa"` occurs twice in `"aaaa"`, not three times.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to search for
+    ///
+    /// # Returns
+    ///
+    /// The `(row, col)` of each match (0-based), or an empty vector if there are
+    /// none.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed("│ a │ b │\r\n│ c │ d │".as_bytes());
+    ///
+    /// assert_eq!(screen.find_all("│").len(), 6);
+    /// assert_eq!(screen.find_all("│")[3], (1, 0));
+    /// ```
+    pub fn find_all(&self, text: &str) -> Vec<(u16, u16)> {
+        if text.is_empty() {
+            return Vec::new();
+        }
+        (0..self.height)
+            .flat_map(|row| {
+                let (contents, columns) = self.row_text_with_columns(row);
+                contents
+                    .match_indices(text)
+                    .map(|(byte_idx, _)| (row, columns[byte_idx]))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Counts the occurrences of text on screen.
+    ///
+    /// Matches are counted per row and never span rows. Like
+    /// [`find_all`](Self::find_all), occurrences don't overlap: `"aa"` occurs
+    /// twice in `"aaaa"`. Handy for checking that a list rendered exactly the
+    /// expected number of items.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to count
+    ///
+    /// # Returns
+    ///
+    /// The number of matches, or 0 if `text` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed("● one\r\n● two\r\n○ three ● four".as_bytes());
+    ///
+    /// assert_eq!(screen.count_matches("●"), 3);
+    /// assert_eq!(screen.count_matches("○"), 1);
+    /// ```
+    pub fn count_matches(&self, text: &str) -> usize {
+        if text.is_empty() {
+            return 0;
+        }
+        (0..self.height)
+            .map(|row| self.row_contents(row).matches(text).count())
+            .sum()
+    }
+
+    /// Returns whether any row matches a regular expression.
+    ///
+    /// Useful for output containing variable data such as timestamps or
+    /// counters. The pattern is matched against each row's contents separately
+    /// (see [`row_contents`](Self::row_contents)), so matches never span rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Regular expression in [`regex`] syntax
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`](crate::TermTestError::Parse) if the
+    /// pattern is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"Last sync: 14:03:27");
+    ///
+    /// assert!(screen.contains_regex(r"\d{2}:\d{2}:\d{2}")?);
+    /// assert!(!screen.contains_regex(r"^Error")?);
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn contains_regex(&self, pattern: &str) -> crate::Result<bool> {
+        Ok(self.find_regex(pattern)?.is_some())
+    }
+
+    /// Finds the first match of a regular expression on screen.
+    ///
+    /// Rows are scanned top to bottom, matching each row's contents separately
+    /// like [`contains_regex`](Self::contains_regex).
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Regular expression in [`regex`] syntax
+    ///
+    /// # Returns
+    ///
+    /// The `(row, col)` where the first match starts (0-based), or None.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`](crate::TermTestError::Parse) if the
+    /// pattern is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"Status\r\n  3 of 10 done");
+    ///
+    /// assert_eq!(screen.find_regex(r"\d+ of \d+")?, Some((1, 2)));
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn find_regex(&self, pattern: &str) -> crate::Result<Option<(u16, u16)>> {
+        Ok(self.find_regex_with(&Self::compile_regex(pattern)?))
+    }
+
+    /// Finds the first match of an already compiled regular expression.
+    ///
+    /// Same as [`find_regex`](Self::find_regex), but the pattern is compiled
+    /// once by the caller. Prefer it when checking the screen repeatedly, for
+    /// example inside a [`wait_for`](crate::TuiTestHarness::wait_for) condition.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let progress = regex::Regex::new(r"\d+ of \d+").unwrap();
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"Status\r\n  3 of 10 done");
+    ///
+    /// assert_eq!(screen.find_regex_with(&progress), Some((1, 2)));
+    /// ```
+    pub fn find_regex_with(&self, re: &regex::Regex) -> Option<(u16, u16)> {
+        (0..self.height).find_map(|row| {
+            let (contents, columns) = self.row_text_with_columns(row);
+            let m = re.find(&contents)?;
+            Some((row, columns[m.start()]))
+        })
+    }
+
+    /// Compiles a user-supplied pattern, reporting syntax errors as parse errors.
+    fn compile_regex(pattern: &str) -> crate::Result<regex::Regex> {
+        regex::Regex::new(pattern)
+            .map_err(|e| crate::TermTestError::Parse(format!("Invalid regex /{}/: {}", pattern, e)))
+    }
+
+    /// Returns whether the cursor's row matches a regular expression.
+    ///
+    /// Interactive programs leave the cursor on their prompt line, so this
+    /// checks the prompt wherever it ended up instead of at a fixed row. The
+    /// row's full contents are matched, including trailing blanks (see
+    /// [`row_contents`](Self::row_contents)).
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - Regular expression in [`regex`] syntax
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`](crate::TermTestError::Parse) if the
+    /// pattern is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"$ echo hi\r\nhi\r\n$ ");
+    ///
+    /// assert!(screen.prompt_matches(r"^\$\s*$")?);
+    /// assert!(!screen.prompt_matches(r"echo")?);
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn prompt_matches(&self, pattern: &str) -> crate::Result<bool> {
+        Ok(self.prompt_matches_with(&Self::compile_regex(pattern)?))
+    }
+
+    /// Returns whether the cursor's row matches an already compiled regular
+    /// expression.
+    ///
+    /// Same as [`prompt_matches`](Self::prompt_matches), but the pattern is
+    /// compiled once by the caller, which suits polling for a prompt.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let prompt = regex::Regex::new(r"^\$\s*$").unwrap();
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"$ echo hi\r\nhi\r\n$ ");
+    ///
+    /// assert!(screen.prompt_matches_with(&prompt));
+    /// ```
+    pub fn prompt_matches_with(&self, re: &regex::Regex) -> bool {
+        re.is_match(&self.row_contents(self.state.cursor_pos.0))
+    }
+
+    /// Returns whether escape sequences appear on screen as literal text.
+    ///
+    /// An application that forwards input or output without handling it can
+    /// leave visible garbage such as `^[[A` (caret notation, as printed by
+    /// `cat -v` or a terminal in cooked mode) or a `␛` control picture. This
+    /// checks for those forms, including the escaped spellings `\x1b[`,
+    /// `\033[` and `\e[`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[1mreal bold\x1b[0m");
+    /// assert!(!screen.has_literal_escapes());
+    ///
+    /// screen.feed(b" ^[[A");
+    /// assert!(screen.has_literal_escapes());
+    /// ```
+    pub fn has_literal_escapes(&self) -> bool {
+        const PATTERNS: [&str; 5] = ["^[", "\u{241B}", "\\x1b[", "\\033[", "\\e["];
+        let contents = self.contents();
+        PATTERNS.iter().any(|pattern| contents.contains(pattern))
+    }
+
+    /// Returns the width of the widest visible line on screen.
+    ///
+    /// Each row is measured after trimming trailing whitespace, so padding cells
+    /// do not count towards the width. This is useful for asserting that content
+    /// fits within a target width or for detecting unexpected wrapping.
+    ///
+    /// # Returns
+    ///
+    /// The trimmed length of the longest row in columns, or 0 if the screen is blank.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"short\r\na much longer line\r\nmid line");
+    ///
+    /// assert_eq!(screen.max_line_width(), 18);
+    /// ```
+    pub fn max_line_width(&self) -> u16 {
+        self.state
+            .cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.c)
+                    .collect::<String>()
+                    .trim_end()
+                    .chars()
+                    .count() as u16
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the lines of content on screen.
+    ///
+    /// Each row is trimmed of trailing whitespace, and blank rows after the last
+    /// row with content are dropped. Rows are never split on newlines in the
+    /// input, so a final line that was not terminated by a newline (e.g. the
+    /// output of `printf 'a\nb'`) counts as a full line, exactly like one that was.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"first\r\nsecond");
+    ///
+    /// assert_eq!(screen.lines(), vec!["first", "second"]);
+    /// ```
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .state
+            .cells
+            .iter()
+            .map(|row| Self::row_text(row).trim_end().to_string())
+            .collect();
+
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        lines
+    }
+
+    /// Returns the number of rows from the top of the screen to the last row
+    /// with content.
+    ///
+    /// This is the length of [`lines()`](Self::lines): interior blank rows are
+    /// counted, trailing blank rows are not, and an unterminated final line
+    /// counts as a line.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"header\r\n\r\nfooter");
+    ///
+    /// assert_eq!(screen.content_height(), 3);
+    /// ```
+    pub fn content_height(&self) -> u16 {
+        self.lines().len() as u16
+    }
+
+    /// Returns the smallest rectangle enclosing all non-blank cells.
+    ///
+    /// A cell is blank if its character is whitespace, as in
+    /// [`assert_region_blank()`](Self::assert_region_blank). Useful for asserting
+    /// that a layout is compact or centered without caring about exact positions.
+    ///
+    /// # Returns
+    ///
+    /// The bounding rectangle, or None if the screen is blank.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{Rect, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[5;30H+------+\x1b[6;30H| Done |\x1b[7;30H+------+");
+    ///
+    /// assert_eq!(screen.content_bounds(), Some(Rect::new(29, 4, 8, 3)));
+    /// ```
+    pub fn content_bounds(&self) -> Option<Rect> {
+        let mut bounds: Option<(u16, u16, u16, u16)> = None;
+        for (row, cells) in self.state.cells.iter().enumerate() {
+            let mut cols = cells
+                .iter()
+                .enumerate()
+                .filter(|(_, cell)| !cell.c.is_whitespace())
+                .map(|(col, _)| col as u16);
+            let Some(first) = cols.next() else {
+                continue;
+            };
+            let last = cols.next_back().unwrap_or(first);
+            let row = row as u16;
+            bounds = Some(match bounds {
+                Some((top, left, _, right)) => (top, left.min(first), row, right.max(last)),
+                None => (row, first, row, last),
+            });
+        }
+
+        bounds.map(|(top, left, bottom, right)| {
+            Rect::new(left, top, right - left + 1, bottom - top + 1)
+        })
+    }
+
+    /// Asserts that every cell in a rectangle is blank.
+    ///
+    /// A cell is blank if its character is whitespace; attributes such as a
+    /// background color are ignored. Parts of the rectangle outside the screen
+    /// are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `rect` - The area that should be empty
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TermTestError::Parse`](crate::TermTestError::Parse) listing the
+    /// position and character of each non-blank cell.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{Rect, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"Sidebar");
+    ///
+    /// // The main panel to the right of the sidebar is empty
+    /// screen.assert_region_blank(Rect::new(20, 0, 60, 24))?;
+    /// assert!(screen.assert_region_blank(Rect::new(0, 0, 20, 1)).is_err());
+    /// # Ok::<(), ratatui_testlib::TermTestError>(())
+    /// ```
+    pub fn assert_region_blank(&self, rect: Rect) -> crate::Result<()> {
+        let mut non_blank = Vec::new();
+        for row in rect.y..rect.bottom().min(self.height) {
+            for col in rect.x..rect.right().min(self.width) {
+                let c = self.state.cells[row as usize][col as usize].c;
+                if !c.is_whitespace() {
+                    non_blank.push(format!("({}, {}) {:?}", row, col, c));
+                }
+            }
+        }
+
+        if non_blank.is_empty() {
+            return Ok(());
+        }
+
+        Err(crate::TermTestError::Parse(format!(
+            "Region {:?} is not blank: {} non-blank cell(s) at {}\n\nScreen state:\n{}",
+            rect,
+            non_blank.len(),
+            non_blank.join(", "),
+            self.debug_contents()
+        )))
+    }
+
+    /// Checks whether text is horizontally centered within the screen width.
+    ///
+    /// Finds the first occurrence of `text` (searching rows top to bottom) and
+    /// compares the blank columns to its left and right. An odd leftover column
+    /// can't be split evenly, so a tolerance of at least 1 is usually appropriate
+    /// for rendered layouts.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text to locate
+    /// * `tolerance` - Maximum allowed difference between left and right margins
+    ///
+    /// # Returns
+    ///
+    /// `true` if the text is found and its margins differ by at most `tolerance`,
+    /// `false` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(20, 5);
+    /// screen.feed(b"\x1b[3;8HDialog"); // 7 columns left, 7 right
+    ///
+    /// assert!(screen.is_centered("Dialog", 0));
+    /// ```
+    pub fn is_centered(&self, text: &str, tolerance: u16) -> bool {
+        if text.is_empty() {
+            return false;
+        }
+
+        (0..self.height)
+            .find_map(|row| {
+                let (line, columns) = self.row_text_with_columns(row);
+                let byte_idx = line.find(text)?;
+                Some((columns[byte_idx], columns[byte_idx + text.len()]))
+            })
+            .is_some_and(|(left, end)| {
+                let right = self.width.saturating_sub(end);
+                left.abs_diff(right) <= tolerance
+            })
+    }
+
+    /// Extracts all numeric values from the screen in reading order.
+    ///
+    /// Scans each row left to right for integers and decimals (e.g. `45`, `3.14`,
+    /// `-2`). A leading `-` counts as a sign only when it does not directly follow
+    /// a letter or digit, so ranges like `10-20` yield `10` and `20`. Digit groups
+    /// separated by commas are read as separate numbers.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"CPU 45% MEM 78%\r\nLoad: 1.25");
+    ///
+    /// let numbers = screen.numbers();
+    /// assert_eq!(numbers, vec![45.0, 78.0, 1.25]);
+    /// assert!(numbers.iter().all(|&n| n <= 100.0));
+    /// ```
+    pub fn numbers(&self) -> Vec<f64> {
+        (0..self.height)
+            .flat_map(|row| self.numbers_in_row(row))
+            .collect()
+    }
+
+    /// Extracts all numeric values from a single row, left to right.
+    ///
+    /// See [`numbers()`](Self::numbers) for the recognized formats.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// The numbers found in the row, or an empty vector if the row is out of bounds.
+    pub fn numbers_in_row(&self, row: u16) -> Vec<f64> {
+        static NUMBER: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        let re = NUMBER
+            .get_or_init(|| regex::Regex::new(r"-?\d+(?:\.\d+)?").expect("valid number pattern"));
+        let text = self.row_contents(row);
+
+        re.find_iter(&text)
+            .filter_map(|m| {
+                let token = m.as_str();
+                let follows_word = text[..m.start()]
+                    .chars()
+                    .next_back()
+                    .is_some_and(char::is_alphanumeric);
+                let token = match token.strip_prefix('-') {
+                    Some(unsigned) if follows_word => unsigned,
+                    _ => token,
+                };
+                token.parse::<f64>().ok()
+            })
+            .collect()
+    }
+
+    /// Checks whether a cell was explicitly written by the application.
+    ///
+    /// A freshly created (or freshly entered alternate) screen is blank, but its
+    /// cells are *unwritten*. Printing any character, including a space, marks a
+    /// cell as written. This distinguishes an application that drew spaces from
+    /// one that left cells untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// `true` if the cell has been written, `false` if it is untouched or out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"a  b");
+    ///
+    /// assert!(screen.is_cell_written(0, 1)); // explicit space
+    /// assert!(!screen.is_cell_written(0, 4)); // never touched
+    /// ```
+    pub fn is_cell_written(&self, row: u16, col: u16) -> bool {
+        row < self.height && col < self.width && self.state.written[row as usize][col as usize]
+    }
+
+    /// Checks whether a row ends in whitespace that was actually written.
+    ///
+    /// Cells that were never printed to are ignored, so a short line on a wide
+    /// screen does not count as having trailing whitespace. Only spaces (or other
+    /// whitespace) explicitly output after the last visible character do, which
+    /// is what causes artifacts when the terminal is resized.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    ///
+    /// # Returns
+    ///
+    /// `true` if the last written cell in the row is whitespace, `false` otherwise
+    /// (including for blank or out-of-bounds rows).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"clean\r\npadded   ");
+    ///
+    /// assert!(!screen.row_has_trailing_whitespace(0));
+    /// assert!(screen.row_has_trailing_whitespace(1));
+    /// ```
+    pub fn row_has_trailing_whitespace(&self, row: u16) -> bool {
+        if row >= self.height {
+            return false;
+        }
+
+        let cells = &self.state.cells[row as usize];
+        let written = &self.state.written[row as usize];
+        written
+            .iter()
+            .rposition(|&w| w)
+            .map(|col| cells[col].c.is_whitespace())
+            .unwrap_or(false)
+    }
+
+    /// Checks whether two rows have the same trimmed contents.
+    ///
+    /// Trailing whitespace is ignored, so a header and its underline compare equal
+    /// only when the visible text matches. Out-of-bounds rows compare as empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - First row index (0-based)
+    /// * `b` - Second row index (0-based)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"-----\r\n-----\r\n---");
+    ///
+    /// assert!(screen.rows_equal(0, 1));
+    /// assert!(!screen.rows_equal(0, 2));
+    /// ```
+    pub fn rows_equal(&self, a: u16, b: u16) -> bool {
+        self.row_contents(a).trim_end() == self.row_contents(b).trim_end()
+    }
+
+    /// Returns the number of absolute cursor positioning sequences processed.
+    ///
+    /// Counts CUP (`ESC [ row ; col H`) and HVP (`ESC [ row ; col f`) sequences
+    /// fed into this screen. Together with [`relative_moves()`](Self::relative_moves)
+    /// this shows how an application repositions the cursor while rendering.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[5;10Hmenu\x1b[2A");
+    ///
+    /// assert_eq!(screen.absolute_moves(), 1);
+    /// assert_eq!(screen.relative_moves(), 1);
+    /// ```
+    pub fn absolute_moves(&self) -> usize {
+        self.state.absolute_moves
+    }
+
+    /// Returns the number of relative cursor movement sequences processed.
+    ///
+    /// Counts CUU, CUD, CUF and CUB (`ESC [ n A/B/C/D`) sequences fed into this
+    /// screen. See [`absolute_moves()`](Self::absolute_moves).
+    pub fn relative_moves(&self) -> usize {
+        self.state.relative_moves
+    }
+
+    /// Takes the terminal queries (cursor-position requests, device attributes
+    /// and DECRQSS) received since the last call.
+    pub(crate) fn take_queries(&mut self) -> Vec<TerminalQuery> {
+        std::mem::take(&mut self.state.queries)
+    }
+
+    /// Returns the number of erase-display sequences that cleared the whole screen.
+    ///
+    /// Counts `ESC [ 2 J`, as well as `ESC [ J` issued with the cursor at the
+    /// top-left corner, which also clears everything. `ESC [ 3 J` only clears
+    /// the scrollback and is not counted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"$ prompt\x1b[2J\x1b[HApp");
+    ///
+    /// assert_eq!(screen.erase_display_count(), 1);
+    /// assert!(!screen.contains("prompt"));
+    /// ```
+    pub fn erase_display_count(&self) -> usize {
+        self.state.erase_display_count
+    }
+
+    /// Returns the number of full-screen clears (`ESC [ 2 J` and `ESC [ 3 J`).
+    ///
+    /// Each one usually means the application redrew the whole screen, so
+    /// counting them over an interaction catches inefficient rendering. Unlike
+    /// [`erase_display_count()`](Self::erase_display_count), `ESC [ J` from the
+    /// top-left corner is not counted, while `ESC [ 3 J` is.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[2J\x1b[Hframe 1\x1b[H\x1b[Jframe 2\x1b[3J");
+    ///
+    /// assert_eq!(screen.full_redraw_count(), 2);
+    /// ```
+    pub fn full_redraw_count(&self) -> usize {
+        self.state.full_redraws
+    }
+
+    /// Marks the start of a new frame for [`overdraw_count()`](Self::overdraw_count).
+    ///
+    /// Resets the per-cell write counts. Call it after each frame an application
+    /// draws, for example after waiting for a keypress to be handled.
+    pub fn mark_frame(&mut self) {
+        let state = &mut self.state;
+        for counts in [&mut state.frame_writes, &mut state.inactive_frame_writes] {
+            for row in counts.iter_mut() {
+                row.fill(0);
+            }
+        }
+    }
+
+    /// Returns the number of cells written more than once since the last frame mark.
+    ///
+    /// Drawing the same cell several times per frame is wasted work, so a
+    /// non-zero count points at inefficient rendering. Counts cover everything
+    /// fed since the screen was created or [`mark_frame()`](Self::mark_frame) was
+    /// last called, and move with the content when the screen scrolls. The main
+    /// and alternate screens are counted separately.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"Loading\x1b[HReady  ");
+    /// assert_eq!(screen.overdraw_count(), 7);
+    ///
+    /// screen.mark_frame();
+    /// screen.feed(b"\x1b[2;1HDone");
+    /// assert_eq!(screen.overdraw_count(), 0);
+    /// ```
+    pub fn overdraw_count(&self) -> usize {
+        self.state
+            .frame_writes
+            .iter()
+            .flatten()
+            .filter(|&&count| count > 1)
+            .count()
+    }
+
+    /// Returns the number of times the alternate screen was entered.
+    ///
+    /// See [`alt_screen_contents()`](Self::alt_screen_contents).
+    pub fn alt_screen_entries(&self) -> usize {
+        self.state.alt_screen_entries
+    }
+
+    /// Returns every cursor shape change (DECSCUSR) processed, in order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{CursorShape, ScreenState};
+    ///
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(b"\x1b[6 q"); // insert mode: steady bar
+    /// screen.feed(b"\x1b[2 q"); // normal mode: steady block
+    ///
+    /// assert_eq!(
+    ///     screen.cursor_shape_history(),
+    ///     vec![CursorShape::SteadyBar, CursorShape::SteadyBlock]
+    /// );
+    /// ```
+    pub fn cursor_shape_history(&self) -> Vec<CursorShape> {
+        self.state.cursor_shapes.clone()
+    }
+
+    /// Returns the number of line feeds processed.
+    ///
+    /// Unlike [`content_height()`](Self::content_height), this keeps counting once
+    /// output starts scrolling, so it measures how many lines an application has
+    /// produced in total.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(80, 2);
+    /// screen.feed(b"one\r\ntwo\r\nthree\r\n");
+    ///
+    /// assert_eq!(screen.line_feeds(), 3);
+    /// assert_eq!(screen.content_height(), 1);
+    /// ```
+    pub fn line_feeds(&self) -> usize {
+        self.state.line_feeds
+    }
+
+    /// Returns whether the content shifted up since the last update.
+    ///
+    /// A line feed on the bottom row scrolls the whole screen up by one line, as
+    /// happens when a streaming log outputs more lines than fit. The flag stays
+    /// set until [`clear_scrolled`](Self::clear_scrolled) is called, however
+    /// many [`feed`](Self::feed) calls follow. A
+    /// [`TuiTestHarness`](crate::TuiTestHarness) clears it at the start of each
+    /// [`update_state`](crate::TuiTestHarness::update_state), so there it covers
+    /// the output processed by the most recent update.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(20, 3);
+    /// screen.feed(b"one\r\ntwo\r\nthree");
+    /// assert!(!screen.did_scroll_up());
+    ///
+    /// screen.feed(b"\r\nfour");
+    /// screen.feed(b"!");
+    /// assert!(screen.did_scroll_up());
+    /// assert_eq!(screen.row_contents(0).trim_end(), "two");
+    ///
+    /// screen.clear_scrolled();
+    /// assert!(!screen.did_scroll_up());
+    /// ```
+    pub fn did_scroll_up(&self) -> bool {
+        self.state.scrolled_lines > 0
+    }
+
+    /// Starts a new update for [`did_scroll_up()`](Self::did_scroll_up).
+    pub fn clear_scrolled(&mut self) {
+        self.state.scrolled_lines = 0;
+    }
+
+    /// Returns the contents of the main (primary) screen buffer.
+    ///
+    /// While an application is on the alternate screen (entered via
+    /// `ESC [ ? 1049 h`, `1047` or `47`), [`contents()`](Self::contents) shows the
+    /// alternate buffer. This method always returns the main buffer, which lets
+    /// tests verify that an application restored it untouched on exit.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(20, 5);
+    /// screen.feed(b"$ shell prompt");
+    /// screen.feed(b"\x1b[?1049h\x1b[HFull screen app");
+    ///
+    /// assert!(screen.main_screen_contents().contains("$ shell prompt"));
+    /// assert!(!screen.main_screen_contents().contains("Full screen app"));
+    /// ```
+    pub fn main_screen_contents(&self) -> String {
+        if self.state.alt_screen_active {
+            Self::render_cells(&self.state.inactive_cells)
+        } else {
+            Self::render_cells(&self.state.cells)
+        }
+    }
+
+    /// Returns the contents of the alternate screen buffer.
+    ///
+    /// When the alternate screen is not active, this returns whatever was left in
+    /// it the last time it was used (blank if it was never entered).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::ScreenState;
+    ///
+    /// let mut screen = ScreenState::new(20, 5);
+    /// screen.feed(b"\x1b[?1049hMenu\x1b[?1049l");
+    ///
+    /// assert!(screen.alt_screen_contents().contains("Menu"));
+    /// assert!(!screen.contains("Menu"));
+    /// ```
+    pub fn alt_screen_contents(&self) -> String {
+        if self.state.alt_screen_active {
+            Self::render_cells(&self.state.cells)
+        } else {
+            Self::render_cells(&self.state.inactive_cells)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_screen() {
+        let screen = ScreenState::new(80, 24);
+        assert_eq!(screen.size(), (80, 24));
+    }
+
+    #[test]
+    fn test_feed_simple_text() {
+        let mut screen = ScreenState::new(80, 24);
+        screen.feed(b"Hello, World!");
+        assert!(screen.contents().contains("Hello, World!"));
+    }
+
+    #[test]
+    fn test_cursor_position() {
+        let mut screen = ScreenState::new(80, 24);
+
+        // Initial position
+        assert_eq!(screen.cursor_position(), (0, 0));
+
+        // Move cursor using CSI sequence (ESC [ 5 ; 10 H = row 5, col 10)
+        screen.feed(b"\x1b[5;10H");
+        let (row, col) = screen.cursor_position();
+
+        // CSI uses 1-based, we convert to 0-based
+        assert_eq!(row, 4); // 5-1 = 4
+        assert_eq!(col, 9); // 10-1 = 9
+    }
+
+    #[test]
+    fn test_text_at() {
+        let mut screen = ScreenState::new(80, 24);
+        screen.feed(b"Test");
+
+        assert_eq!(screen.text_at(0, 0), Some('T'));
+        assert_eq!(screen.text_at(0, 1), Some('e'));
+        assert_eq!(screen.text_at(0, 2), Some('s'));
+        assert_eq!(screen.text_at(0, 3), Some('t'));
+        assert_eq!(screen.text_at(0, 4), Some(' '));
+        assert_eq!(screen.text_at(100, 100), None);
+    }
+
+    #[test]
+    fn test_sixel_stream_range() {
+        let mut screen = ScreenState::new(80, 24);
+        let dcs: &[u8] = b"\x1bPq\"1;1;16;12#0~\x1b\\";
+
+        let mut stream = b"before \x1b[1mbold\x1b[0m ".to_vec();
+        stream.extend_from_slice(dcs);
+        stream.extend_from_slice(b" after");
+
+        // Split the stream to make sure offsets carry across feeds
+        let (first, second) = stream.split_at(10);
+        screen.feed(first);
+        screen.feed(second);
+
+        let range = screen.sixel_stream_range(0).unwrap();
+        assert_eq!(&stream[range.clone()], dcs);
+
+        // Removing the range reconstructs the stream without the image
+        let mut stripped = stream[..range.start].to_vec();
+        stripped.extend_from_slice(&stream[range.end..]);
+        assert_eq!(stripped, b"before \x1b[1mbold\x1b[0m  after".to_vec());
+    }
+
+    #[test]
+    fn test_is_wrapped_line() {
+        let mut screen = ScreenState::new(10, 4);
+        screen.feed(b"0123456789");
+
+        // Filling the last column alone doesn't wrap
+        assert_eq!(screen.cursor_position(), (0, 9));
+        assert!(!screen.is_wrapped_line(1));
+
+        screen.feed(b"abcdefghijklm\r\nnext");
+        assert_eq!(screen.lines(), vec!["0123456789", "abcdefghij", "klm", "next"]);
+        assert!(!screen.is_wrapped_line(0));
+        assert!(screen.is_wrapped_line(1));
+        assert!(screen.is_wrapped_line(2));
+        assert!(!screen.is_wrapped_line(3));
+        assert!(!screen.is_wrapped_line(10));
+
+        // Wrapping at the bottom scrolls and keeps the flags with their rows
+        screen.feed(b"\x1b[4;1H0123456789X");
+        assert_eq!(screen.lines()[3], "X");
+        assert!(screen.is_wrapped_line(0));
+        assert!(screen.is_wrapped_line(1));
+        assert!(screen.is_wrapped_line(3));
+
+        // With autowrap disabled, output stays on the last column
+        screen.feed(b"\x1b[?7l\x1b[2J\x1b[1;1H0123456789XY");
+        assert_eq!(screen.lines(), vec!["012345678Y"]);
+        assert!(!screen.is_wrapped_line(1));
+    }
+
+    #[test]
+    fn test_has_literal_escapes() {
+        let mut screen = ScreenState::new(40, 3);
+        screen.feed(b"\x1b[31m[OK]\x1b[0m path\\to [e]");
+        assert!(!screen.has_literal_escapes());
+
+        for garbage in ["^[[B", "\u{241B}[0m", "\\x1b[2J", "\\033[H", "\\e[K"] {
+            let mut screen = ScreenState::new(40, 3);
+            screen.feed(garbage.as_bytes());
+            assert!(screen.has_literal_escapes(), "{:?} not detected", garbage);
+        }
+    }
+
+    #[test]
+    fn test_to_ansi_round_trip() {
+        let mut screen = ScreenState::new(12, 4);
+        screen.feed(b"\x1b[1;31mError\x1b[0m: \x1b[3;4mfile\x1b[0m\r\n");
+        screen.feed(b"\x1b[97;44m sel \x1b[7;5m inv \x1b[0m\r\n");
+        screen.feed("\x1b[38;5;208;48;5;17m日本\x1b[0m end".as_bytes());
+        screen.feed(b"\x1b[4;1H\x1b[42m      \x1b[0m");
+
+        let ansi = screen.to_ansi();
+        let mut replay = ScreenState::new(12, 4);
+        replay.feed(ansi.as_bytes());
+
+        for row in 0..4 {
+            for col in 0..12 {
+                assert_eq!(
+                    replay.get_cell(row, col),
+                    screen.get_cell(row, col),
+                    "cell ({}, {}) differs",
+                    row,
+                    col
+                );
+            }
+        }
+        assert_eq!(replay.contents(), screen.contents());
+        assert!(ansi.ends_with("\x1b[0m      "));
+        assert_eq!(ScreenState::new(3, 2).to_ansi(), "   \r\n   ");
+    }
+
+    #[cfg(feature = "ratatui-helpers")]
+    #[test]
+    fn test_render_ratatui_at() {
+        use ratatui::{
+            style::{Color as RColor, Style},
+            widgets::{Block, Paragraph},
+        };
+
+        let draw = |frame: &mut ratatui::Frame<'_>| {
+            let block = Block::bordered()
+                .title("Panel")
+                .border_style(Style::default().fg(RColor::Cyan));
+            let text = Paragraph::new("reflowing text in a panel").block(block);
+            frame.render_widget(text, frame.area());
+        };
+
+        let mut screen = ScreenState::new(10, 2);
+        screen.render_ratatui_at((80, 24), draw).unwrap();
+        assert_eq!(screen.size(), (80, 24));
+        assert_eq!(screen.row_contents(0), format!("┌Panel{}┐", "─".repeat(73)));
+        assert_eq!(screen.row_contents(23), format!("└{}┘", "─".repeat(78)));
+        assert!(screen
+            .row_contents(1)
+            .starts_with("│reflowing text in a panel "));
+        assert_eq!(screen.fg_color_at(0, 0), Some(Color::Indexed(6)));
+
+        let mut large = ScreenState::new(1, 1);
+        large.render_ratatui_at((80, 24), draw).unwrap();
+        assert!(large.diff(&screen).is_empty());
+
+        // Re-render the same screen at a smaller size
+        screen.render_ratatui_at((40, 12), draw).unwrap();
+        assert_eq!(screen.size(), (40, 12));
+        assert_eq!(screen.row_contents(0), format!("┌Panel{}┐", "─".repeat(33)));
+        assert_eq!(screen.row_contents(11), format!("└{}┘", "─".repeat(38)));
+        assert!(screen.row_contents(10).ends_with('│'));
+
+        let changed = large.diff(&screen);
+        assert!(changed.iter().any(|d| d.row == 0 && d.col == 39));
+
+        // The scrollback limit survives re-rendering
+        let mut screen = ScreenState::with_scrollback(10, 2, 10);
+        screen.render_ratatui_at((20, 3), draw).unwrap();
+        screen.feed(b"\x1b[3;1H\r\n");
+        assert!(screen.scrollback_contents().starts_with("┌Panel"));
+
+        // RGB colors are kept as 24-bit colors
+        let mut screen = ScreenState::new(10, 1);
+        screen
+            .render_ratatui_at((10, 1), |frame| {
+                let style = Style::default()
+                    .fg(RColor::Rgb(10, 20, 30))
+                    .bg(RColor::Rgb(1, 2, 3));
+                frame.render_widget(Paragraph::new("rgb").style(style), frame.area());
+            })
+            .unwrap();
+        assert_eq!(screen.fg_color_at(0, 0), Some(Color::Rgb(10, 20, 30)));
+        assert_eq!(screen.bg_color_at(0, 9), Some(Color::Rgb(1, 2, 3)));
+    }
+
+    #[test]
+    fn test_to_html() {
+        let mut screen = ScreenState::new(20, 3);
+        screen.feed(b"\x1b[1;97;44m Title \x1b[0m <ok>\r\n");
+        screen.feed(b"\x1b[38;5;208mwarn\x1b[4;5m!\x1b[0m\r\n");
+        screen.feed(b"\x1b[7msel\x1b[27;3;32m &");
+
+        assert_eq!(
+            screen.to_html(),
+            "<pre>\
+             <span style=\"color:#ffffff;background:#0000ee;font-weight:bold\"> Title </span> &lt;ok&gt;\n\
+             <span style=\"color:#ff8700\">warn</span>\
+             <span style=\"color:#ff8700;text-decoration:underline blink\">!</span>\n\
+             <span style=\"color:#000000;background:#e5e5e5\">sel</span>\
+             <span style=\"color:#00cd00;font-style:italic\"> &amp;</span>\
+             </pre>"
+        );
+        assert_eq!(ScreenState::new(4, 2).to_html(), "<pre>\n</pre>");
+    }
+
+    #[test]
+    fn test_cell() {
+        let mut screen = ScreenState::new(10, 3);
+        screen.feed(b"a\x1b[7;5;31mb\x1b[0m");
+
+        assert_eq!(screen.cell(0, 0), Some(Cell { c: 'a', ..Cell::default() }));
+        assert_eq!(screen.cell(0, 1), Some(Cell { c: 'b', fg: Some(1), ..Cell::default() }));
+        let attributes = screen.attributes_at(0, 1).unwrap();
+        assert!(attributes.inverse && attributes.blink);
+        assert_eq!(screen.cell(2, 9), Some(Cell::default()));
+        assert_eq!(screen.cell(3, 0), None);
+        assert_eq!(screen.cell(0, 10), None);
+    }
+
+    #[test]
+    fn test_fg_and_bg_color_at() {
+        let mut screen = ScreenState::new(20, 5);
+        screen.feed(b"  item\r\n\x1b[44;97m> selected\x1b[0m\r\n\x1b[38;5;196merror");
+
+        assert_eq!(screen.fg_color_at(0, 2), Some(Color::Default));
+        assert_eq!(screen.bg_color_at(0, 2), Some(Color::Default));
+        assert_eq!(screen.bg_color_at(1, 0), Some(Color::Indexed(4)));
+        assert_eq!(screen.fg_color_at(1, 0), Some(Color::Indexed(15)));
+        assert_eq!(screen.bg_color_at(1, 15), Some(Color::Default));
+        assert_eq!(screen.fg_color_at(2, 0), Some(Color::Indexed(196)));
+        assert_eq!(screen.fg_color_at(5, 0), None);
+        assert_eq!(screen.bg_color_at(0, 20), None);
+    }
+
+    #[test]
+    fn test_truecolor() {
+        let mut screen = ScreenState::new(20, 3);
+        screen.feed(b"\x1b[38;2;255;128;0mA\x1b[48;2;1;2;3mB\x1b[31mC\x1b[39;49mD");
+
+        assert_eq!(screen.fg_color_at(0, 0), Some(Color::Rgb(255, 128, 0)));
+        assert_eq!(screen.rgb_at(0, 0), Some((255, 128, 0)));
+        assert_eq!(screen.cell(0, 0).unwrap().fg, None);
+        assert_eq!(screen.bg_color_at(0, 1), Some(Color::Rgb(1, 2, 3)));
+        // An indexed color replaces the 24-bit one
+        assert_eq!(screen.fg_color_at(0, 2), Some(Color::Indexed(1)));
+        assert_eq!(screen.cell(0, 2).unwrap().fg, Some(1));
+        assert_eq!(screen.bg_color_at(0, 2), Some(Color::Rgb(1, 2, 3)));
+        assert_eq!(screen.cell(0, 3), Some(Cell { c: 'D', ..Cell::default() }));
+
+        let runs = screen.styled_runs(0);
+        assert_eq!(runs[0].fg, Color::Rgb(255, 128, 0));
+        assert_eq!(runs[1].bg, Color::Rgb(1, 2, 3));
+
+        // Erasing fills with the 24-bit background
+        screen.feed(b"\r\n\x1b[48;2;9;9;9m\x1b[K\x1b[0m");
+        assert_eq!(screen.bg_color_at(1, 19), Some(Color::Rgb(9, 9, 9)));
+
+        let ansi = screen.to_ansi();
+        assert!(ansi.starts_with("\x1b[0;38;2;255;128;0mA\x1b[0;38;2;255;128;0;48;2;1;2;3mB"));
+        let mut replay = ScreenState::new(20, 3);
+        replay.feed(ansi.as_bytes());
+        assert!(replay.diff(&screen).is_empty());
+        assert_eq!(replay.get_cell(1, 19), screen.get_cell(1, 19));
+
+        let html = screen.to_html();
+        assert!(html.starts_with("<pre><span style=\"color:#ff8000\">A</span>"));
+        assert!(html.contains("<span style=\"color:#ff8000;background:#010203\">B</span>"));
+    }
+
+    #[test]
+    fn test_styled_runs() {
+        let mut screen = ScreenState::new(20, 3);
+        screen.feed(b"\x1b[31mred\x1b[1;44mblue");
+
+        let runs = screen.styled_runs(0);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].text, "red");
+        assert_eq!(runs[0].fg, Color::Indexed(1));
+        assert_eq!(runs[0].bg, Color::Default);
+        assert_eq!(runs[0].start_col, 0);
+        assert_eq!(runs[1].text, "blue");
+        assert_eq!(runs[1].bg, Color::Indexed(4));
+        assert!(runs[1].style.bold);
+        assert_eq!(runs[1].start_col, 3);
+
+        assert!(screen.styled_runs(1).is_empty());
+        assert!(screen.styled_runs(3).is_empty());
+    }
+
+    #[test]
+    fn test_styled_runs_status_line() {
+        let mut screen = ScreenState::new(30, 3);
+        screen.feed("\x1b[1;31mFAIL\x1b[0m 日本 ok \x1b[4mdone".as_bytes());
+
+        let runs = screen.styled_runs(0);
+        assert_eq!(runs.len(), 3);
+        assert_eq!(runs[0].text, "FAIL");
+        assert_eq!(runs[0].fg, Color::Indexed(1));
+        assert_eq!(
+            runs[0].attributes(),
+            CellAttributes { bold: true, ..CellAttributes::default() }
+        );
+
+        // The rest is default apart from the underlined tail
+        assert_eq!(runs[1].text, " 日本 ok ");
+        assert_eq!(runs[1].style, CellStyle::default());
+        assert_eq!(runs[2].text, "done");
+        assert_eq!(runs[2].start_col, 13);
+        assert!(runs[2].attributes().underline);
+    }
+
+    #[test]
+    fn test_attributes_at() {
+        let mut screen = ScreenState::new(20, 3);
+        screen.feed(b"\x1b[1;3;4;5;7mA\x1b[22;23;24;25;27mB\x1b[7mC\x1b[0mD");
+
+        assert_eq!(
+            screen.attributes_at(0, 0),
+            Some(CellAttributes {
+                bold: true,
+                italic: true,
+                underline: true,
+                inverse: true,
+                blink: true,
+            })
+        );
+        assert_eq!(screen.attributes_at(0, 1), Some(CellAttributes::default()));
+        assert!(screen.attributes_at(0, 2).unwrap().inverse);
+        assert_eq!(screen.attributes_at(0, 3), Some(CellAttributes::default()));
+        assert_eq!(screen.attributes_at(3, 0), None);
+
+        assert!(screen.is_bold_at(0, 0));
+        assert!(!screen.is_bold_at(0, 1));
+        assert!(!screen.is_bold_at(0, 20));
+    }
+
+    #[test]
+    fn test_find() {
+        let mut screen = ScreenState::new(20, 4);
+        screen.feed("│ left │ ERROR\r\nERROR again".as_bytes());
+
+        // Column counts characters, not bytes
+        assert_eq!(screen.find("ERROR"), Some((0, 9)));
+        assert_eq!(screen.find("again"), Some((1, 6)));
+        assert_eq!(screen.find("│"), Some((0, 0)));
+        assert_eq!(screen.find("missing"), None);
+        assert_eq!(screen.find(""), None);
+
+        // No match across the row boundary
+        assert_eq!(screen.find("ERRORERROR"), None);
+    }
+
+    #[test]
+    fn test_find_all() {
+        let mut screen = ScreenState::new(12, 4);
+        screen.feed("┌──┬──┐\r\n│ab│aaaa\r\n└──┴──┘".as_bytes());
+
+        assert_eq!(screen.find_all("│"), vec![(1, 0), (1, 3)]);
+        assert_eq!(screen.find_all("┬"), vec![(0, 3)]);
+        assert_eq!(screen.find_all("──").len(), 4);
+
+        // Non-overlapping: resumes after the previous match
+        assert_eq!(screen.find_all("aa"), vec![(1, 4), (1, 6)]);
+
+        assert!(screen.find_all("zz").is_empty());
+        assert!(screen.find_all("").is_empty());
+    }
+
+    #[test]
+    fn test_contains_ignore_case() {
+        let mut screen = ScreenState::new(40, 3);
+        screen.feed("Warning: Straße CLOSED".as_bytes());
+
+        assert!(screen.contains_ignore_case("warning"));
+        assert!(screen.contains_ignore_case("STRAßE closed"));
+        assert!(!screen.contains_ignore_case("open"));
+        assert!(!screen.contains("closed"));
+    }
+
+    #[test]
+    fn test_scroll_region() {
+        let mut screen = ScreenState::with_scrollback(20, 5, 10);
+        assert_eq!(screen.scroll_region(), (0, 4));
+
+        // Header on row 0, status line on row 4, body scrolls in between
+        screen.feed(b"Header\x1b[5;1HStatus\x1b[2;4r");
+        assert_eq!(screen.scroll_region(), (1, 3));
+        assert_eq!(screen.cursor_position(), (0, 0));
+
+        screen.feed(b"\x1b[2;1Hline 1\r\nline 2\r\nline 3\r\nline 4\r\nline 5");
+        assert_eq!(screen.lines(), vec!["Header", "line 3", "line 4", "line 5", "Status"]);
+        assert!(screen.did_scroll_up());
+        // Rows scrolled out of a region below the top don't go to scrollback
+        assert!(screen.scrollback_contents().is_empty());
+
+        // Invalid regions are ignored; no parameters reset to the full screen
+        screen.feed(b"\x1b[4;2r");
+        assert_eq!(screen.scroll_region(), (1, 3));
+        screen.feed(b"\x1b[r");
+        assert_eq!(screen.scroll_region(), (0, 4));
+
+        screen.feed(b"\x1b[5;1H\r\n");
+        assert_eq!(screen.row_contents(0).trim_end(), "line 3");
+        assert_eq!(screen.scrollback_contents().trim_end(), "Header");
+
+        screen.feed(b"\x1b[1;3r");
+        screen.resize(20, 6);
+        assert_eq!(screen.scroll_region(), (0, 5));
+    }
+
+    #[test]
+    fn test_bracketed_paste_enabled() {
+        let mut screen = ScreenState::new(20, 5);
+        assert!(!screen.bracketed_paste_enabled());
+
+        screen.feed(b"\x1b[?2004h");
+        assert!(screen.bracketed_paste_enabled());
+
+        // Survives switching to the alternate screen and back
+        screen.feed(b"\x1b[?1049h\x1b[?1049l");
+        assert!(screen.bracketed_paste_enabled());
+
+        screen.feed(b"\x1b[?2004l");
+        assert!(!screen.bracketed_paste_enabled());
+    }
+
+    #[test]
+    fn test_mouse_tracking_mode() {
+        let mut screen = ScreenState::new(20, 5);
+        assert_eq!(screen.mouse_tracking_mode(), None);
+
+        // SGR encoding alone does not turn reporting on
+        screen.feed(b"\x1b[?1006h");
+        assert!(!screen.mouse_tracking_enabled());
+
+        screen.feed(b"\x1b[?1000;1006h");
+        assert_eq!(screen.mouse_tracking_mode(), Some(MouseTrackingMode::Normal));
+
+        screen.feed(b"\x1b[?1003h");
+        assert_eq!(screen.mouse_tracking_mode(), Some(MouseTrackingMode::AnyEvent));
+
+        // Disabling a mode that is not active leaves tracking on
+        screen.feed(b"\x1b[?1000l");
+        assert_eq!(screen.mouse_tracking_mode(), Some(MouseTrackingMode::AnyEvent));
+
+        screen.feed(b"\x1b[?1003l");
+        assert!(!screen.mouse_tracking_enabled());
+
+        screen.feed(b"\x1b[?9h");
+        assert_eq!(screen.mouse_tracking_mode(), Some(MouseTrackingMode::X10));
+    }
+
+    #[test]
+    fn test_cursor_visible() {
+        let mut screen = ScreenState::new(20, 5);
+        assert!(screen.cursor_visible());
+
+        screen.feed(b"\x1b[?25l\x1b[2;2HOpen menu");
+        assert!(!screen.cursor_visible());
+
+        // Other modes in the same sequence are still applied
+        screen.feed(b"\x1b[?2004;25h");
+        assert!(screen.cursor_visible());
+
+        // Without the private marker this is a different (ANSI) mode
+        screen.feed(b"\x1b[25l");
+        assert!(screen.cursor_visible());
+        assert!(screen.contains("Open menu"));
+    }
+
+    #[test]
+    fn test_wide_chars() {
+        let mut screen = ScreenState::new(10, 3);
+        screen.feed("日本語".as_bytes());
+
+        assert_eq!(screen.row_contents(0).trim_end(), "日本語");
+        assert_eq!(screen.cursor_position(), (0, 6));
+        assert_eq!(screen.get_cell(0, 2).map(|cell| cell.c), Some('本'));
+        assert_eq!(screen.get_cell(0, 3).map(|cell| cell.c), Some(' '));
+
+        // Text after a wide glyph lands in the column after its spacer
+        screen.feed("x".as_bytes());
+        assert_eq!(screen.get_cell(0, 6).map(|cell| cell.c), Some('x'));
+        assert!(screen.contains("日本語x"));
+        assert_eq!(screen.lines(), vec!["日本語x"]);
+
+        // A wide glyph that doesn't fit in the last column wraps
+        screen.feed("\r\n123456789漢".as_bytes());
+        assert_eq!(screen.row_contents(1), "123456789 ");
+        assert_eq!(screen.row_contents(2).trim_end(), "漢");
+    }
+
+    #[test]
+    fn test_find_after_wide_chars() {
+        let mut screen = ScreenState::new(20, 3);
+        screen.feed("日本 ok\r\n    表示\r\ne\u{301}!".as_bytes());
+
+        // Columns count cells, not characters
+        assert_eq!(screen.find("ok"), Some((0, 5)));
+        assert_eq!(screen.find_all("本"), vec![(0, 2)]);
+        let re = regex::Regex::new("o.").unwrap();
+        assert_eq!(screen.find_regex_with(&re), Some((0, 5)));
+
+        // Zero-width characters take no cell
+        assert_eq!(screen.find("!"), Some((2, 1)));
+        assert_eq!(screen.cursor_position(), (2, 2));
+
+        // "表示" is 4 columns wide: 4 blank columns left, 12 right
+        assert!(!screen.is_centered("表示", 0));
+        screen.feed("\x1b[2;1H\x1b[2K\x1b[2;9H表示".as_bytes());
+        assert!(screen.is_centered("表示", 0));
+    }
+
+    #[test]
+    fn test_line_wrapped_at() {
+        let mut screen = ScreenState::new(80, 6);
+        screen.feed(&[b'a'; 79]);
+        screen.feed(b"\r\n");
+        screen.feed(&[b'b'; 80]);
+        screen.feed(b"\r\n");
+        screen.feed(&[b'c'; 81]);
+        screen.feed(b"\r\n");
+        screen.feed(&[b'd'; 150]);
+
+        assert!(!screen.line_wrapped_at(0)); // just under the width
+        assert!(!screen.line_wrapped_at(1)); // exactly the width
+        assert!(screen.line_wrapped_at(2)); // just over the width
+        assert!(!screen.line_wrapped_at(3)); // continuation of row 2
+        assert!(screen.line_wrapped_at(4));
+        assert!(!screen.line_wrapped_at(5)); // the last row has nothing below
+        assert!(!screen.line_wrapped_at(100));
+    }
+
+    #[test]
+    fn test_count_matches() {
+        let mut screen = ScreenState::new(6, 4);
+        screen.feed("● a ●\r\n● b\r\naaaaaa".as_bytes());
+        // "aaaaaa" wraps onto the next row but is matched per row
+        screen.feed(b"aa");
+
+        assert_eq!(screen.count_matches("●"), 3);
+        assert_eq!(screen.count_matches("aa"), 4);
+        assert_eq!(screen.count_matches("aaa"), 2);
+        assert_eq!(screen.count_matches("zz"), 0);
+        assert_eq!(screen.count_matches(""), 0);
+    }
+
+    #[test]
+    fn test_wrap_points() {
+        let mut screen = ScreenState::new(80, 24);
+        screen.feed(b"header\r\n");
+        screen.feed(&[b'x'; 100]);
+        screen.feed(b"\r\n");
+        screen.feed(&[b'y'; 200]);
+
+        assert_eq!(screen.wrap_points(), vec![(1, 80), (3, 80), (4, 80)]);
+        assert_eq!(screen.row_contents(2).trim_end().len(), 20);
+
+        // An exactly full row is not a wrap
+        let mut screen = ScreenState::new(80, 24);
+        screen.feed(&[b'z'; 80]);
+        screen.feed(b"\r\nnext");
+        assert!(screen.wrap_points().is_empty());
+    }
+
+    #[test]
+    fn test_contains_and_find_regex() {
+        let mut screen = ScreenState::new(30, 4);
+        screen.feed("┃ 12:30:45 ┃\r\nprogress: 42%".as_bytes());
+
+        assert!(screen.contains_regex(r"\d{2}:\d{2}:\d{2}").unwrap());
+        assert_eq!(screen.find_regex(r"\d{2}:\d{2}").unwrap(), Some((0, 2)));
+        assert_eq!(screen.find_regex(r"\d+%").unwrap(), Some((1, 10)));
+        assert_eq!(screen.find_regex(r"^progress").unwrap(), Some((1, 0)));
+        assert!(!screen.contains_regex(r"error|fail").unwrap());
+
+        let percent = regex::Regex::new(r"\d+%").unwrap();
+        assert_eq!(screen.find_regex_with(&percent), Some((1, 10)));
+
+        match screen.contains_regex(r"(unclosed") {
+            Err(crate::TermTestError::Parse(msg)) => assert!(msg.contains("Invalid regex")),
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_region_contents() {
+        let mut screen = ScreenState::new(10, 3);
+        screen.feed(b"abcdefghij\r\nklmnopqrst\r\nuvwxyz");
+
+        assert_eq!(screen.region_contents(0, 2, 3, 2), vec!["cde", "mno"]);
+
+        // Clamped to the screen edges
+        assert_eq!(screen.region_contents(1, 8, 5, 5), vec!["st", "  "]);
+        assert_eq!(screen.region_contents(2, 0, u16::MAX, u16::MAX), vec!["uvwxyz    "]);
+
+        // Entirely outside or empty
+        assert!(screen.region_contents(3, 0, 5, 5).is_empty());
+        assert!(screen.region_contents(0, 10, 5, 5).is_empty());
+        assert!(screen.region_contents(0, 0, 0, 2).is_empty());
+
+        // Wide glyphs don't bring their spacer along
+        screen.feed("\x1b[3;1H\x1b[2K| 日本 |".as_bytes());
+        assert_eq!(screen.region_contents(2, 2, 4, 1), vec!["日本"]);
+        assert_eq!(screen.region_contents(2, 0, 8, 1), vec!["| 日本 |"]);
+    }
+
+    #[test]
+    fn test_scrollback() {
+        let mut screen = ScreenState::with_scrollback(10, 2, 2);
+        screen.feed(b"one\r\ntwo\r\nthree\r\nfour\r\nfive");
+
+        // Only the two most recent off-screen lines are kept
+        let scrollback = screen.scrollback_contents();
+        let lines: Vec<&str> = scrollback.lines().map(str::trim_end).collect();
+        assert_eq!(lines, vec!["two", "three"]);
+        assert_eq!(screen.lines(), vec!["four", "five"]);
+
+        // The alternate screen doesn't add to scrollback
+        screen.feed(b"\x1b[?1049h\r\n\r\n\r\nalt");
+        screen.feed(b"\x1b[?1049l");
+        assert_eq!(screen.scrollback_contents(), scrollback);
+
+        // ED 3 clears the scrollback but leaves the display alone
+        screen.feed(b"\x1b[3J");
+        assert_eq!(screen.scrollback_contents(), "");
+        assert_eq!(screen.lines(), vec!["four", "five"]);
+        assert_eq!(screen.erase_display_count(), 0);
+        assert_eq!(screen.full_redraw_count(), 1);
+
+        // Without scrollback, lines are discarded
+        let mut screen = ScreenState::new(10, 2);
+        screen.feed(b"one\r\ntwo\r\nthree");
+        assert_eq!(screen.scrollback_contents(), "");
+    }
+
+    #[test]
+    fn test_content_bounds() {
+        let mut screen = ScreenState::new(40, 10);
+        assert_eq!(screen.content_bounds(), None);
+
+        screen.feed(b"\x1b[3;10Hx\x1b[7;5Hlonger text\x1b[5;25H!");
+        let bounds = screen.content_bounds().unwrap();
+        assert_eq!(bounds, Rect::new(4, 2, 21, 5));
+        for (row, col) in [(2, 9), (6, 4), (6, 14), (4, 24)] {
+            assert!(bounds.contains(col, row));
+        }
+
+        // Colored blanks don't count as content
+        screen.feed(b"\x1b[44m\x1b[10;1H   \x1b[0m");
+        assert_eq!(screen.content_bounds(), Some(bounds));
+    }
+
+    #[test]
+    fn test_content_bounds_centered_dialog() {
+        let mut screen = ScreenState::new(80, 24);
+        screen.feed(b"\x1b[11;31H+------------------+");
+        screen.feed(b"\x1b[12;31H|    Save file?    |");
+        screen.feed(b"\x1b[13;31H|   [Yes]  [No]    |");
+        screen.feed(b"\x1b[14;31H+------------------+");
+
+        let bounds = screen.content_bounds().unwrap();
+        assert_eq!(bounds, Rect::new(30, 10, 20, 4));
+
+        // Centered: equal margins on both sides and above and below
+        assert_eq!(bounds.x, 80 - bounds.right());
+        assert_eq!(bounds.y, 24 - bounds.bottom());
+    }
+
+    #[test]
+    fn test_diff() {
+        let mut before = ScreenState::new(10, 3);
+        before.feed(b"> item 1\r\n  item 2");
+        let mut after = ScreenState::new(10, 3);
+        after.feed(b"  item 1\r\n> item 2");
+
+        assert_eq!(
+            before.diff(&after),
+            vec![
+                CellDiff {
+                    row: 0,
+                    col: 0,
+                    before: Some('>'),
+                    after: Some(' ')
+                },
+                CellDiff {
+                    row: 1,
+                    col: 0,
+                    before: Some(' '),
+                    after: Some('>')
+                },
+            ]
+        );
+        assert!(before.diff(&before).is_empty());
+
+        // Attribute-only changes are ignored
+        let mut styled = ScreenState::new(10, 3);
+        styled.feed(b"\x1b[1m> item 1\x1b[0m\r\n  item 2");
+        assert!(before.diff(&styled).is_empty());
+
+        // Different sizes compare the union, ignoring blank cells
+        let mut wide = ScreenState::new(12, 3);
+        wide.feed(b"> item 1\r\n  item 2  ok");
+        assert_eq!(
+            before.diff(&wide),
+            vec![
+                CellDiff {
+                    row: 1,
+                    col: 10,
+                    before: None,
+                    after: Some('o')
+                },
+                CellDiff {
+                    row: 1,
+                    col: 11,
+                    before: None,
+                    after: Some('k')
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hyperlinks() {
+        let mut screen = ScreenState::new(40, 5);
+        screen.feed(b"\x1b]8;;file:///tmp/a.txt\x07a.txt\x1b]8;;\x07 plain ");
+        screen.feed(b"\x1b[3;5H\x1b]8;id=x;https://e.com/?q=1;2\x1b\\query\x1b]8;;\x1b\\");
+
+        assert_eq!(
+            screen.hyperlinks(),
+            vec![
+                Hyperlink {
+                    uri: "file:///tmp/a.txt".to_string(),
+                    row: 0,
+                    col: 0,
+                    text: "a.txt".to_string(),
+                },
+                Hyperlink {
+                    uri: "https://e.com/?q=1;2".to_string(),
+                    row: 2,
+                    col: 4,
+                    text: "query".to_string(),
+                },
+            ]
+        );
+        assert!(screen.contains("a.txt plain"));
+
+        // A lone terminator produces nothing
+        let mut screen = ScreenState::new(40, 5);
+        screen.feed(b"\x1b]8;;\x07text");
+        assert!(screen.hyperlinks().is_empty());
+    }
+
+    #[test]
+    fn test_window_title() {
+        let mut screen = ScreenState::new(40, 5);
+        assert_eq!(screen.window_title(), None);
+
+        screen.feed(b"\x1b]0;first\x07hello");
+        assert_eq!(screen.window_title().as_deref(), Some("first"));
+
+        // OSC 2 with an ST terminator; the most recent title wins
+        screen.feed(b"\x1b]2;a; b\x1b\\");
+        assert_eq!(screen.window_title().as_deref(), Some("a; b"));
+
+        // Other OSC sequences leave the title alone
+        screen.feed(b"\x1b]8;;https://e.com\x07x\x1b]8;;\x07");
+        assert_eq!(screen.window_title().as_deref(), Some("a; b"));
+        assert!(screen.contains("hellox"));
+    }
+
+    #[test]
+    fn test_cell_bytes() {
+        let mut screen = ScreenState::new(10, 2);
+        screen.feed("a€".as_bytes());
+
+        assert_eq!(screen.cell_bytes(0, 0), Some(b"a".to_vec()));
+        assert_eq!(screen.cell_bytes(0, 1), Some(vec![0xE2, 0x82, 0xAC]));
+        assert_eq!(screen.cell_bytes(0, 2), Some(b" ".to_vec()));
+        assert_eq!(screen.cell_bytes(2, 0), None);
+    }
+
+    #[test]
+    fn test_rgb_at_uses_custom_palette() {
+        let mut palette = [(0, 0, 0); 256];
+        palette[4] = (38, 139, 210);
+        palette[208] = (1, 2, 3);
+
+        let mut screen = ScreenState::with_palette(20, 3, palette);
+        screen.feed(b"\x1b[34mB\x1b[38;5;208mO\x1b[0mN");
+
+        assert_eq!(screen.rgb_at(0, 0), Some((38, 139, 210)));
+        assert_eq!(screen.rgb_at(0, 1), Some((1, 2, 3)));
+        assert_eq!(screen.rgb_at(0, 2), None);
+        assert_eq!(screen.rgb_at(5, 0), None);
+
+        let mut xterm = ScreenState::new(20, 3);
+        xterm.feed(b"\x1b[34mB\x1b[38;5;208mO");
+        assert_eq!(xterm.rgb_at(0, 0), Some((0, 0, 238)));
+        assert_eq!(xterm.rgb_at(0, 1), Some((255, 135, 0)));
+    }
+
+    #[test]
+    fn test_did_scroll_up() {
+        let mut screen = ScreenState::new(20, 3);
+        assert!(!screen.did_scroll_up());
+
+        screen.feed(b"line 1\r\nline 2\r\nline 3");
+        assert!(!screen.did_scroll_up());
+
+        screen.feed(b"\r\nline 4\r\nline 5");
+        assert!(screen.did_scroll_up());
+        assert_eq!(screen.lines(), vec!["line 3", "line 4", "line 5"]);
+        assert_eq!(screen.cursor_position(), (2, 6));
+
+        // The flag holds across feeds until cleared
+        screen.feed(b"!");
+        assert!(screen.did_scroll_up());
+        screen.clear_scrolled();
+        screen.feed(b"!");
+        assert!(!screen.did_scroll_up());
+    }
+
+    #[test]
+    fn test_numbers() {
+        let mut screen = ScreenState::new(40, 4);
+        screen.feed(b"CPU 45% MEM 78%\r\nTemp -3.5C  up 10-20\r\n\r\nv2 build 007");
+
+        assert_eq!(screen.numbers_in_row(0), vec![45.0, 78.0]);
+        assert_eq!(screen.numbers_in_row(1), vec![-3.5, 10.0, 20.0]);
+        assert!(screen.numbers_in_row(2).is_empty());
+        assert!(screen.numbers_in_row(10).is_empty());
+        assert_eq!(screen.numbers(), vec![45.0, 78.0, -3.5, 10.0, 20.0, 2.0, 7.0]);
+    }
+
+    #[test]
+    fn test_image_markers() {
+        let mut screen = ScreenState::new(80, 24);
+        assert!(screen.image_markers().is_empty());
+
+        screen.feed(b"\x1b[5;10H\x1b]1337;File=inline=1;width=10;height=5:SGVsbG8=\x07");
+        assert_eq!(
+            screen.image_markers(),
+            &[ImageMarker {
+                protocol: ImageProtocol::ITerm2,
+                row: 4,
+                col: 9
+            }]
+        );
+        assert_eq!(screen.iterm2_regions().len(), 1);
+
+        screen.feed(b"\x1b[1;1H\x1b_Ga=T,f=100;AAAA\x1b\\");
+        screen.feed(b"\x1b[2;2H\x1bPq\"1;1;8;6#0~\x1b\\");
+        let protocols: Vec<_> = screen.image_markers().iter().map(|m| m.protocol).collect();
+        assert_eq!(
+            protocols,
+            vec![
+                ImageProtocol::ITerm2,
+                ImageProtocol::Kitty,
+                ImageProtocol::Sixel
+            ]
+        );
+        assert_eq!((screen.image_markers()[1].row, screen.image_markers()[1].col), (0, 0));
+        assert_eq!((screen.image_markers()[2].row, screen.image_markers()[2].col), (1, 1));
+    }
+
+    #[test]
+    fn test_kitty_images() {
+        let mut screen = ScreenState::new(80, 24);
+        screen.feed(b"\x1b[4;6H\x1b_Ga=T,f=100,s=10,v=20,i=3;AAAA\x1b\\");
+        screen.feed(b"\x1b_Ga=q,i=31;AAAA\x1b\\");
+        screen.feed(b"\x1b[1;1H\x1b_Gf=24,m=1;AAAA\x1b\\");
+
+        let images = screen.kitty_images();
+        assert_eq!(images.len(), 2);
+
+        assert_eq!((images[0].row, images[0].col), (3, 5));
+        assert_eq!(images[0].param("a"), Some("T"));
+        assert_eq!(images[0].param("f"), Some("100"));
+        assert_eq!(images[0].param("s"), Some("10"));
+        assert_eq!(images[0].param("v"), Some("20"));
+        assert_eq!(images[0].param("i"), Some("3"));
+        assert_eq!(images[0].params.len(), 5);
+
+        // No action key defaults to transmit
+        assert_eq!((images[1].row, images[1].col), (0, 0));
+        assert_eq!(images[1].param("a"), None);
+        assert_eq!(images[1].param("m"), Some("1"));
+    }
+
+    #[test]
+    fn test_is_centered() {
+        let mut screen = ScreenState::new(20, 4);
+        // "Title" spans columns 7-11: 7 columns on the left, 8 on the right
+        screen.feed(b"\x1b[1;8HTitle");
+        screen.feed(b"\x1b[2;5H[ OK ]");
+        screen.feed(b"\x1b[3;8H[Cancel]");
+
+        assert!(!screen.is_centered("Title", 0));
+        assert!(screen.is_centered("Title", 1));
+        assert!(!screen.is_centered("[ OK ]", 1));
+        assert!(screen.is_centered("[ OK ]", 6));
+        assert!(screen.is_centered("[Cancel]", 2));
+        assert!(!screen.is_centered("missing", 20));
+    }
+
+    #[test]
+    fn test_current_pen() {
+        let mut screen = ScreenState::new(80, 24);
+        assert_eq!(screen.current_pen(), CellStyle::default());
+
+        screen.feed(b"\x1b[1;31m");
+        let pen = screen.current_pen();
+        assert!(pen.bold);
+        assert_eq!(pen.fg, Some(1));
+        assert_eq!(pen.bg, None);
+        assert!(!pen.italic && !pen.underline);
+        // Nothing was printed with it
+        assert!(!screen.is_cell_written(0, 0));
+
+        screen.feed(b"X\x1b[44m");
+        assert_eq!(screen.get_cell(0, 0).unwrap().style(), pen);
+        assert_eq!(screen.current_pen().bg, Some(4));
+
+        screen.feed(b"\x1b[0m");
+        assert_eq!(screen.current_pen(), CellStyle::default());
+    }
+
+    #[test]
+    fn test_cursor_shape_history() {
+        let mut screen = ScreenState::new(80, 24);
+        assert!(screen.cursor_shape_history().is_empty());
+
+        screen.feed(b"\x1b[5 qinsert\x1b[ q");
+        // Not DECSCUSR: no space intermediate, or an out-of-range shape
+        screen.feed(b"\x1b[2q\x1b[9 q");
+
+        assert_eq!(
+            screen.cursor_shape_history(),
+            vec![CursorShape::BlinkingBar, CursorShape::Default]
+        );
+        assert!(screen.contains("insert"));
+    }
+
+    #[test]
+    fn test_assert_region_blank() {
+        let mut screen = ScreenState::new(20, 5);
+        screen.feed(b"Menu\x1b[2;6HPanel\x1b[3;6Htext");
+        let panel = Rect::new(5, 1, 10, 3);
+
+        match screen.assert_region_blank(panel) {
+            Err(crate::TermTestError::Parse(msg)) => {
+                assert!(msg.contains("9 non-blank cell(s)"));
+                assert!(msg.contains("(1, 5) 'P'"));
+                assert!(msg.contains("(2, 8) 't'"));
+            }
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+
+        // Clear the panel by overwriting it with spaces
+        screen.feed(b"\x1b[2;6H     \x1b[3;6H    ");
+        assert!(screen.assert_region_blank(panel).is_ok());
+        // Content outside the region doesn't matter, nor does off-screen area
+        assert!(screen
+            .assert_region_blank(Rect::new(5, 0, 100, 100))
+            .is_ok());
+        assert!(screen.assert_region_blank(Rect::new(0, 0, 4, 1)).is_err());
+    }
+
+    #[test]
+    fn test_erase_display_and_line() {
+        let mut screen = ScreenState::new(10, 3);
+        screen.feed(b"aaaaaaaaaa\r\nbbbbbbbbbb\r\ncccccccccc");
+
+        // EL: to end, to cursor, whole line
+        screen.feed(b"\x1b[1;4H\x1b[K\x1b[2;4H\x1b[1K\x1b[3;1H\x1b[2K");
+        assert_eq!(screen.lines(), vec!["aaa", "    bbbbbb"]);
+        assert!(!screen.is_cell_written(0, 5));
+        assert_eq!(screen.erase_display_count(), 0);
+
+        // ED from the middle doesn't count as a full clear
+        screen.feed(b"\x1b[1;2H\x1b[J");
+        assert_eq!(screen.lines(), vec!["a"]);
+        assert_eq!(screen.erase_display_count(), 0);
+
+        screen.feed(b"\x1b[44m\x1b[2J");
+        assert_eq!(screen.content_height(), 0);
+        assert_eq!(screen.get_cell(2, 9).unwrap().bg, Some(4));
+        screen.feed(b"\x1b[H\x1b[J");
+        assert_eq!(screen.erase_display_count(), 2);
+    }
+
+    #[test]
+    fn test_rows_equal() {
+        let mut screen = ScreenState::new(20, 5);
+        screen.feed(b"Name  Size\r\n----  ----\r\n----  ----  \r\n----  ---");
+
+        assert!(screen.rows_equal(1, 2));
+        assert!(screen.rows_equal(1, 1));
+        assert!(!screen.rows_equal(0, 1));
+        assert!(!screen.rows_equal(1, 3));
+        // Blank and out-of-bounds rows are both empty
+        assert!(screen.rows_equal(4, 99));
+    }
+
+    #[test]
+    fn test_max_line_width() {
+        let mut screen = ScreenState::new(80, 24);
+        assert_eq!(screen.max_line_width(), 0);
+
+        screen.feed(b"abc\r\n");
+        screen.feed(b"abcdefghij   \r\n");
+        screen.feed(b"\x1b[5;1Habcdef");
+        assert_eq!(screen.max_line_width(), 10);
+
+        // Leading spaces count, trailing spaces do not
+        screen.feed(b"\x1b[10;20Hxy");
+        assert_eq!(screen.max_line_width(), 21);
     }
 
-    /// Returns a mutable reference to the Sixel regions.
-    ///
-    /// This is primarily intended for testing purposes, allowing tests to
-    /// inject mock Sixel data for memory profiling and other tests.
-    ///
-    /// # Returns
-    ///
-    /// A mutable reference to the vector of Sixel regions.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use ratatui_testlib::{screen::SixelRegion, ScreenState};
-    ///
-    /// let mut screen = ScreenState::new(80, 24);
-    ///
-    /// // Add a mock Sixel region for testing
-    /// screen.sixel_regions_mut().push(SixelRegion {
-    ///     start_row: 5,
-    ///     start_col: 10,
-    ///     width: 100,
-    ///     height: 50,
-    ///     data: vec![0u8; 1000],
-    /// });
-    ///
-    /// assert_eq!(screen.sixel_regions().len(), 1);
-    /// ```
-    pub fn sixel_regions_mut(&mut self) -> &mut Vec<SixelRegion> {
-        &mut self.state.sixel_regions
+    #[cfg(feature = "snapshot-json")]
+    #[test]
+    fn test_grid_snapshot_json_round_trip() {
+        let mut screen = ScreenState::new(20, 5);
+        screen.feed(b"plain \x1b[1;4;31mstyled\x1b[0m\r\n\x1b[44;93mcolors\x1b[0m");
+
+        let snapshot = screen.snapshot();
+        let json = snapshot.to_json().unwrap();
+        let restored = GridSnapshot::from_json(&json).unwrap();
+
+        assert_eq!(restored, snapshot);
+        assert!(restored.cells[0][6].bold);
+        assert_eq!(restored.cells[1][0].bg, Some(4));
+
+        assert!(GridSnapshot::from_json("{not json").is_err());
     }
 
-    /// Checks if a Sixel region exists at the given position.
-    ///
-    /// This method checks if any Sixel region has its starting position
-    /// at the exact (row, col) coordinates provided.
-    ///
-    /// # Arguments
-    ///
-    /// * `row` - Row to check (0-indexed)
-    /// * `col` - Column to check (0-indexed)
-    ///
-    /// # Returns
-    ///
-    /// `true` if a Sixel region starts at the given position, `false` otherwise.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use ratatui_testlib::ScreenState;
-    ///
-    /// let mut screen = ScreenState::new(80, 24);
-    ///
-    /// // Render a Sixel at position (5, 10) - ESC[5;10H moves to row 5, col 10 (1-based)
-    /// screen.feed(b"\x1b[5;10H"); // Move cursor
-    /// screen.feed(b"\x1bPq"); // Start Sixel
-    /// screen.feed(b"\"1;1;100;50#0~"); // Raster + data
-    /// screen.feed(b"\x1b\\"); // End Sixel
-    ///
-    /// // Check for Sixel at 0-based coordinates (4, 9)
-    /// assert!(screen.has_sixel_at(4, 9));
-    /// assert!(!screen.has_sixel_at(0, 0));
-    /// ```
-    pub fn has_sixel_at(&self, row: u16, col: u16) -> bool {
-        self.state
-            .sixel_regions
-            .iter()
-            .any(|region| region.start_row == row && region.start_col == col)
+    #[test]
+    fn test_lines_without_trailing_newline() {
+        let mut screen = ScreenState::new(20, 5);
+        assert!(screen.lines().is_empty());
+        assert_eq!(screen.content_height(), 0);
+
+        screen.feed(b"a\r\nb");
+        assert_eq!(screen.lines(), vec!["a", "b"]);
+        assert_eq!(screen.content_height(), 2);
+
+        // A trailing newline does not add a line
+        screen.feed(b"\r\n");
+        assert_eq!(screen.content_height(), 2);
     }
 
-    /// Returns all Kitty graphics regions currently on screen.
-    ///
-    /// This method provides access to all Kitty graphics that have been rendered
-    /// via APC (Application Program Command) sequences. Each region includes position
-    /// and dimension information.
-    ///
-    /// # Returns
-    ///
-    /// A slice of [`KittyRegion`] containing all detected Kitty graphics.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use ratatui_testlib::ScreenState;
-    ///
-    /// let mut screen = ScreenState::new(80, 24);
-    /// // ... render some Kitty graphics ...
-    ///
-    /// let regions = screen.kitty_regions();
-    /// for (i, region) in regions.iter().enumerate() {
-    ///     println!(
-    ///         "Kitty region {}: position ({}, {}), size {}x{}",
-    ///         i, region.start_row, region.start_col, region.width, region.height
-    ///     );
-    /// }
-    /// ```
-    pub fn kitty_regions(&self) -> &[KittyRegion] {
-        &self.state.kitty_regions
+    #[test]
+    fn test_terminal_queries() {
+        let mut screen = ScreenState::new(20, 5);
+        screen.feed(b"\x1b[c\x1b[3;4H\x1b[6n\x1b[>c\x1b[>0c\x1bP$qm\x1b\\\x1bP$q q\x1b\\");
+
+        assert_eq!(
+            screen.take_queries(),
+            vec![
+                TerminalQuery::PrimaryAttributes,
+                TerminalQuery::CursorPosition(2, 3),
+                TerminalQuery::SecondaryAttributes,
+                TerminalQuery::SecondaryAttributes,
+                TerminalQuery::Setting("m".to_string()),
+                TerminalQuery::Setting(" q".to_string()),
+            ]
+        );
+        assert!(screen.take_queries().is_empty());
+
+        // DECRQSS is not mistaken for a Sixel image
+        assert!(screen.sixel_regions().is_empty());
+        assert_eq!(screen.cursor_position(), (2, 3));
     }
 
-    /// Returns a mutable reference to the Kitty regions.
-    ///
-    /// This is primarily intended for testing purposes.
-    ///
-    /// # Returns
-    ///
-    /// A mutable reference to the vector of Kitty regions.
-    pub fn kitty_regions_mut(&mut self) -> &mut Vec<KittyRegion> {
-        &mut self.state.kitty_regions
+    #[test]
+    fn test_feed_str_and_from_str() {
+        let mut screen = ScreenState::from_str(10, 3, "┌─┐\r\n│é│");
+        assert_eq!(screen.lines(), vec!["┌─┐", "│é│"]);
+        assert_eq!(screen.cursor_position(), (1, 3));
+
+        screen.feed_str("\r\n\x1b[31m└─┘");
+        assert_eq!(screen.row_contents(2).trim_end(), "└─┘");
+        assert_eq!(screen.fg_color_at(2, 0), Some(Color::Indexed(1)));
     }
 
-    /// Returns all iTerm2 inline image regions currently on screen.
-    ///
-    /// This method provides access to all iTerm2 inline images that have been rendered
-    /// via OSC 1337 sequences. Each region includes position and dimension information.
-    ///
-    /// # Returns
-    ///
-    /// A slice of [`ITerm2Region`] containing all detected iTerm2 inline images.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use ratatui_testlib::ScreenState;
-    ///
-    /// let mut screen = ScreenState::new(80, 24);
-    /// // ... render some iTerm2 inline images ...
-    ///
-    /// let regions = screen.iterm2_regions();
-    /// for (i, region) in regions.iter().enumerate() {
-    ///     println!(
-    ///         "iTerm2 region {}: position ({}, {}), size {}x{}",
-    ///         i, region.start_row, region.start_col, region.width, region.height
-    ///     );
-    /// }
-    /// ```
-    pub fn iterm2_regions(&self) -> &[ITerm2Region] {
-        &self.state.iterm2_regions
+    #[test]
+    fn test_resize() {
+        let mut screen = ScreenState::with_scrollback(10, 4, 10);
+        screen.feed(b"one\r\ntwo\r\nthree\r\nfour");
+
+        screen.resize(20, 6);
+        assert_eq!(screen.size(), (20, 6));
+        assert_eq!(screen.contents_trimmed(), "one\ntwo\nthree\nfour");
+        assert_eq!(screen.cursor_position(), (3, 4));
+        screen.feed(b"\x1b[1;11Hwide\x1b[4;5H");
+        assert_eq!(screen.row_contents(0).trim_end(), "one       wide");
+
+        // Shrinking keeps the cursor row, moving the top rows to scrollback
+        screen.resize(3, 2);
+        assert_eq!(screen.size(), (3, 2));
+        assert_eq!(screen.contents_trimmed(), "thr\nfou");
+        assert_eq!(screen.cursor_position(), (1, 2));
+        assert_eq!(
+            screen.scrollback_contents(),
+            format!("one       wide{}\ntwo{}", " ".repeat(6), " ".repeat(17))
+        );
+
+        screen.feed(b"\r\nX");
+        assert_eq!(screen.contents_trimmed(), "fou\nX");
+
+        screen.resize(0, 5);
+        assert_eq!(screen.size(), (3, 2));
     }
 
-    /// Returns a mutable reference to the iTerm2 regions.
-    ///
-    /// This is primarily intended for testing purposes.
-    ///
-    /// # Returns
-    ///
-    /// A mutable reference to the vector of iTerm2 regions.
-    pub fn iterm2_regions_mut(&mut self) -> &mut Vec<ITerm2Region> {
-        &mut self.state.iterm2_regions
+    #[test]
+    fn test_reset() {
+        let mut palette = default_palette();
+        palette[1] = (1, 2, 3);
+        let mut screen = ScreenState::with_palette(20, 3, palette);
+        screen.state.scrollback_limit = 10;
+        screen.feed(b"one\r\ntwo\r\nthree\r\n\x1b[?2004h\x1b[31mred\x1b[");
+
+        screen.reset();
+        assert_eq!(screen.contents_trimmed(), "");
+        assert_eq!(screen.cursor_position(), (0, 0));
+        assert_eq!(screen.size(), (20, 3));
+        assert!(!screen.bracketed_paste_enabled());
+        assert!(screen.scrollback_contents().is_empty());
+
+        // Palette and scrollback capacity remain
+        screen.feed(b"A\r\nB\r\nC\r\nD");
+        assert_eq!(screen.contents_trimmed(), "B\nC\nD");
+        assert_eq!(screen.scrollback_contents().trim_end(), "A");
+        screen.feed(b"\x1b[1;1H\x1b[31mX");
+        assert_eq!(screen.rgb_at(0, 0), Some((1, 2, 3)));
     }
 
-    /// Returns the screen contents for debugging purposes.
-    ///
-    /// This is currently an alias for [`contents()`](Self::contents), but may
-    /// include additional debug information in the future.
-    ///
-    /// # Returns
-    ///
-    /// A string containing the screen contents.
-    pub fn debug_contents(&self) -> String {
-        self.contents()
+    #[test]
+    fn test_dirty_rows() {
+        let mut screen = ScreenState::new(20, 4);
+        assert!(screen.dirty_rows().is_empty());
+
+        screen.feed(b"one\r\ntwo\r\nthree");
+        assert_eq!(screen.dirty_rows(), vec![0, 1, 2]);
+
+        // Marks accumulate over several feeds until cleared
+        screen.feed(b"\x1b[4;1H!");
+        assert_eq!(screen.dirty_rows(), vec![0, 1, 2, 3]);
+        screen.clear_dirty_rows();
+        assert!(screen.dirty_rows().is_empty());
+
+        // Moving the cursor changes nothing, redrawing identical content does
+        screen.feed(b"\x1b[2;1H\x1b[4;1H");
+        assert!(screen.dirty_rows().is_empty());
+        screen.feed(b"\x1b[2;1Htwo");
+        assert_eq!(screen.dirty_rows(), vec![1]);
+
+        screen.clear_dirty_rows();
+        screen.feed(b"\x1b[3;1H\x1b[K");
+        assert_eq!(screen.dirty_rows(), vec![2]);
+
+        // Scrolling marks the whole scrolling region
+        screen.clear_dirty_rows();
+        screen.feed(b"\x1b[2;3r\x1b[3;1H\n");
+        assert_eq!(screen.dirty_rows(), vec![1, 2]);
+
+        screen.clear_dirty_rows();
+        screen.feed(b"\x1b[?1049h");
+        assert_eq!(screen.dirty_rows(), vec![0, 1, 2, 3]);
     }
 
-    /// Checks if the screen contains the specified text.
-    ///
-    /// This is a convenience method that searches the entire screen contents
-    /// for the given substring. It's useful for simple text-based assertions
-    /// in tests.
-    ///
-    /// # Arguments
-    ///
-    /// * `text` - Text to search for
-    ///
-    /// # Returns
-    ///
-    /// `true` if the text appears anywhere on the screen, `false` otherwise.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use ratatui_testlib::ScreenState;
-    ///
-    /// let mut screen = ScreenState::new(80, 24);
-    /// screen.feed(b"Welcome to the application");
-    ///
-    /// assert!(screen.contains("Welcome"));
-    /// assert!(screen.contains("application"));
-    /// assert!(!screen.contains("goodbye"));
-    /// ```
-    pub fn contains(&self, text: &str) -> bool {
-        self.contents().contains(text)
+    #[test]
+    fn test_overdraw_count() {
+        let mut screen = ScreenState::new(20, 3);
+        screen.feed(b"abc");
+        assert_eq!(screen.overdraw_count(), 0);
+
+        // Overwrite the middle cell twice more; it still counts once
+        screen.feed(b"\x1b[1;2HX\x1b[1;2HY");
+        assert_eq!(screen.overdraw_count(), 1);
+        assert_eq!(screen.row_contents(0).trim_end(), "aYc");
+
+        screen.mark_frame();
+        assert_eq!(screen.overdraw_count(), 0);
+
+        // Counts follow the content when the screen scrolls
+        screen.feed(b"\x1b[3;1Hzz\x1b[3;1Hzz\r\n");
+        assert_eq!(screen.overdraw_count(), 2);
+        assert_eq!(screen.state.frame_writes[1][..2], [2, 2]);
+
+        // Each screen buffer keeps its own counts
+        screen.feed(b"\x1b[?1049h");
+        assert_eq!(screen.overdraw_count(), 0);
+        screen.feed(b"\x1b[Hx\x1b[Hx");
+        assert_eq!(screen.overdraw_count(), 1);
+        screen.feed(b"\x1b[?1049l");
+        assert_eq!(screen.overdraw_count(), 2);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_is_wrapped() {
+        let mut screen = ScreenState::new(10, 5);
+        screen.feed(b"0123456789abcdefghij!\r\nhard\r\n");
+        assert!(screen.is_wrapped(0));
+        assert!(screen.is_wrapped(1));
+        assert!(!screen.is_wrapped(2));
+        assert!(!screen.is_wrapped(3));
+        assert!(!screen.is_wrapped(4));
+        assert!(!screen.is_wrapped(99));
+
+        // With autowrap disabled, long text is truncated instead
+        screen.feed(b"\x1b[?7l\x1b[4;1H0123456789truncated");
+        assert!(!screen.is_wrapped(3));
+        assert_eq!(screen.row_contents(3), "012345678d");
+    }
 
     #[test]
-    fn test_create_screen() {
-        let screen = ScreenState::new(80, 24);
-        assert_eq!(screen.size(), (80, 24));
+    fn test_char_grid() {
+        let mut screen = ScreenState::new(5, 4);
+        screen.feed("x\x1b[2;2Hx\x1b[3;3Hx\x1b[4;1H日y".as_bytes());
+
+        let grid = screen.char_grid();
+        assert_eq!(grid.len(), 4);
+        assert!(grid.iter().all(|row| row.len() == 5));
+        assert_eq!([grid[0][0], grid[1][1], grid[2][2]], ['x'; 3]);
+        assert_eq!(grid[0][1], ' ');
+        assert_eq!(grid[3][..3], ['日', ' ', 'y']);
     }
 
     #[test]
-    fn test_feed_simple_text() {
-        let mut screen = ScreenState::new(80, 24);
-        screen.feed(b"Hello, World!");
-        assert!(screen.contents().contains("Hello, World!"));
+    fn test_prompt_matches() {
+        let mut screen = ScreenState::new(40, 6);
+        screen.feed(b"user@host:~$ ls\r\nfile.txt\r\n");
+        assert!(!screen.prompt_matches(r"\$\s*$").unwrap());
+
+        screen.feed(b"user@host:~$ ");
+        assert!(screen.prompt_matches(r"\$\s*$").unwrap());
+        assert!(screen.prompt_matches(r"^user@host").unwrap());
+
+        // Only the cursor's row is checked
+        screen.feed(b"\x1b[1;1H");
+        assert!(!screen.prompt_matches(r"\$\s*$").unwrap());
+        let prompt = regex::Regex::new(r"^user@host").unwrap();
+        assert!(screen.prompt_matches_with(&prompt));
+
+        assert!(screen.prompt_matches("(unclosed").is_err());
     }
 
     #[test]
-    fn test_cursor_position() {
-        let mut screen = ScreenState::new(80, 24);
+    fn test_content_hash() {
+        let mut a = ScreenState::new(10, 3);
+        let mut b = ScreenState::new(10, 3);
+        assert_eq!(a.content_hash(), b.content_hash());
 
-        // Initial position
-        assert_eq!(screen.cursor_position(), (0, 0));
+        a.feed(b"ab\r\ncd");
+        b.feed(b"\x1b[2;1H\x1b[44mcd\x1b[1;1H\x1b[1mab");
+        assert_eq!(a.content_hash(), b.content_hash());
 
-        // Move cursor using CSI sequence (ESC [ 5 ; 10 H = row 5, col 10)
-        screen.feed(b"\x1b[5;10H");
-        let (row, col) = screen.cursor_position();
+        // Same text in a different place
+        b.feed(b"\x1b[2J\x1b[1;2Hab\r\n cd");
+        assert_ne!(a.content_hash(), b.content_hash());
 
-        // CSI uses 1-based, we convert to 0-based
-        assert_eq!(row, 4); // 5-1 = 4
-        assert_eq!(col, 9); // 10-1 = 9
+        // Same blank screen at a different size
+        assert_ne!(ScreenState::new(10, 3).content_hash(), ScreenState::new(3, 10).content_hash());
     }
 
     #[test]
-    fn test_text_at() {
+    fn test_trimmed_contents() {
+        let mut screen = ScreenState::new(10, 5);
+        assert_eq!(screen.trimmed_contents(), "");
+
+        screen.feed(b"ab   \r\n\r\n  c  \r\n\r\n");
+        assert_eq!(screen.trimmed_contents(), "ab\n\n  c");
+        assert_eq!(screen.contents().lines().count(), 5);
+    }
+
+    #[test]
+    fn test_is_cell_written() {
+        let mut screen = ScreenState::new(20, 5);
+        screen.feed(b"x   y");
+        screen.feed(b"\x1b[3;5H  ");
+
+        for col in 0..5 {
+            assert!(screen.is_cell_written(0, col), "cell (0, {}) should be written", col);
+        }
+        assert!(!screen.is_cell_written(0, 5));
+        assert!(screen.is_cell_written(2, 4));
+        assert!(screen.is_cell_written(2, 5));
+        assert!(!screen.is_cell_written(2, 3));
+        assert!(!screen.is_cell_written(2, 6));
+        assert!(!screen.is_cell_written(1, 0));
+        assert!(!screen.is_cell_written(50, 50));
+
+        // Both written and unwritten cells look like blanks
+        assert_eq!(screen.text_at(0, 1), screen.text_at(0, 5));
+    }
+
+    #[test]
+    fn test_row_has_trailing_whitespace() {
+        let mut screen = ScreenState::new(40, 5);
+        screen.feed(b"no trailing\r\n");
+        screen.feed(b"two spaces  \r\n");
+        screen.feed(b"\r\n");
+        screen.feed(b"   indented");
+
+        assert!(!screen.row_has_trailing_whitespace(0));
+        assert!(screen.row_has_trailing_whitespace(1));
+        assert!(!screen.row_has_trailing_whitespace(2));
+        assert!(!screen.row_has_trailing_whitespace(3));
+        assert!(!screen.row_has_trailing_whitespace(99));
+
+        // Overwriting the trailing spaces with text clears the condition
+        screen.feed(b"\x1b[2;11Hxx");
+        assert!(!screen.row_has_trailing_whitespace(1));
+    }
+
+    #[test]
+    fn test_cursor_move_counters() {
         let mut screen = ScreenState::new(80, 24);
-        screen.feed(b"Test");
+        assert_eq!(screen.absolute_moves(), 0);
+        assert_eq!(screen.relative_moves(), 0);
 
-        assert_eq!(screen.text_at(0, 0), Some('T'));
-        assert_eq!(screen.text_at(0, 1), Some('e'));
-        assert_eq!(screen.text_at(0, 2), Some('s'));
-        assert_eq!(screen.text_at(0, 3), Some('t'));
-        assert_eq!(screen.text_at(0, 4), Some(' '));
-        assert_eq!(screen.text_at(100, 100), None);
+        screen.feed(b"\x1b[H\x1b[3;4Htext\x1b[10;1f");
+        screen.feed(b"\x1b[A\x1b[2B\x1b[5C\x1b[D");
+
+        // Plain text and line feeds are not cursor movement sequences
+        screen.feed(b"more\r\n");
+
+        assert_eq!(screen.absolute_moves(), 3);
+        assert_eq!(screen.relative_moves(), 4);
+    }
+
+    #[test]
+    fn test_alt_screen_preserves_main() {
+        let mut screen = ScreenState::new(20, 5);
+        screen.feed(b"main line\r\nprompt $ ");
+        let main_before = screen.contents();
+        let cursor_before = screen.cursor_position();
+
+        // Enter the alternate screen and draw over it
+        screen.feed(b"\x1b[?1049h");
+        assert!(!screen.contains("main line"));
+        screen.feed(b"\x1b[1;1HALT CONTENT\x1b[3;5Hmore");
+        assert!(screen.contains("ALT CONTENT"));
+        assert_eq!(screen.main_screen_contents(), main_before);
+
+        // Leave it again
+        screen.feed(b"\x1b[?1049l");
+        assert_eq!(screen.contents(), main_before);
+        assert_eq!(screen.main_screen_contents(), main_before);
+        assert_eq!(screen.cursor_position(), cursor_before);
+        assert!(screen.alt_screen_contents().contains("ALT CONTENT"));
     }
 
     #[test]
@@ -1661,6 +6552,26 @@ mod tests {
         assert_eq!(regions[1].height, 80);
     }
 
+    #[test]
+    fn test_sixel_cleared_by_erase() {
+        let mut screen = ScreenState::new(80, 24);
+        let sixel = b"\x1b[5;5H\x1bPq\"1;1;80;60#0~\x1b\\";
+
+        screen.feed(sixel);
+        screen.feed(b"\x1b[2J");
+        assert!(screen.sixel_regions().is_empty());
+
+        // Printing, even right after the image, leaves it in place
+        screen.feed(sixel);
+        screen.feed(b"after\x1b[1;1Houtside\x1b[8;10Hx");
+        assert_eq!(screen.sixel_regions().len(), 1);
+
+        // Erasing a line that crosses the image also removes it
+        screen.feed(sixel);
+        screen.feed(b"\x1b[10;1H\x1b[2K");
+        assert!(screen.sixel_regions().is_empty());
+    }
+
     #[test]
     fn test_sixel_without_raster_attributes() {
         let mut screen = ScreenState::new(80, 24);
@@ -75,10 +75,9 @@
 //! # }
 //! ```
 
-use crate::{
-    error::{Result, TermTestError},
-    graphics::{GraphicsCapture as UnifiedGraphicsCapture, GraphicsProtocol, GraphicsRegion},
-};
+use std::{collections::HashSet, ops::Range};
+
+use crate::error::{Result, TermTestError};
 
 /// Represents a captured Sixel sequence with position information.
 ///
@@ -90,6 +89,7 @@ use crate::{
 /// - `raw`: The raw Sixel escape sequence bytes (including DCS wrapper)
 /// - `position`: Cursor position when the Sixel was rendered (row, col) in terminal cells
 /// - `bounds`: Calculated bounding rectangle (row, col, width, height) in terminal cells
+/// - `stream_range`: Location of the sequence in the raw output stream, when known
 ///
 /// # Example
 ///
@@ -114,6 +114,16 @@ pub struct SixelSequence {
     pub position: (u16, u16),
     /// Calculated bounding rectangle (row, col, width, height).
     pub bounds: (u16, u16, u16, u16),
+    /// Byte range of the complete escape sequence within the output stream.
+    ///
+    /// For sequences captured from a [`ScreenState`](crate::ScreenState) this
+    /// counts every byte fed to that screen since it was created or reset (see
+    /// [`ScreenState::sixel_stream_range`](crate::ScreenState::sixel_stream_range)).
+    /// A harness feeds all PTY output to its screen, so this indexes
+    /// [`TuiTestHarness::recorded_output`](crate::TuiTestHarness::recorded_output)
+    /// only if recording started before the first output was read and was
+    /// never stopped.
+    pub stream_range: Option<Range<usize>>,
 }
 
 impl SixelSequence {
@@ -125,7 +135,12 @@ impl SixelSequence {
     /// * `position` - Cursor position when rendered
     /// * `bounds` - Bounding rectangle (row, col, width, height)
     pub fn new(raw: Vec<u8>, position: (u16, u16), bounds: (u16, u16, u16, u16)) -> Self {
-        Self { raw, position, bounds }
+        Self {
+            raw,
+            position,
+            bounds,
+            stream_range: None,
+        }
     }
 
     /// Checks if this Sixel is completely within the specified area.
@@ -201,6 +216,86 @@ impl SixelSequence {
     }
 }
 
+/// Builder for Sixel test fixtures.
+///
+/// Produces both the raw bytes to feed into a terminal (a cursor-positioning
+/// sequence followed by a complete DCS Sixel sequence with raster attributes)
+/// and the [`SixelSequence`] that [`SixelCapture::from_screen_state`] is expected
+/// to report for them. This replaces hand-written escape byte strings in tests.
+///
+/// The `raw` field of the produced sequence holds the DCS payload (everything
+/// between `ESC P q` and `ESC \`), matching what the screen state captures.
+///
+/// # Example
+///
+/// ```rust
+/// use ratatui_testlib::{
+///     sixel::{SixelCapture, SixelSequenceBuilder},
+///     ScreenState,
+/// };
+///
+/// let (bytes, expected) = SixelSequenceBuilder::new().at(10, 10).size(200, 150).build();
+///
+/// let mut screen = ScreenState::new(80, 40);
+/// screen.feed(&bytes);
+///
+/// let capture = SixelCapture::from_screen_state(&screen);
+/// assert_eq!(capture.sequences(), &[expected]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SixelSequenceBuilder {
+    position: (u16, u16),
+    size: (u32, u32),
+}
+
+impl SixelSequenceBuilder {
+    /// Creates a builder for a zero-sized Sixel at (0, 0).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the cursor position (row, col) where the Sixel is rendered, 0-indexed.
+    pub fn at(mut self, row: u16, col: u16) -> Self {
+        self.position = (row, col);
+        self
+    }
+
+    /// Sets the image dimensions in pixels, written as raster attributes.
+    pub fn size(mut self, width_px: u32, height_px: u32) -> Self {
+        self.size = (width_px, height_px);
+        self
+    }
+
+    /// Builds the fixture.
+    ///
+    /// # Returns
+    ///
+    /// A tuple of (bytes to feed into a terminal, expected [`SixelSequence`]).
+    /// Bounds are converted to cells using 8 pixels per column and 6 pixels
+    /// per row, rounding up. The expected `stream_range` assumes the bytes are
+    /// fed into a fresh screen.
+    pub fn build(self) -> (Vec<u8>, SixelSequence) {
+        let (row, col) = self.position;
+        let (width_px, height_px) = self.size;
+
+        let payload = format!("\"1;1;{};{}#0;2;100;100;100#0~", width_px, height_px).into_bytes();
+
+        let mut bytes = format!("\x1b[{};{}H", row + 1, col + 1).into_bytes();
+        let dcs_start = bytes.len();
+        bytes.extend_from_slice(b"\x1bPq");
+        bytes.extend_from_slice(&payload);
+        bytes.extend_from_slice(b"\x1b\\");
+
+        let width_cells = width_px.div_ceil(8) as u16;
+        let height_cells = height_px.div_ceil(6) as u16;
+        let mut sequence =
+            SixelSequence::new(payload, (row, col), (row, col, width_cells, height_cells));
+        sequence.stream_range = Some(dcs_start..bytes.len());
+
+        (bytes, sequence)
+    }
+}
+
 /// Captures all Sixel sequences from terminal output.
 ///
 /// This type provides methods for querying and validating Sixel graphics
@@ -285,13 +380,22 @@ impl SixelCapture {
     ///
     /// * `screen` - Reference to the ScreenState containing Sixel information
     pub fn from_screen_state(screen: &crate::screen::ScreenState) -> Self {
-        // Use the unified graphics capture and filter for Sixel
-        let unified = UnifiedGraphicsCapture::from_screen_state(screen);
-        let sequences = unified
-            .by_protocol(GraphicsProtocol::Sixel)
+        let sequences = screen
+            .sixel_regions()
             .iter()
-            .map(|region: &&GraphicsRegion| {
-                SixelSequence::new(region.raw_data.clone(), region.position, region.bounds)
+            .enumerate()
+            .map(|(index, region)| {
+                // Bounds use 8x6 pixel cells, rounding up, like the unified capture
+                let position = (region.start_row, region.start_col);
+                let width_cells = region.width.div_ceil(8) as u16;
+                let height_cells = region.height.div_ceil(6) as u16;
+                let mut sequence = SixelSequence::new(
+                    region.data.clone(),
+                    position,
+                    (position.0, position.1, width_cells, height_cells),
+                );
+                sequence.stream_range = screen.sixel_stream_range(index);
+                sequence
             })
             .collect();
 
@@ -452,6 +556,182 @@ impl SixelCapture {
     pub fn differs_from(&self, other: &SixelCapture) -> bool {
         self.sequences != other.sequences
     }
+
+    /// Classifies how the images changed between this capture and `other`.
+    ///
+    /// Sequences are paired up in stages. Images identical in both captures
+    /// (same position, bounds and data) are unchanged and left out. Of the
+    /// rest, an image whose data reappears at a different position has
+    /// *moved*, and an image at the same position with different bounds has
+    /// been *resized*. Whatever remains is *removed* (only in `self`) or
+    /// *added* (only in `other`).
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The later capture to compare against
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{sixel::SixelCapture, ScreenState, SixelSequenceBuilder};
+    ///
+    /// let (before, _) = SixelSequenceBuilder::new().at(1, 1).size(80, 60).build();
+    /// let (after, _) = SixelSequenceBuilder::new().at(1, 20).size(80, 60).build();
+    ///
+    /// let mut screen1 = ScreenState::new(80, 24);
+    /// screen1.feed(&before);
+    /// let mut screen2 = ScreenState::new(80, 24);
+    /// screen2.feed(&after);
+    ///
+    /// let diff = SixelCapture::from_screen_state(&screen1)
+    ///     .diff(&SixelCapture::from_screen_state(&screen2));
+    /// assert_eq!(diff.moved.len(), 1);
+    /// assert!(diff.added.is_empty() && diff.removed.is_empty());
+    /// ```
+    pub fn diff(&self, other: &SixelCapture) -> SixelDiff {
+        fn same(a: &SixelSequence, b: &SixelSequence) -> bool {
+            a.position == b.position && a.bounds == b.bounds && a.raw == b.raw
+        }
+
+        let mut before: Vec<&SixelSequence> = self.sequences.iter().collect();
+        let mut after: Vec<&SixelSequence> = other.sequences.iter().collect();
+
+        // Drop unchanged images
+        before.retain(|old| match after.iter().position(|new| same(old, new)) {
+            Some(index) => {
+                after.remove(index);
+                false
+            }
+            None => true,
+        });
+
+        let mut diff = SixelDiff::default();
+        before.retain(|old| {
+            if let Some(index) = after.iter().position(|new| new.raw == old.raw) {
+                let new = after.remove(index);
+                diff.moved.push(((*old).clone(), new.clone()));
+                false
+            } else if let Some(index) = after.iter().position(|new| new.position == old.position) {
+                let new = after.remove(index);
+                diff.resized.push(((*old).clone(), new.clone()));
+                false
+            } else {
+                true
+            }
+        });
+
+        diff.removed = before.into_iter().cloned().collect();
+        diff.added = after.into_iter().cloned().collect();
+        diff
+    }
+
+    /// Counts distinct images, ignoring redraws of the same image.
+    ///
+    /// Applications that redraw every frame emit the same Sixel repeatedly, so
+    /// [`sequences()`](Self::sequences) can contain duplicates. Two sequences are
+    /// the same image when their position, bounds and data are all equal; where
+    /// in the stream they occurred is not compared.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{sixel::SixelCapture, ScreenState, SixelSequenceBuilder};
+    ///
+    /// let (bytes, _) = SixelSequenceBuilder::new().at(2, 4).size(80, 60).build();
+    /// let mut screen = ScreenState::new(80, 24);
+    /// screen.feed(&bytes);
+    /// screen.feed(&bytes); // next frame redraws the same image
+    ///
+    /// let capture = SixelCapture::from_screen_state(&screen);
+    /// assert_eq!(capture.sequences().len(), 2);
+    /// assert_eq!(capture.unique_count(), 1);
+    /// ```
+    pub fn unique_count(&self) -> usize {
+        self.sequences
+            .iter()
+            .map(|seq| (seq.position, seq.bounds, seq.raw.as_slice()))
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Renders an ASCII map of the screen showing which cells each Sixel covers.
+    ///
+    /// Each line of the map is one screen row. Blank cells are `.`, cells
+    /// covered by a single Sixel show its index in [`sequences()`](Self::sequences)
+    /// (`0`-`9`, then `a`-`z`, then `#`), and cells covered by more than one
+    /// Sixel are `*`. Print it when an image-layout assertion fails to see at a
+    /// glance where the images landed.
+    ///
+    /// Coverage is computed from each image's raster attributes (its size in
+    /// pixels) and the given cell size; sequences without raster attributes
+    /// fall back to their `bounds`. Cells outside the screen are clipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `screen_width` - Screen width in columns
+    /// * `screen_height` - Screen height in rows
+    /// * `cell_px` - Size of one cell in pixels as (width, height)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::{sixel::SixelCapture, ScreenState, SixelSequenceBuilder};
+    ///
+    /// let (bytes, _) = SixelSequenceBuilder::new().at(1, 2).size(24, 12).build();
+    /// let mut screen = ScreenState::new(8, 4);
+    /// screen.feed(&bytes);
+    ///
+    /// let capture = SixelCapture::from_screen_state(&screen);
+    /// assert_eq!(
+    ///     capture.layout_map(8, 4, (8, 6)),
+    ///     "........\n..000...\n..000...\n........"
+    /// );
+    /// ```
+    pub fn layout_map(&self, screen_width: u16, screen_height: u16, cell_px: (u16, u16)) -> String {
+        const LABELS: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+        let mut grid = vec![vec![b'.'; screen_width as usize]; screen_height as usize];
+        for (index, seq) in self.sequences.iter().enumerate() {
+            let (row, col) = seq.position;
+            let (width, height) = match raster_size(&seq.raw) {
+                Some((w, h)) if cell_px.0 > 0 && cell_px.1 > 0 => (
+                    w.div_ceil(u32::from(cell_px.0)) as u16,
+                    h.div_ceil(u32::from(cell_px.1)) as u16,
+                ),
+                _ => (seq.bounds.2, seq.bounds.3),
+            };
+            let label = LABELS.get(index).copied().unwrap_or(b'#');
+
+            let row_end = row.saturating_add(height).min(screen_height);
+            let col_end = col.saturating_add(width).min(screen_width);
+            for r in row..row_end {
+                for c in col..col_end {
+                    let cell = &mut grid[r as usize][c as usize];
+                    *cell = if *cell == b'.' { label } else { b'*' };
+                }
+            }
+        }
+
+        grid.into_iter()
+            .map(|line| String::from_utf8(line).expect("map is ASCII"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Extracts the image size in pixels from Sixel raster attributes (`"Pan;Pad;Ph;Pv`).
+fn raster_size(data: &[u8]) -> Option<(u32, u32)> {
+    let start = data.iter().position(|&b| b == b'"')? + 1;
+    let attrs: Vec<u32> = data[start..]
+        .split(|&b| !b.is_ascii_digit() && b != b';')
+        .next()?
+        .split(|&b| b == b';')
+        .map(|n| std::str::from_utf8(n).ok()?.parse().ok())
+        .collect::<Option<_>>()?;
+    match attrs[..] {
+        [_, _, width, height, ..] => Some((width, height)),
+        _ => None,
+    }
 }
 
 impl Default for SixelCapture {
@@ -460,6 +740,31 @@ impl Default for SixelCapture {
     }
 }
 
+/// How the images changed between two captures.
+///
+/// Returned by [`SixelCapture::diff`]. Paired entries are `(before, after)`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SixelDiff {
+    /// Images only present in the later capture.
+    pub added: Vec<SixelSequence>,
+    /// Images only present in the earlier capture.
+    pub removed: Vec<SixelSequence>,
+    /// Images with the same data drawn at a different position.
+    pub moved: Vec<(SixelSequence, SixelSequence)>,
+    /// Images at the same position whose bounds changed.
+    pub resized: Vec<(SixelSequence, SixelSequence)>,
+}
+
+impl SixelDiff {
+    /// Returns `true` if no image changed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.moved.is_empty()
+            && self.resized.is_empty()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -500,4 +805,108 @@ mod tests {
         assert_eq!(capture.sequences_in_area(area).len(), 1);
         assert_eq!(capture.sequences_outside_area(area).len(), 1);
     }
+
+    #[test]
+    fn test_unique_count() {
+        let (image, _) = SixelSequenceBuilder::new().at(1, 1).size(16, 12).build();
+        let (moved, _) = SixelSequenceBuilder::new().at(5, 1).size(16, 12).build();
+
+        let mut screen = crate::screen::ScreenState::new(80, 24);
+        screen.feed(&image);
+        screen.feed(&image);
+
+        let capture = SixelCapture::from_screen_state(&screen);
+        assert_eq!(capture.sequences().len(), 2);
+        assert_eq!(capture.unique_count(), 1);
+
+        screen.feed(&moved);
+        let capture = SixelCapture::from_screen_state(&screen);
+        assert_eq!(capture.sequences().len(), 3);
+        assert_eq!(capture.unique_count(), 2);
+        assert_eq!(SixelCapture::new().unique_count(), 0);
+    }
+
+    #[test]
+    fn test_diff() {
+        let (logo, _) = SixelSequenceBuilder::new().at(0, 0).size(16, 12).build();
+        let (logo_moved, _) = SixelSequenceBuilder::new().at(0, 30).size(16, 12).build();
+        let (preview, _) = SixelSequenceBuilder::new().at(5, 5).size(80, 60).build();
+        let (preview_resized, _) = SixelSequenceBuilder::new().at(5, 5).size(160, 120).build();
+        let (static_img, _) = SixelSequenceBuilder::new().at(10, 40).size(8, 6).build();
+        let (removed, _) = SixelSequenceBuilder::new().at(15, 0).size(24, 18).build();
+        let (added, _) = SixelSequenceBuilder::new().at(20, 60).size(40, 30).build();
+
+        let mut screen1 = crate::screen::ScreenState::new(80, 24);
+        for bytes in [&logo, &preview, &static_img, &removed] {
+            screen1.feed(bytes);
+        }
+        let mut screen2 = crate::screen::ScreenState::new(80, 24);
+        for bytes in [&preview_resized, &static_img, &logo_moved, &added] {
+            screen2.feed(bytes);
+        }
+
+        let before = SixelCapture::from_screen_state(&screen1);
+        let after = SixelCapture::from_screen_state(&screen2);
+        let diff = before.diff(&after);
+
+        assert_eq!(diff.moved.len(), 1);
+        assert_eq!(diff.moved[0].0.position, (0, 0));
+        assert_eq!(diff.moved[0].1.position, (0, 30));
+
+        assert_eq!(diff.resized.len(), 1);
+        assert_eq!(diff.resized[0].0.bounds, (5, 5, 10, 10));
+        assert_eq!(diff.resized[0].1.bounds, (5, 5, 20, 20));
+
+        assert_eq!(diff.removed.len(), 1);
+        assert_eq!(diff.removed[0].position, (15, 0));
+        assert_eq!(diff.added.len(), 1);
+        assert_eq!(diff.added[0].position, (20, 60));
+
+        assert!(!diff.is_empty());
+        assert!(before.diff(&before).is_empty());
+    }
+
+    #[test]
+    fn test_layout_map() {
+        let (first, _) = SixelSequenceBuilder::new().at(0, 1).size(16, 12).build();
+        let (second, _) = SixelSequenceBuilder::new().at(1, 2).size(24, 6).build();
+        let (offscreen, _) = SixelSequenceBuilder::new().at(3, 8).size(40, 40).build();
+
+        let mut screen = crate::screen::ScreenState::new(10, 4);
+        screen.feed(&first);
+        screen.feed(&second);
+        screen.feed(&offscreen);
+
+        let capture = SixelCapture::from_screen_state(&screen);
+        assert_eq!(
+            capture.layout_map(10, 4, (8, 6)),
+            ".00.......\n.0*11.....\n..........\n........22"
+        );
+
+        // A larger cell size shrinks the coverage
+        assert_eq!(
+            capture.layout_map(10, 4, (16, 12)),
+            ".0........\n..11......\n..........\n........22"
+        );
+        assert_eq!(SixelCapture::new().layout_map(3, 2, (8, 6)), "...\n...");
+    }
+
+    #[test]
+    fn test_sixel_sequence_builder_round_trip() {
+        let (bytes, expected) = SixelSequenceBuilder::new()
+            .at(10, 10)
+            .size(200, 150)
+            .build();
+        assert_eq!(expected.position, (10, 10));
+        assert_eq!(expected.bounds, (10, 10, 25, 25));
+        assert!(bytes.starts_with(b"\x1b[11;11H\x1bPq"));
+        assert!(bytes.ends_with(b"\x1b\\"));
+        assert_eq!(expected.stream_range, Some(8..bytes.len()));
+
+        let mut screen = crate::screen::ScreenState::new(80, 40);
+        screen.feed(&bytes);
+
+        let capture = SixelCapture::from_screen_state(&screen);
+        assert_eq!(capture.sequences(), &[expected]);
+    }
 }
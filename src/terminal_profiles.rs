@@ -692,6 +692,149 @@ impl Default for TerminalProfile {
     }
 }
 
+/// What the emulated terminal reports itself to be.
+///
+/// Applications often probe the terminal before choosing which features to
+/// use. This controls the replies to those identification queries:
+///
+/// - Primary Device Attributes (`ESC [ c`) lists capabilities; attribute 4
+///   means Sixel graphics are available.
+/// - Secondary Device Attributes (`ESC [ > c`) reports the terminal type and
+///   firmware version.
+/// - DECRQSS (`ESC P $ q Pt ESC \`) reports the current value of a setting.
+///
+/// Pass an identity to
+/// [`TuiTestHarness::with_terminal_identity`](crate::TuiTestHarness::with_terminal_identity)
+/// to test an application against several terminals.
+///
+/// # Example
+///
+/// ```rust
+/// use ratatui_testlib::TerminalIdentity;
+///
+/// let identity = TerminalIdentity::vt340().with_setting("m", "0m");
+///
+/// assert!(identity.supports_sixel());
+/// assert_eq!(identity.decrqss_response("m"), "\x1bP1$r0m\x1b\\");
+/// assert_eq!(identity.decrqss_response("r"), "\x1bP0$r\x1b\\");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalIdentity {
+    /// Attributes reported in reply to Primary DA, conformance level first.
+    pub device_attributes: Vec<u16>,
+    /// Terminal type reported in reply to Secondary DA.
+    pub terminal_type: u16,
+    /// Firmware version reported in reply to Secondary DA.
+    pub version: u16,
+    /// DECRQSS replies keyed by the requested setting, such as `"m"` or `" q"`.
+    ///
+    /// Requests for settings not listed here are answered as invalid.
+    pub settings: HashMap<String, String>,
+}
+
+impl TerminalIdentity {
+    /// Creates an identity reporting the given Primary DA attributes.
+    ///
+    /// The terminal type and version start at 0 and no DECRQSS settings are
+    /// known.
+    ///
+    /// # Arguments
+    ///
+    /// * `device_attributes` - Attributes for the Primary DA reply, conformance
+    ///   level first (for example `[62, 22]` for a VT220-class color terminal)
+    pub fn new(device_attributes: Vec<u16>) -> Self {
+        Self {
+            device_attributes,
+            terminal_type: 0,
+            version: 0,
+            settings: HashMap::new(),
+        }
+    }
+
+    /// A DEC VT100 with the advanced video option.
+    pub fn vt100() -> Self {
+        Self::new(vec![1, 2])
+    }
+
+    /// A DEC VT340 with Sixel graphics and color.
+    pub fn vt340() -> Self {
+        Self::new(vec![63, 1, 2, 3, 4, 6, 8, 9, 15, 16, 29]).with_version(19, 10)
+    }
+
+    /// xterm, reporting VT220 conformance with ANSI color and no Sixel.
+    pub fn xterm() -> Self {
+        Self::new(vec![62, 22]).with_version(41, 390)
+    }
+
+    /// Sets the terminal type and version reported in reply to Secondary DA.
+    pub fn with_version(mut self, terminal_type: u16, version: u16) -> Self {
+        self.terminal_type = terminal_type;
+        self.version = version;
+        self
+    }
+
+    /// Adds the DECRQSS reply for a setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The requested setting, as sent after `ESC P $ q`
+    /// * `reply` - The value reported back, as sent after `ESC P 1 $ r`
+    pub fn with_setting(mut self, query: impl Into<String>, reply: impl Into<String>) -> Self {
+        self.settings.insert(query.into(), reply.into());
+        self
+    }
+
+    /// Returns whether the Primary DA reply advertises Sixel graphics.
+    pub fn supports_sixel(&self) -> bool {
+        self.device_attributes.iter().skip(1).any(|&attr| attr == 4)
+    }
+
+    /// Returns the reply to Primary DA (`ESC [ c`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::TerminalIdentity;
+    ///
+    /// assert_eq!(TerminalIdentity::vt100().primary_da_response(), "\x1b[?1;2c");
+    /// ```
+    pub fn primary_da_response(&self) -> String {
+        let attrs: Vec<String> = self.device_attributes.iter().map(u16::to_string).collect();
+        format!("\x1b[?{}c", attrs.join(";"))
+    }
+
+    /// Returns the reply to Secondary DA (`ESC [ > c`).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ratatui_testlib::TerminalIdentity;
+    ///
+    /// assert_eq!(TerminalIdentity::xterm().secondary_da_response(), "\x1b[>41;390;0c");
+    /// ```
+    pub fn secondary_da_response(&self) -> String {
+        format!("\x1b[>{};{};0c", self.terminal_type, self.version)
+    }
+
+    /// Returns the reply to a DECRQSS request for `query`.
+    ///
+    /// Known settings get a valid reply (`ESC P 1 $ r value ESC \`), anything
+    /// else an invalid one (`ESC P 0 $ r ESC \`).
+    pub fn decrqss_response(&self, query: &str) -> String {
+        match self.settings.get(query) {
+            Some(reply) => format!("\x1bP1$r{}\x1b\\", reply),
+            None => "\x1bP0$r\x1b\\".to_string(),
+        }
+    }
+}
+
+impl Default for TerminalIdentity {
+    /// Returns the xterm identity.
+    fn default() -> Self {
+        Self::xterm()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -852,4 +995,23 @@ mod tests {
         assert!(maximum.supports(Feature::KittyGraphics));
         assert!(maximum.supports(Feature::ITerm2Images));
     }
+
+    #[test]
+    fn test_terminal_identity_responses() {
+        let vt340 = TerminalIdentity::vt340();
+        assert!(vt340.supports_sixel());
+        assert_eq!(vt340.primary_da_response(), "\x1b[?63;1;2;3;4;6;8;9;15;16;29c");
+        assert_eq!(vt340.secondary_da_response(), "\x1b[>19;10;0c");
+
+        let xterm = TerminalIdentity::default();
+        assert_eq!(xterm, TerminalIdentity::xterm());
+        assert!(!xterm.supports_sixel());
+
+        // The conformance level is not an attribute
+        assert!(!TerminalIdentity::new(vec![4]).supports_sixel());
+
+        let identity = TerminalIdentity::vt100().with_setting(" q", "2 q");
+        assert_eq!(identity.decrqss_response(" q"), "\x1bP1$r2 q\x1b\\");
+        assert_eq!(identity.decrqss_response("m"), "\x1bP0$r\x1b\\");
+    }
 }